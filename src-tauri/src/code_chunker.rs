@@ -0,0 +1,194 @@
+// src-tauri/src/code_chunker.rs
+// AST-aware chunking of source files for the vector database
+
+use crate::tree_sitter_parser::Symbol;
+use crate::vector_db::CodeChunk;
+
+/// Symbols longer than this are split into overlapping windows
+pub const MAX_CHUNK_LINES: usize = 120;
+
+/// Lines shared between consecutive windows of an oversized chunk
+pub const CHUNK_OVERLAP_LINES: usize = 20;
+
+/// A contiguous line range (1-based, inclusive) to be turned into a chunk
+#[derive(Debug, Clone, PartialEq)]
+struct ChunkRange {
+    start: usize,
+    end: usize,
+    symbol_name: Option<String>,
+    chunk_type: String,
+}
+
+/// Split a file into chunks, one per top-level symbol
+///
+/// Nested symbols (methods inside a class or impl) stay inside their parent's
+/// chunk. Code between symbols becomes `gap_chunk_type` chunks, and anything
+/// longer than `MAX_CHUNK_LINES` is split with `CHUNK_OVERLAP_LINES` overlap.
+/// Embeddings are left empty so `VectorDB::upsert` can fill them in one batch.
+pub fn chunk_file(
+    file_path: &str,
+    content: &str,
+    symbols: &[Symbol],
+    gap_chunk_type: &str,
+) -> Vec<CodeChunk> {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    plan_ranges(lines.len(), symbols, gap_chunk_type)
+        .into_iter()
+        .flat_map(split_oversized)
+        .filter_map(|range| {
+            let text = lines[range.start - 1..range.end].join("\n");
+            if text.trim().is_empty() {
+                return None;
+            }
+            Some(CodeChunk {
+                id: format!("{}:{}:{}", file_path, range.start, range.end),
+                file_path: file_path.to_string(),
+                content: text,
+                embedding: Vec::new(),
+                symbol_name: range.symbol_name,
+                chunk_type: range.chunk_type,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+/// Turn a symbol kind ("function", "impl") into a chunk type ("Function", "Impl")
+pub fn chunk_type_for_kind(kind: &str) -> String {
+    let mut chars = kind.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Symbol".to_string(),
+    }
+}
+
+/// Order top-level symbol ranges and fill the gaps between them
+fn plan_ranges(total_lines: usize, symbols: &[Symbol], gap_chunk_type: &str) -> Vec<ChunkRange> {
+    let mut sorted: Vec<&Symbol> = symbols
+        .iter()
+        .filter(|s| s.line >= 1 && s.line <= total_lines)
+        .collect();
+    // Outer symbols first when two start on the same line
+    sorted.sort_by(|a, b| a.line.cmp(&b.line).then(b.end_line.cmp(&a.end_line)));
+
+    let mut ranges = Vec::new();
+    let mut next_free = 1;
+
+    for symbol in sorted {
+        if symbol.line < next_free {
+            // Nested in (or overlapping) a symbol we already took
+            continue;
+        }
+        let end = symbol.end_line.clamp(symbol.line, total_lines);
+
+        if symbol.line > next_free {
+            push_gap(&mut ranges, next_free, symbol.line - 1, gap_chunk_type);
+        }
+        ranges.push(ChunkRange {
+            start: symbol.line,
+            end,
+            symbol_name: Some(symbol.name.clone()),
+            chunk_type: chunk_type_for_kind(&symbol.kind),
+        });
+        next_free = end + 1;
+    }
+
+    if next_free <= total_lines {
+        push_gap(&mut ranges, next_free, total_lines, gap_chunk_type);
+    }
+
+    ranges
+}
+
+fn push_gap(ranges: &mut Vec<ChunkRange>, start: usize, end: usize, chunk_type: &str) {
+    ranges.push(ChunkRange {
+        start,
+        end,
+        symbol_name: None,
+        chunk_type: chunk_type.to_string(),
+    });
+}
+
+/// Split a range into overlapping windows if it is too long
+fn split_oversized(range: ChunkRange) -> Vec<ChunkRange> {
+    let len = range.end - range.start + 1;
+    if len <= MAX_CHUNK_LINES {
+        return vec![range];
+    }
+
+    let step = MAX_CHUNK_LINES - CHUNK_OVERLAP_LINES;
+    let mut windows = Vec::new();
+    let mut start = range.start;
+    loop {
+        let end = (start + MAX_CHUNK_LINES - 1).min(range.end);
+        windows.push(ChunkRange {
+            start,
+            end,
+            symbol_name: range.symbol_name.clone(),
+            chunk_type: range.chunk_type.clone(),
+        });
+        if end == range.end {
+            break;
+        }
+        start += step;
+    }
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, kind: &str, line: usize, end_line: usize) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line,
+            column: 0,
+            end_line,
+            end_column: 0,
+            signature: None,
+            documentation: None,
+            is_exported: false,
+            parent: None,
+            metrics: None,
+        }
+    }
+
+    #[test]
+    fn test_chunks_follow_top_level_symbols() {
+        let content = "use std::fmt;\n\nstruct A;\n\nimpl A {\n    fn f() {}\n}\n";
+        let symbols = vec![
+            symbol("A", "struct", 3, 3),
+            symbol("A", "impl", 5, 7),
+            symbol("f", "function", 6, 6),
+        ];
+
+        let chunks = chunk_file("src/a.rs", content, &symbols, "Module");
+        let ids: Vec<&str> = chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["src/a.rs:1:2", "src/a.rs:3:3", "src/a.rs:5:7"]);
+        assert_eq!(chunks[2].chunk_type, "Impl");
+        assert_eq!(chunks[2].symbol_name.as_deref(), Some("A"));
+        assert_eq!(chunks[0].symbol_name, None);
+    }
+
+    #[test]
+    fn test_oversized_symbol_is_split_with_overlap() {
+        let content: String = (1..=250).map(|i| format!("line {}\n", i)).collect();
+        let symbols = vec![symbol("big", "function", 1, 250)];
+
+        let chunks = chunk_file("big.ts", &content, &symbols, "Module");
+        let ids: Vec<&str> = chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["big.ts:1:120", "big.ts:101:220", "big.ts:201:250"]);
+        assert!(chunks.iter().all(|c| c.symbol_name.as_deref() == Some("big")));
+    }
+}
@@ -9,6 +9,8 @@ use serde_json::json;
 use log::{info, error};
 use tauri::{AppHandle, Manager, Emitter};
 
+use crate::devcontainer::{DevContainerSession, ExecOutput};
+
 // --------------------
 // SYSTEM UTILITIES
 // --------------------
@@ -109,18 +111,28 @@ fn scan_dir(dir: &Path, files: &mut Vec<String>) {
         }
     }
 }
+/// Directories skipped by workspace walks (file tree, indexer)
+pub const IGNORED_DIRS: &[&str] = &["node_modules", ".git", "dist", "build", "target", ".next", "venv", ".venv"];
+
+/// True if any component of `path` below `root` is one of `IGNORED_DIRS`
+pub fn is_ignored_path(root: &Path, path: &Path) -> bool {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.components().any(|c| {
+        c.as_os_str().to_str().map(|name| IGNORED_DIRS.contains(&name)).unwrap_or(false)
+    })
+}
+
 #[tauri::command]
 pub async fn get_all_files(path: String) -> Result<Vec<String>, String> {
     use walkdir::WalkDir;
     
-    let ignored = vec!["node_modules", ".git", "dist", "build", "target", ".next", "venv", ".venv"];
     let mut files = Vec::new();
     
     for entry in WalkDir::new(&path)
         .into_iter()
         .filter_entry(|e: &walkdir::DirEntry| {
             let name = e.file_name().to_str().unwrap_or("");
-            !ignored.contains(&name)
+            !IGNORED_DIRS.contains(&name)
         })
         .filter_map(|e: Result<walkdir::DirEntry, walkdir::Error>| e.ok()) 
     {
@@ -526,7 +538,7 @@ const ALLOWED_COMMAND_LIST: &[&str] = &[
 ];
 
 #[tauri::command]
-pub fn execute_terminal_command(command: String, path: String, _app: AppHandle) -> Result<serde_json::Value, String> {
+pub async fn execute_terminal_command(command: String, path: String, _app: AppHandle) -> Result<serde_json::Value, String> {
     info!("🔧 Komut çalıştırılıyor: {} (dizin: {})", command, path);
     
     // Command validation
@@ -561,9 +573,24 @@ pub fn execute_terminal_command(command: String, path: String, _app: AppHandle)
         return Err("Komut boş olamaz".to_string());
     }
 
+    // Container modundaki workspace: komut dev container içinde çalışır
+    if let Some(session) = DevContainerSession::find(Path::new(&path)).await {
+        let output = session.output("sh", &["-c".to_string(), command.clone()], Path::new(&path)).await.map_err(|e| {
+            error!("❌ Komut çalıştırma hatası: {}", e);
+            format!("Komut çalıştırılamadı: {}", e)
+        })?;
+
+        return Ok(json!({
+            "success": output.exit_code == 0,
+            "stdout": output.stdout,
+            "stderr": output.stderr,
+            "exit_code": output.exit_code
+        }));
+    }
+
     #[cfg(target_os = "windows")]
     {
-        let mut cmd = Command::new("cmd");
+        let mut cmd = tokio::process::Command::new("cmd");
         cmd.arg("/C");
         cmd.arg(&command);
         
@@ -571,7 +598,7 @@ pub fn execute_terminal_command(command: String, path: String, _app: AppHandle)
             cmd.current_dir(&path);
         }
         
-        let output = cmd.output().map_err(|e| {
+        let output = cmd.output().await.map_err(|e| {
             error!("❌ Komut çalıştırma hatası: {}", e);
             format!("Komut çalıştırılamadı: {}", e)
         })?;
@@ -590,7 +617,7 @@ pub fn execute_terminal_command(command: String, path: String, _app: AppHandle)
 
     #[cfg(not(target_os = "windows"))]
     {
-        let mut cmd = Command::new("sh");
+        let mut cmd = tokio::process::Command::new("sh");
         cmd.arg("-c");
         cmd.arg(&command);
         
@@ -598,7 +625,7 @@ pub fn execute_terminal_command(command: String, path: String, _app: AppHandle)
             cmd.current_dir(&path);
         }
         
-        let output = cmd.output().map_err(|e| {
+        let output = cmd.output().await.map_err(|e| {
             error!("❌ Komut çalıştırma hatası: {}", e);
             format!("Komut çalıştırılamadı: {}", e)
         })?;
@@ -685,7 +712,7 @@ pub async fn semantic_search(
     // Get VectorDB instance from app state
     let vector_db = app.state::<crate::vector_db::VectorDB>();
     
-    // 1. Generate embedding for query using internal fastembed
+    // 1. Generate embedding for query with the VectorDB's built-in embedder
     let query_embedding = vector_db.generate_embedding(&query).await.map_err(|e| e.to_string())?;
 
     // 2. Query VectorDB with path filter
@@ -714,31 +741,17 @@ pub async fn vector_index_file(
     // Get VectorDB instance
     let vector_db = app.state::<crate::vector_db::VectorDB>();
 
-    // Chunking logic (very basic for now, can be improved)
-    let chunks: Vec<&str> = content.split("\n\n").filter(|s| !s.trim().is_empty()).collect();
-    
-    let mut code_chunks = Vec::new();
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
-    for (i, text_chunk) in chunks.iter().enumerate() {
-        code_chunks.push(crate::vector_db::CodeChunk {
-            id: format!("{}:chunk{}", path, i),
-            file_path: path.clone(),
-            content: text_chunk.to_string(),
-            embedding: vec![], // Will be generated in upsert
-            symbol_name: None,
-            chunk_type: chunk_type.clone(),
-            timestamp,
-        });
-    }
+    // One chunk per top-level symbol, plain windows for everything else
+    let code_chunks = chunk_for_index(&path, &content, &chunk_type).await;
+    let chunk_count = code_chunks.len();
+
+    // Line ranges shift on edits, so drop the file's old chunks first
+    vector_db.delete_file(&path).await?;
 
     match vector_db.upsert(code_chunks).await {
         Ok(_) => {
             info!("✅ {} parse edildi ve indekslendi", path);
-            Ok(json!({ "success": true, "chunks": chunks.len() }))
+            Ok(json!({ "success": true, "chunks": chunk_count }))
         },
         Err(e) => {
             error!("❌ VectorDB upsert hatası [{}]: {}", path, e);
@@ -916,6 +929,25 @@ pub async fn git_pull(repo_path: String) -> Result<(), String> {
 // --------------------
 // PROJECT TESTING
 // --------------------
+/// Output of `program args` in `path`, inside the dev container when the workspace is in container mode
+async fn project_command(path: &str, session: Option<&DevContainerSession>, program: &str, args: &[&str]) -> Result<ExecOutput, String> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    if let Some(session) = session {
+        return session.output(program, &args, Path::new(path)).await;
+    }
+    let output = tokio::process::Command::new(program)
+        .args(&args)
+        .current_dir(path)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(ExecOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code: output.status.code().map(i64::from).unwrap_or(-1),
+    })
+}
+
 #[tauri::command]
 pub async fn test_project(path: String) -> Result<serde_json::Value, String> {
     info!("🧪 Proje test ediliyor: {}", path);
@@ -968,6 +1000,7 @@ pub async fn test_project(path: String) -> Result<serde_json::Value, String> {
         build_command = Some(("python", vec!["-m", "py_compile", "."]));
     }
     
+    let session = DevContainerSession::find(project_path).await;
     let mut all_output = String::new();
     let mut all_errors = String::new();
     let mut overall_success = true;
@@ -976,21 +1009,16 @@ pub async fn test_project(path: String) -> Result<serde_json::Value, String> {
     if let Some((cmd, ref args)) = build_command {
         info!("🔨 Build komutu çalıştırılıyor: {} {:?}", cmd, args);
         
-        let output = Command::new(cmd)
-            .args(args)
-            .current_dir(&path)
-            .output()
+        let output = project_command(&path, session.as_ref(), cmd, args)
+            .await
             .map_err(|e| format!("Build komutu çalıştırılamadı: {}", e))?;
         
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        
-        all_output.push_str(&format!("=== BUILD OUTPUT ===\n{}\n", stdout));
-        if !stderr.is_empty() {
-            all_errors.push_str(&format!("=== BUILD ERRORS ===\n{}\n", stderr));
+        all_output.push_str(&format!("=== BUILD OUTPUT ===\n{}\n", output.stdout));
+        if !output.stderr.is_empty() {
+            all_errors.push_str(&format!("=== BUILD ERRORS ===\n{}\n", output.stderr));
         }
         
-        if !output.status.success() {
+        if output.exit_code != 0 {
             overall_success = false;
             info!("❌ Build başarısız");
         } else {
@@ -1002,21 +1030,16 @@ pub async fn test_project(path: String) -> Result<serde_json::Value, String> {
     if let Some((cmd, ref args)) = test_command {
         info!("🧪 Test komutu çalıştırılıyor: {} {:?}", cmd, args);
         
-        let output = Command::new(cmd)
-            .args(args)
-            .current_dir(&path)
-            .output()
+        let output = project_command(&path, session.as_ref(), cmd, args)
+            .await
             .map_err(|e| format!("Test komutu çalıştırılamadı: {}", e))?;
         
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        
-        all_output.push_str(&format!("=== TEST OUTPUT ===\n{}\n", stdout));
-        if !stderr.is_empty() {
-            all_errors.push_str(&format!("=== TEST ERRORS ===\n{}\n", stderr));
+        all_output.push_str(&format!("=== TEST OUTPUT ===\n{}\n", output.stdout));
+        if !output.stderr.is_empty() {
+            all_errors.push_str(&format!("=== TEST ERRORS ===\n{}\n", output.stderr));
         }
         
-        if !output.status.success() {
+        if output.exit_code != 0 {
             overall_success = false;
             info!("❌ Testler başarısız");
         } else {
@@ -1080,14 +1103,29 @@ pub async fn execute_command(command: String, args: Vec<String>, cwd: Option<Str
         }
     }
 
-    let mut cmd = Command::new(&command);
+    let session = match cwd.as_deref() {
+        Some(dir) => DevContainerSession::find(Path::new(dir)).await,
+        None => None,
+    };
+    if let (Some(session), Some(dir)) = (&session, &cwd) {
+        let output = session.output(&command, &args, Path::new(dir)).await
+            .map_err(|e| format!("Failed to execute command: {}", e))?;
+        return Ok(json!({
+            "success": output.exit_code == 0,
+            "stdout": output.stdout,
+            "stderr": output.stderr,
+            "exit_code": output.exit_code
+        }));
+    }
+
+    let mut cmd = tokio::process::Command::new(&command);
     cmd.args(&args);
     
     if let Some(working_dir) = cwd {
         cmd.current_dir(working_dir);
     }
     
-    let output = cmd.output().map_err(|e| format!("Failed to execute command: {}", e))?;
+    let output = cmd.output().await.map_err(|e| format!("Failed to execute command: {}", e))?;
     
     Ok(json!({
         "success": output.status.success(),
@@ -1215,7 +1253,16 @@ pub async fn download_gguf_model(
 // VECTOR DATABASE COMMANDS (AI-Native IDE Evolution)
 // --------------------
 
-use crate::vector_db::{VectorDB, CodeChunk};
+use crate::vector_db::{VectorDB, CodeChunk, EmbeddingSource};
+
+/// Embed text for the vector DB: an explicit endpoint goes over HTTP,
+/// otherwise the DB's configured embedder (local GGUF or built-in) is used
+async fn embed_for_vector_db(db: &VectorDB, text: String, endpoint: Option<String>) -> Result<Vec<f32>, String> {
+    match endpoint {
+        Some(endpoint) => create_embedding_bge(text, Some(endpoint)).await,
+        None => db.generate_embedding(&text).await,
+    }
+}
 
 /// Initialize vector database
 #[tauri::command]
@@ -1233,17 +1280,84 @@ pub async fn init_vector_db(db_path: String, app: AppHandle) -> Result<(), Strin
     Ok(())
 }
 
+/// Chunk a file along the symbols `TreeSitterParser` finds
+///
+/// Unsupported languages still get plain line-window chunks.
+pub(crate) async fn chunk_for_index(file_path: &str, content: &str, gap_chunk_type: &str) -> Vec<CodeChunk> {
+    let symbols = {
+        let mut parser = TREE_SITTER_PARSER.lock().await;
+        match parser.parse_file(file_path, content) {
+            Ok(analysis) => analysis.symbols,
+            Err(e) => {
+                info!("ℹ️ Sembol çıkarılamadı, satır bazlı bölünüyor [{}]: {}", file_path, e);
+                Vec::new()
+            }
+        }
+    };
+
+    crate::code_chunker::chunk_file(file_path, content, &symbols, gap_chunk_type)
+}
+
+/// Choose how the vector database computes embeddings
+///
+/// `source` is "local" (GGUF embedding model, loaded CPU-only if needed),
+/// "http" (OpenAI-compatible endpoint) or "hashed" (built-in fallback).
+#[tauri::command]
+pub async fn set_vector_embedding_source(
+    source: String,
+    model_path: Option<String>,
+    endpoint: Option<String>,
+    n_ctx: Option<u32>,
+    app: AppHandle
+) -> Result<String, String> {
+    info!("🧩 Embedding kaynağı ayarlanıyor: {}", source);
+
+    let embedding_source = match source.as_str() {
+        "local" => {
+            let model_path = model_path.ok_or("Yerel embedding için model_path gerekli")?;
+            // The pool is keyed by the first shard, as load_gguf_model stores it
+            let model_path = crate::gguf::resolve_split_gguf_path(&model_path);
+            let gguf_state = app.state::<std::sync::Arc<std::sync::Mutex<crate::gguf::GgufState>>>();
+
+            let already_loaded = gguf_state
+                .lock()
+                .map(|guard| guard.models.contains_key(&model_path))
+                .unwrap_or(false);
+            if !already_loaded {
+                crate::gguf::load_gguf_model(gguf_state.clone(), model_path.clone(), n_ctx.unwrap_or(512), 0).await?;
+            }
+
+            EmbeddingSource::Local {
+                model_path,
+                state: gguf_state.inner().clone(),
+            }
+        }
+        "http" => EmbeddingSource::Http {
+            endpoint: endpoint.unwrap_or_else(|| "http://127.0.0.1:1234/v1/embeddings".to_string()),
+        },
+        "hashed" => EmbeddingSource::Hashed,
+        other => return Err(format!("Bilinmeyen embedding kaynağı: {}", other)),
+    };
+
+    let description = embedding_source.describe();
+    let db = app.state::<VectorDB>();
+    db.set_embedding_source(embedding_source);
+
+    info!("✅ Embedding kaynağı: {}", description);
+    Ok(description)
+}
+
 /// Search vector database for similar code chunks
 #[tauri::command]
 pub async fn vector_search(query: String, top_k: u32, endpoint: Option<String>, app: AppHandle) -> Result<Vec<CodeChunk>, String> {
     info!("🔍 Vector search: {} (top_k: {})", query, top_k);
     
-    // Create embedding for query
-    let query_embedding = create_embedding_bge(query, endpoint).await?;
-    
     // Get VectorDB instance from app state
     let db = app.state::<VectorDB>();
     
+    // Create embedding for query
+    let query_embedding = embed_for_vector_db(&db, query, endpoint).await?;
+    
     // Search
     let results = db.query(query_embedding, top_k as usize, None)
         .await
@@ -1261,31 +1375,28 @@ pub async fn index_file_vector(file_path: String, endpoint: Option<String>, app:
     // Read file content
     let content = read_file(file_path.clone())?;
     
-    // Create embedding
-    let embedding = create_embedding_bge(content.clone(), endpoint).await?;
-    
-    // Create chunk
-    let chunk = CodeChunk {
-        id: format!("{}:0:0", file_path),
-        file_path: file_path.clone(),
-        content,
-        embedding,
-        symbol_name: None,
-        chunk_type: "File".to_string(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs(),
-    };
-    
     // Get VectorDB instance from app state
     let db = app.state::<VectorDB>();
     
-    // Upsert to vector DB
-    db.upsert(vec![chunk])
+    // Split into symbol chunks; embeddings are filled in by upsert unless an endpoint is given
+    let mut chunks = chunk_for_index(&file_path, &content, "Module").await;
+    if endpoint.is_some() {
+        for chunk in chunks.iter_mut() {
+            chunk.embedding = embed_for_vector_db(&db, chunk.content.clone(), endpoint.clone()).await?;
+        }
+    }
+    
+    // Replace the file's previous chunks
+    db.delete_file(&file_path)
+        .await
+        .map_err(|e| format!("Vector DB delete hatası: {}", e))?;
+    
+    let chunk_count = chunks.len();
+    db.upsert(chunks)
         .await
         .map_err(|e| format!("Vector DB upsert hatası: {}", e))?;
     
+    info!("🧩 {} chunk yazıldı", chunk_count);
     info!("✅ Dosya indekslendi: {}", file_path);
     Ok(())
 }
@@ -1303,8 +1414,11 @@ pub async fn index_manual_vector(
 ) -> Result<(), String> {
     info!("📇 Manuel veri indeksleniyor: {} ({})", id, chunk_type);
     
+    // Get VectorDB instance from app state
+    let db = app.state::<VectorDB>();
+    
     // Create embedding
-    let embedding = create_embedding_bge(content.clone(), endpoint).await?;
+    let embedding = embed_for_vector_db(&db, content.clone(), endpoint).await?;
     
     // Create chunk
     let chunk = CodeChunk {
@@ -1320,9 +1434,6 @@ pub async fn index_manual_vector(
             .as_secs(),
     };
     
-    // Get VectorDB instance from app state
-    let db = app.state::<VectorDB>();
-    
     // Upsert
     db.upsert(vec![chunk])
         .await
@@ -1354,6 +1465,7 @@ pub async fn delete_file_index(file_path: String, app: AppHandle) -> Result<(),
 // --------------------
 
 use crate::rag_pipeline::{RAGPipeline, QueryIntent, ContextSource};
+use crate::dependency_graph::DependencyGraphState;
 
 /// Analyze query intent
 #[tauri::command]
@@ -1384,7 +1496,21 @@ pub async fn build_rag_context(
     // Build context with database and embedding
     let db = app.state::<VectorDB>();
     let result: Result<(String, Vec<ContextSource>), Box<dyn std::error::Error>> = pipeline.build_context(intent.clone(), &query, &db).await;
-    let (context, sources) = result.map_err(|e| format!("Context build hatası: {}", e))?;
+    let (mut context, mut sources) = result.map_err(|e| format!("Context build hatası: {}", e))?;
+
+    // Files around the vector hits in the import graph
+    if let Some(graph_state) = app.try_state::<DependencyGraphState>() {
+        let hit_files: Vec<String> = sources.iter()
+            .filter(|s| s.source_type == "vector_db")
+            .map(|s| s.file_path.clone())
+            .collect();
+        if let Ok(Some((graph_context, graph_sources))) =
+            graph_state.with_graph(|graph| pipeline.dependency_context(graph, &hit_files))
+        {
+            context.push_str(&graph_context);
+            sources.extend(graph_sources);
+        }
+    }
     
     info!("✅ Context oluşturuldu: {} tokens", RAGPipeline::estimate_tokens(&context));
     
@@ -1400,7 +1526,7 @@ pub async fn build_rag_context(
 // TREE-SITTER PARSER COMMANDS (AI-Native IDE Evolution)
 // --------------------
 
-use crate::tree_sitter_parser::{TreeSitterParser, FileAnalysis};
+use crate::tree_sitter_parser::{TreeSitterParser, FileAnalysis, Symbol, HOTSPOT_COMPLEXITY};
 use tokio::sync::Mutex as TokioMutex;
 use once_cell::sync::Lazy;
 
@@ -1411,7 +1537,7 @@ static TREE_SITTER_PARSER: Lazy<TokioMutex<TreeSitterParser>> = Lazy::new(|| {
 
 /// Parse file and extract symbols using tree-sitter
 #[tauri::command]
-pub async fn parse_file_ast(file_path: String) -> Result<FileAnalysis, String> {
+pub async fn parse_file_ast(file_path: String, app: AppHandle) -> Result<FileAnalysis, String> {
     info!("🌳 Dosya parse ediliyor: {}", file_path);
     
     // Read file content
@@ -1422,8 +1548,13 @@ pub async fn parse_file_ast(file_path: String) -> Result<FileAnalysis, String> {
     let mut parser: tokio::sync::MutexGuard<TreeSitterParser> = TREE_SITTER_PARSER.lock().await;
     
     // Parse file
-    let analysis = parser.parse_file(&file_path, &content)
+    let mut analysis = parser.parse_file(&file_path, &content)
         .map_err(|e| format!("Parse hatası: {}", e))?;
+
+    // Both import directions come from the workspace graph, once it is built
+    if let Some(graph) = app.try_state::<DependencyGraphState>() {
+        graph.fill(&mut analysis);
+    }
     
     info!("✅ Parse tamamlandı: {} sembol bulundu", analysis.symbols.len());
     Ok(analysis)
@@ -1441,6 +1572,35 @@ pub async fn clear_ast_cache() -> Result<(), String> {
     Ok(())
 }
 
+/// Read a file and parse it with the shared parser (served from the AST cache while the content is unchanged)
+pub(crate) async fn analyze_file(file_path: &str) -> Result<FileAnalysis, String> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| format!("Dosya okunamadı: {}", e))?;
+
+    let mut parser = TREE_SITTER_PARSER.lock().await;
+    parser.parse_file(file_path, &content)
+        .map_err(|e| format!("Parse hatası: {}", e))
+}
+
+/// Functions whose cyclomatic complexity is at or above `threshold`, worst first
+#[tauri::command]
+pub async fn get_complexity_hotspots(file_path: String, threshold: Option<usize>) -> Result<Vec<Symbol>, String> {
+    let threshold = threshold.unwrap_or(HOTSPOT_COMPLEXITY);
+    info!("🔥 Karmaşıklık analizi: {} (eşik {})", file_path, threshold);
+
+    let analysis = analyze_file(&file_path).await?;
+    let hotspots: Vec<Symbol> = analysis.hotspots(threshold).into_iter().cloned().collect();
+
+    info!("✅ {} hotspot bulundu", hotspots.len());
+    Ok(hotspots)
+}
+
+/// Drop a file from the shared parser's AST cache
+pub(crate) async fn invalidate_parsed_file(file_path: &str) {
+    let mut parser = TREE_SITTER_PARSER.lock().await;
+    parser.invalidate_file(file_path);
+}
+
 /// Invalidate cache for specific file
 #[tauri::command]
 pub async fn invalidate_file_cache(file_path: String) -> Result<(), String> {
@@ -0,0 +1,1348 @@
+// src-tauri/src/compose.rs
+// Docker Compose projects: compose files are parsed with tree-sitter-yaml into a typed model
+// that keeps YAML line numbers, `-f` overrides are merged the way `docker compose` does, and
+// services are driven one at a time through the compose CLI.
+
+use crate::docker::{follow_logs, DockerClient, DockerState};
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime, State};
+use tree_sitter::{Node, Parser};
+
+/// File names `docker compose` picks up when no `-f` is given, in its order of preference
+const COMPOSE_FILES: &[&str] = &["compose.yaml", "compose.yml", "docker-compose.yaml", "docker-compose.yml"];
+
+/// Directories not worth walking when looking for compose projects
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor", "venv", ".venv"];
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+// ---------------------------------------------------------------------------
+// YAML
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Yaml {
+    Null,
+    Scalar(String),
+    Seq(Vec<YamlNode>),
+    Map(Vec<YamlEntry>),
+}
+
+/// A YAML value and the 1-based line it starts on
+#[derive(Debug, Clone, PartialEq)]
+struct YamlNode {
+    value: Yaml,
+    line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct YamlEntry {
+    key: String,
+    line: usize,
+    value: YamlNode,
+}
+
+impl YamlNode {
+    fn get(&self, key: &str) -> Option<&YamlNode> {
+        match &self.value {
+            Yaml::Map(entries) => entries.iter().find(|e| e.key == key).map(|e| &e.value),
+            _ => None,
+        }
+    }
+
+    fn entries(&self) -> &[YamlEntry] {
+        match &self.value {
+            Yaml::Map(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self.value {
+            Yaml::Null => "boş değer",
+            Yaml::Scalar(_) => "metin",
+            Yaml::Seq(_) => "liste",
+            Yaml::Map(_) => "eşleme",
+        }
+    }
+}
+
+/// Builds [`YamlNode`]s from the tree-sitter-yaml syntax tree, resolving anchors, aliases
+/// and `<<` merge keys on the way
+struct YamlReader<'a> {
+    source: &'a str,
+    anchors: HashMap<String, YamlNode>,
+    errors: Vec<(usize, String)>,
+}
+
+impl<'a> YamlReader<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        &self.source[node.byte_range()]
+    }
+
+    fn read(&mut self, node: Node) -> YamlNode {
+        let line = node.start_position().row + 1;
+        let value = match node.kind() {
+            "document" | "block_node" | "flow_node" | "block_sequence_item" => {
+                let mut anchor = None;
+                let mut content = None;
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    match child.kind() {
+                        "anchor" => anchor = Some(self.text(child).trim_start_matches('&').to_string()),
+                        "tag" | "comment" => {}
+                        _ => content = Some(child),
+                    }
+                }
+                let value = content.map(|c| self.read(c)).unwrap_or(YamlNode { value: Yaml::Null, line });
+                if let Some(anchor) = anchor {
+                    self.anchors.insert(anchor, value.clone());
+                }
+                return value;
+            }
+            "block_mapping" | "flow_mapping" => {
+                let mut entries: Vec<YamlEntry> = Vec::new();
+                let mut merges = Vec::new();
+                let mut cursor = node.walk();
+                for pair in node.named_children(&mut cursor) {
+                    let (key, value) = match pair.kind() {
+                        "block_mapping_pair" | "flow_pair" => (pair.child_by_field_name("key"), pair.child_by_field_name("value")),
+                        // `{a, b}`: keys without values
+                        "flow_node" => (Some(pair), None),
+                        _ => continue,
+                    };
+                    let key_line = pair.start_position().row + 1;
+                    let key = match key.map(|k| self.read(k).value) {
+                        Some(Yaml::Scalar(key)) => key,
+                        Some(Yaml::Null) | None => String::new(),
+                        Some(_) => {
+                            self.errors.push((key_line, "Eşleme anahtarı metin olmalı".to_string()));
+                            continue;
+                        }
+                    };
+                    let value = value.map(|v| self.read(v)).unwrap_or(YamlNode { value: Yaml::Null, line: key_line });
+                    if key == "<<" {
+                        merges.push(value);
+                        continue;
+                    }
+                    entries.retain(|e| e.key != key);
+                    entries.push(YamlEntry { key, line: key_line, value });
+                }
+                // Merged keys never override the mapping's own keys
+                for merge in merges {
+                    let sources = match merge.value {
+                        Yaml::Seq(items) => items,
+                        _ => vec![merge],
+                    };
+                    for source in sources {
+                        for entry in source.entries() {
+                            if !entries.iter().any(|e| e.key == entry.key) {
+                                entries.push(entry.clone());
+                            }
+                        }
+                    }
+                }
+                Yaml::Map(entries)
+            }
+            "block_sequence" | "flow_sequence" => {
+                let mut items = Vec::new();
+                let mut cursor = node.walk();
+                for item in node.named_children(&mut cursor) {
+                    match item.kind() {
+                        "comment" => {}
+                        // `[a: 1]` is a single-pair mapping
+                        "flow_pair" => {
+                            let item_line = item.start_position().row + 1;
+                            let key = item.child_by_field_name("key").map(|k| self.read(k).value);
+                            let value = item.child_by_field_name("value").map(|v| self.read(v))
+                                .unwrap_or(YamlNode { value: Yaml::Null, line: item_line });
+                            let key = match key {
+                                Some(Yaml::Scalar(key)) => key,
+                                _ => String::new(),
+                            };
+                            items.push(YamlNode { value: Yaml::Map(vec![YamlEntry { key, line: item_line, value }]), line: item_line });
+                        }
+                        _ => items.push(self.read(item)),
+                    }
+                }
+                Yaml::Seq(items)
+            }
+            "plain_scalar" => {
+                let text = self.text(node);
+                match text {
+                    "~" | "null" | "Null" | "NULL" => Yaml::Null,
+                    // Multi-line plain scalars fold into one line
+                    _ => Yaml::Scalar(text.lines().map(str::trim).collect::<Vec<_>>().join(" ")),
+                }
+            }
+            "single_quote_scalar" => {
+                let text = self.text(node);
+                Yaml::Scalar(text[1..text.len().saturating_sub(1).max(1)].replace("''", "'"))
+            }
+            "double_quote_scalar" => Yaml::Scalar(unescape_double_quoted(self.text(node))),
+            "block_scalar" => Yaml::Scalar(block_scalar(self.text(node))),
+            "alias" => {
+                let name = self.text(node).trim_start_matches('*');
+                match self.anchors.get(name) {
+                    Some(value) => return YamlNode { value: value.value.clone(), line },
+                    None => {
+                        self.errors.push((line, format!("Tanımsız anchor: *{}", name)));
+                        Yaml::Null
+                    }
+                }
+            }
+            _ => Yaml::Null,
+        };
+        YamlNode { value, line }
+    }
+}
+
+fn unescape_double_quoted(text: &str) -> String {
+    let inner = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')).unwrap_or(text);
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('0') => result.push('\0'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    result.push(c);
+                }
+            }
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// `|` and `>` block scalars: dedent, fold `>` lines and apply the chomping indicator
+fn block_scalar(text: &str) -> String {
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or("");
+    let body: Vec<&str> = lines.collect();
+    let indent = body.iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let body: Vec<&str> = body.iter().map(|l| l.get(indent..).unwrap_or("")).collect();
+    let content = if header.starts_with('>') {
+        body.split(|l| l.is_empty())
+            .map(|paragraph| paragraph.join(" "))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        body.join("\n")
+    };
+    let content = content.trim_end_matches('\n');
+    if header.contains('-') {
+        content.to_string()
+    } else if header.contains('+') {
+        format!("{}\n", body.join("\n"))
+    } else {
+        format!("{}\n", content)
+    }
+}
+
+/// Parses one YAML document; syntax errors come back with their line
+fn parse_yaml(source: &str) -> Result<(YamlNode, Vec<(usize, String)>), String> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&tree_sitter_yaml::LANGUAGE.into())
+        .map_err(|e| format!("YAML dili yüklenemedi: {}", e))?;
+    let tree = parser.parse(source, None).ok_or("YAML ayrıştırılamadı")?;
+    let root = tree.root_node();
+
+    let mut errors = Vec::new();
+    if root.has_error() {
+        syntax_errors(root, source, &mut errors);
+    }
+    let mut reader = YamlReader { source, anchors: HashMap::new(), errors: Vec::new() };
+    let mut cursor = root.walk();
+    let document = root.named_children(&mut cursor).find(|n| n.kind() == "document");
+    let value = match document {
+        Some(document) => reader.read(document),
+        None => YamlNode { value: Yaml::Null, line: 1 },
+    };
+    errors.extend(reader.errors);
+    Ok((value, errors))
+}
+
+fn syntax_errors(node: Node, source: &str, errors: &mut Vec<(usize, String)>) {
+    if node.is_missing() {
+        errors.push((node.start_position().row + 1, format!("YAML sözdizimi hatası: eksik `{}`", node.kind())));
+        return;
+    }
+    if node.is_error() {
+        // An ERROR around parsed nodes holds what tree-sitter could read before giving up, so
+        // the culprit is the first token after it, or its last child when it runs to the end
+        let after = source[node.end_byte()..].char_indices()
+            .find(|(_, c)| !c.is_whitespace())
+            .map(|(offset, _)| node.end_byte() + offset);
+        let at = match (node.named_child_count(), after) {
+            (0, _) => node.start_byte(),
+            (_, Some(offset)) => offset,
+            (_, None) => node.child(node.child_count() - 1).map(|n| n.start_byte()).unwrap_or(node.start_byte()),
+        };
+        let line = source[..at].matches('\n').count() + 1;
+        let text = source[at..].lines().next().unwrap_or("").trim();
+        errors.push((line, format!("YAML sözdizimi hatası: `{}`", text)));
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.has_error() {
+            syntax_errors(child, source, errors);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Compose model
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PortMapping {
+    pub host_ip: Option<String>,
+    /// Host port or range; `None` lets the engine pick one
+    pub published: Option<String>,
+    pub target: String,
+    pub protocol: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct VolumeMount {
+    /// `bind`, `volume` or `tmpfs`
+    pub kind: String,
+    pub source: Option<String>,
+    pub target: String,
+    pub read_only: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ServiceDependency {
+    pub service: String,
+    pub condition: String,
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+pub struct BuildConfig {
+    pub context: String,
+    pub dockerfile: Option<String>,
+    pub target: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ServiceContainer {
+    pub id: String,
+    pub name: String,
+    pub state: String,
+    pub status: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    pub build: Option<BuildConfig>,
+    pub ports: Vec<PortMapping>,
+    pub volumes: Vec<VolumeMount>,
+    pub depends_on: Vec<ServiceDependency>,
+    pub profiles: Vec<String>,
+    /// False when the service is behind a profile that isn't enabled
+    pub enabled: bool,
+    /// `running`, `exited`, ..., `not_created`, or `unknown` when the engine isn't reachable
+    pub status: String,
+    pub containers: Vec<ServiceContainer>,
+    /// Where the service is first defined
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ComposeDiagnostic {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ComposeProject {
+    pub name: String,
+    pub working_dir: String,
+    pub files: Vec<String>,
+    pub services: Vec<ComposeService>,
+    /// Named volumes declared at the top level
+    pub volumes: Vec<String>,
+    pub diagnostics: Vec<ComposeDiagnostic>,
+}
+
+/// What one compose file contributes before merging
+#[derive(Debug, Default)]
+struct ComposeFile {
+    name: Option<String>,
+    services: Vec<ComposeService>,
+    volumes: Vec<String>,
+}
+
+/// Reads one compose file; knows its path, the variables for interpolation and collects
+/// diagnostics against that file
+struct FileReader<'a> {
+    file: String,
+    env: &'a HashMap<String, String>,
+    diagnostics: &'a mut Vec<ComposeDiagnostic>,
+}
+
+impl FileReader<'_> {
+    fn error(&mut self, line: usize, message: impl Into<String>) {
+        self.diagnostics.push(ComposeDiagnostic { file: self.file.clone(), line, message: message.into() });
+    }
+
+    /// A scalar with `${VAR}` interpolation; anything else is reported and skipped
+    fn string(&mut self, node: &YamlNode, what: &str) -> Option<String> {
+        match &node.value {
+            Yaml::Scalar(text) => match interpolate(text, self.env) {
+                Ok(text) => Some(text),
+                Err(e) => {
+                    self.error(node.line, e);
+                    None
+                }
+            },
+            _ => {
+                self.error(node.line, format!("`{}` metin olmalı, {} bulundu", what, node.kind()));
+                None
+            }
+        }
+    }
+
+    fn strings(&mut self, node: &YamlNode, what: &str) -> Vec<String> {
+        match &node.value {
+            Yaml::Seq(items) => items.iter().filter_map(|item| self.string(item, what)).collect(),
+            Yaml::Null => Vec::new(),
+            _ => {
+                self.error(node.line, format!("`{}` liste olmalı, {} bulundu", what, node.kind()));
+                Vec::new()
+            }
+        }
+    }
+
+    fn read(&mut self, root: &YamlNode) -> ComposeFile {
+        let mut compose = ComposeFile::default();
+        if let Yaml::Null = root.value {
+            return compose;
+        }
+        if !matches!(root.value, Yaml::Map(_)) {
+            self.error(root.line, "Compose dosyası bir eşleme olmalı");
+            return compose;
+        }
+        if let Some(name) = root.get("name") {
+            compose.name = self.string(name, "name");
+        }
+        if let Some(volumes) = root.get("volumes") {
+            compose.volumes = volumes.entries().iter().map(|e| e.key.clone()).collect();
+        }
+        let Some(services) = root.get("services") else {
+            return compose;
+        };
+        if !matches!(services.value, Yaml::Map(_) | Yaml::Null) {
+            self.error(services.line, format!("`services` bir eşleme olmalı, {} bulundu", services.kind()));
+            return compose;
+        }
+        for entry in services.entries() {
+            if let Some(service) = self.service(entry) {
+                compose.services.push(service);
+            }
+        }
+        compose
+    }
+
+    fn service(&mut self, entry: &YamlEntry) -> Option<ComposeService> {
+        let node = &entry.value;
+        if !matches!(node.value, Yaml::Map(_) | Yaml::Null) {
+            self.error(entry.line, format!("`{}` servisi bir eşleme olmalı, {} bulundu", entry.key, node.kind()));
+            return None;
+        }
+        let mut service = ComposeService {
+            name: entry.key.clone(),
+            image: None,
+            build: None,
+            ports: Vec::new(),
+            volumes: Vec::new(),
+            depends_on: Vec::new(),
+            profiles: Vec::new(),
+            enabled: true,
+            status: "unknown".to_string(),
+            containers: Vec::new(),
+            file: self.file.clone(),
+            line: entry.line,
+        };
+        if let Some(image) = node.get("image") {
+            service.image = self.string(image, "image");
+        }
+        if let Some(build) = node.get("build") {
+            service.build = self.build(build);
+        }
+        if let Some(ports) = node.get("ports") {
+            service.ports = self.list(ports, "ports", |reader, item| reader.port(item));
+        }
+        if let Some(volumes) = node.get("volumes") {
+            service.volumes = self.list(volumes, "volumes", |reader, item| reader.volume(item));
+        }
+        if let Some(depends_on) = node.get("depends_on") {
+            service.depends_on = self.depends_on(depends_on);
+        }
+        if let Some(profiles) = node.get("profiles") {
+            service.profiles = self.strings(profiles, "profiles");
+        }
+        Some(service)
+    }
+
+    fn list<T>(&mut self, node: &YamlNode, what: &str, mut item: impl FnMut(&mut Self, &YamlNode) -> Option<T>) -> Vec<T> {
+        match &node.value {
+            Yaml::Seq(items) => items.iter().filter_map(|i| item(self, i)).collect(),
+            Yaml::Null => Vec::new(),
+            _ => {
+                self.error(node.line, format!("`{}` liste olmalı, {} bulundu", what, node.kind()));
+                Vec::new()
+            }
+        }
+    }
+
+    fn build(&mut self, node: &YamlNode) -> Option<BuildConfig> {
+        match &node.value {
+            Yaml::Scalar(_) => Some(BuildConfig { context: self.string(node, "build")?, ..Default::default() }),
+            Yaml::Map(_) => {
+                // Left empty so an override without `context` keeps the base one
+                let context = match node.get("context") {
+                    Some(context) => self.string(context, "build.context")?,
+                    None => String::new(),
+                };
+                let dockerfile = node.get("dockerfile").and_then(|d| self.string(d, "build.dockerfile"));
+                let target = node.get("target").and_then(|t| self.string(t, "build.target"));
+                Some(BuildConfig { context, dockerfile, target })
+            }
+            _ => {
+                self.error(node.line, format!("`build` metin veya eşleme olmalı, {} bulundu", node.kind()));
+                None
+            }
+        }
+    }
+
+    fn port(&mut self, node: &YamlNode) -> Option<PortMapping> {
+        let port = match &node.value {
+            Yaml::Map(_) => {
+                let Some(target) = node.get("target") else {
+                    self.error(node.line, "Port tanımında `target` eksik");
+                    return None;
+                };
+                let target = self.string(target, "ports.target")?;
+                let published = node.get("published").and_then(|p| self.string(p, "ports.published"));
+                let host_ip = node.get("host_ip").and_then(|h| self.string(h, "ports.host_ip"));
+                let protocol = node.get("protocol").and_then(|p| self.string(p, "ports.protocol"));
+                PortMapping { host_ip, published, target, protocol: protocol.unwrap_or_else(|| "tcp".to_string()) }
+            }
+            _ => {
+                let text = self.string(node, "ports")?;
+                match parse_port(&text) {
+                    Ok(port) => port,
+                    Err(e) => {
+                        self.error(node.line, e);
+                        return None;
+                    }
+                }
+            }
+        };
+        let ranges = std::iter::once(&port.target).chain(port.published.as_ref());
+        for range in ranges {
+            if let Err(e) = port_range(range) {
+                self.error(node.line, e);
+                return None;
+            }
+        }
+        Some(port)
+    }
+
+    fn volume(&mut self, node: &YamlNode) -> Option<VolumeMount> {
+        let volume = match &node.value {
+            Yaml::Map(_) => {
+                let Some(target) = node.get("target") else {
+                    self.error(node.line, "Volume tanımında `target` eksik");
+                    return None;
+                };
+                let target = self.string(target, "volumes.target")?;
+                let source = node.get("source").and_then(|s| self.string(s, "volumes.source"));
+                let kind = match node.get("type") {
+                    Some(kind) => self.string(kind, "volumes.type")?,
+                    None => "volume".to_string(),
+                };
+                let read_only = matches!(node.get("read_only").map(|r| &r.value), Some(Yaml::Scalar(v)) if v == "true");
+                VolumeMount { kind, source, target, read_only }
+            }
+            _ => {
+                let text = self.string(node, "volumes")?;
+                match parse_volume(&text) {
+                    Ok(volume) => volume,
+                    Err(e) => {
+                        self.error(node.line, e);
+                        return None;
+                    }
+                }
+            }
+        };
+        if !volume.target.starts_with('/') && !is_windows_path(&volume.target) {
+            self.error(node.line, format!("Volume hedefi mutlak bir yol olmalı: {}", volume.target));
+            return None;
+        }
+        Some(volume)
+    }
+
+    fn depends_on(&mut self, node: &YamlNode) -> Vec<ServiceDependency> {
+        const CONDITIONS: &[&str] = &["service_started", "service_healthy", "service_completed_successfully"];
+        match &node.value {
+            Yaml::Seq(items) => items.iter()
+                .filter_map(|item| {
+                    let service = self.string(item, "depends_on")?;
+                    Some(ServiceDependency {
+                        service,
+                        condition: "service_started".to_string(),
+                        file: self.file.clone(),
+                        line: item.line,
+                    })
+                })
+                .collect(),
+            Yaml::Map(entries) => entries.iter()
+                .filter_map(|entry| {
+                    let condition = match entry.value.get("condition") {
+                        Some(condition) => self.string(condition, "depends_on.condition")?,
+                        None => "service_started".to_string(),
+                    };
+                    if !CONDITIONS.contains(&condition.as_str()) {
+                        self.error(entry.line, format!(
+                            "Geçersiz depends_on koşulu `{}`; {} olmalı",
+                            condition,
+                            CONDITIONS.join(", ")
+                        ));
+                        return None;
+                    }
+                    Some(ServiceDependency {
+                        service: entry.key.clone(),
+                        condition,
+                        file: self.file.clone(),
+                        line: entry.line,
+                    })
+                })
+                .collect(),
+            Yaml::Null => Vec::new(),
+            _ => {
+                self.error(node.line, format!("`depends_on` liste veya eşleme olmalı, {} bulundu", node.kind()));
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Compose variable interpolation: `$VAR`, `${VAR}`, `${VAR:-default}`, `${VAR-default}`,
+/// `${VAR:?error}`, `${VAR?error}` and `$$` for a literal dollar. Unset variables are empty.
+fn interpolate(text: &str, env: &HashMap<String, String>) -> Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            result.push('$');
+            rest = after;
+        } else if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced.find('}').ok_or_else(|| format!("Kapanmamış değişken: ${{{}", braced))?;
+            let expression = &braced[..end];
+            rest = &braced[end + 1..];
+            let split = expression.find([':', '-', '?']).unwrap_or(expression.len());
+            let (name, modifier) = expression.split_at(split);
+            let value = env.get(name).map(String::as_str);
+            let (set, operator, argument) = match modifier.strip_prefix(':') {
+                // With a colon, an empty value counts as unset
+                Some(m) => (value.is_some_and(|v| !v.is_empty()), m.get(..1).unwrap_or(""), m.get(1..).unwrap_or("")),
+                None => (value.is_some(), modifier.get(..1).unwrap_or(""), modifier.get(1..).unwrap_or("")),
+            };
+            match (set, operator) {
+                (true, _) => result.push_str(value.unwrap_or("")),
+                (false, "-") => result.push_str(&interpolate(argument, env)?),
+                (false, "?") => {
+                    let message = if argument.is_empty() { "değişken tanımlı değil" } else { argument };
+                    return Err(format!("{}: {}", name, message));
+                }
+                (false, _) => {}
+            }
+        } else {
+            let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+            if end == 0 {
+                result.push('$');
+                continue;
+            }
+            result.push_str(env.get(&rest[..end]).map(String::as_str).unwrap_or(""));
+            rest = &rest[end..];
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Short port syntax: `[[host_ip:]published:]target[/protocol]`, host ip may be `[ipv6]`
+fn parse_port(text: &str) -> Result<PortMapping, String> {
+    let invalid = || format!("Geçersiz port tanımı: {}", text);
+    let (spec, protocol) = match text.rsplit_once('/') {
+        Some((spec, protocol)) => (spec, protocol.to_string()),
+        None => (text, "tcp".to_string()),
+    };
+    let (host_ip, ports) = match spec.strip_prefix('[') {
+        Some(ipv6) => {
+            let (ip, rest) = ipv6.split_once("]:").ok_or_else(invalid)?;
+            (Some(ip.to_string()), rest)
+        }
+        None => (None, spec),
+    };
+    let parts: Vec<&str> = ports.split(':').collect();
+    let (host_ip, published, target) = match (host_ip, parts.as_slice()) {
+        (None, [target]) => (None, None, *target),
+        (host_ip, [published, target]) => (host_ip, Some(*published), *target),
+        (None, [host_ip, published, target]) => (Some(host_ip.to_string()), Some(*published), *target),
+        _ => return Err(invalid()),
+    };
+    if target.is_empty() {
+        return Err(invalid());
+    }
+    Ok(PortMapping {
+        host_ip,
+        published: published.filter(|p| !p.is_empty()).map(str::to_string),
+        target: target.to_string(),
+        protocol,
+    })
+}
+
+/// A port (`8080`) or range (`8000-8010`) within 1..=65535
+fn port_range(text: &str) -> Result<(), String> {
+    let (low, high) = text.split_once('-').unwrap_or((text, text));
+    let valid = |port: &str| port.parse::<u16>().is_ok_and(|p| p > 0);
+    if valid(low) && valid(high) && low.parse::<u16>().ok() <= high.parse::<u16>().ok() {
+        Ok(())
+    } else {
+        Err(format!("Geçersiz port: {}", text))
+    }
+}
+
+fn is_windows_path(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && (bytes[2] == b'\\' || bytes[2] == b'/')
+}
+
+/// Short volume syntax: `[source:]target[:mode]`; a drive letter in the source is not a separator
+fn parse_volume(text: &str) -> Result<VolumeMount, String> {
+    let (drive, rest) = if is_windows_path(text) { text.split_at(2) } else { ("", text) };
+    let mut parts: Vec<String> = rest.split(':').map(str::to_string).collect();
+    parts[0] = format!("{}{}", drive, parts[0]);
+    let (source, target, mode) = match parts.as_slice() {
+        [target] => (None, target.clone(), ""),
+        [source, target] => (Some(source.clone()), target.clone(), ""),
+        [source, target, mode] => (Some(source.clone()), target.clone(), mode.as_str()),
+        _ => return Err(format!("Geçersiz volume tanımı: {}", text)),
+    };
+    if target.is_empty() || source.as_deref() == Some("") {
+        return Err(format!("Geçersiz volume tanımı: {}", text));
+    }
+    let kind = match &source {
+        Some(s) if s.starts_with(['.', '/', '~']) || is_windows_path(s) => "bind",
+        _ => "volume",
+    };
+    Ok(VolumeMount {
+        kind: kind.to_string(),
+        source,
+        target,
+        read_only: mode.split(',').any(|m| m == "ro"),
+    })
+}
+
+/// Applies an override file's service on top of the base one: scalars replace, ports and
+/// profiles accumulate, volumes replace by target and dependencies by service
+fn merge_service(base: &mut ComposeService, over: ComposeService) {
+    if over.image.is_some() {
+        base.image = over.image;
+    }
+    match (&mut base.build, over.build) {
+        (Some(build), Some(over)) => {
+            if !over.context.is_empty() {
+                build.context = over.context;
+            }
+            build.dockerfile = over.dockerfile.or(build.dockerfile.take());
+            build.target = over.target.or(build.target.take());
+        }
+        (build, over) => {
+            if over.is_some() {
+                *build = over;
+            }
+        }
+    }
+    for port in over.ports {
+        if !base.ports.contains(&port) {
+            base.ports.push(port);
+        }
+    }
+    for volume in over.volumes {
+        base.volumes.retain(|v| v.target != volume.target);
+        base.volumes.push(volume);
+    }
+    for dependency in over.depends_on {
+        base.depends_on.retain(|d| d.service != dependency.service);
+        base.depends_on.push(dependency);
+    }
+    for profile in over.profiles {
+        if !base.profiles.contains(&profile) {
+            base.profiles.push(profile);
+        }
+    }
+}
+
+/// Cross-service checks that only make sense on the merged project
+fn validate(project: &mut ComposeProject) {
+    let names: HashSet<&str> = project.services.iter().map(|s| s.name.as_str()).collect();
+    let mut diagnostics = Vec::new();
+    if project.services.is_empty() {
+        diagnostics.push(ComposeDiagnostic {
+            file: project.files.first().cloned().unwrap_or_default(),
+            line: 1,
+            message: "Hiç servis tanımlanmamış".to_string(),
+        });
+    }
+    for service in &project.services {
+        if service.image.is_none() && service.build.is_none() {
+            diagnostics.push(ComposeDiagnostic {
+                file: service.file.clone(),
+                line: service.line,
+                message: format!("`{}` servisinde `image` veya `build` olmalı", service.name),
+            });
+        }
+        for dependency in &service.depends_on {
+            let message = if !names.contains(dependency.service.as_str()) {
+                format!("`{}` tanımsız bir servise bağlı: `{}`", service.name, dependency.service)
+            } else if service.enabled && project.services.iter().any(|s| s.name == dependency.service && !s.enabled) {
+                format!("`{}` servisinin bağlı olduğu `{}` etkin profillerde değil", service.name, dependency.service)
+            } else {
+                continue;
+            };
+            diagnostics.push(ComposeDiagnostic { file: dependency.file.clone(), line: dependency.line, message });
+        }
+        for volume in &service.volumes {
+            let Some(source) = volume.source.as_ref().filter(|_| volume.kind == "volume") else {
+                continue;
+            };
+            if !project.volumes.contains(source) {
+                diagnostics.push(ComposeDiagnostic {
+                    file: service.file.clone(),
+                    line: service.line,
+                    message: format!("`{}` tanımsız bir volume kullanıyor: `{}`", service.name, source),
+                });
+            }
+        }
+    }
+    if let Some(cycle) = dependency_cycle(&project.services) {
+        let first = project.services.iter().find(|s| s.name == cycle[0]);
+        diagnostics.push(ComposeDiagnostic {
+            file: first.map(|s| s.file.clone()).unwrap_or_default(),
+            line: first.map(|s| s.line).unwrap_or(1),
+            message: format!("depends_on döngüsü: {}", cycle.join(" → ")),
+        });
+    }
+    project.diagnostics.extend(diagnostics);
+}
+
+/// The first `depends_on` cycle, as service names ending where they started
+fn dependency_cycle(services: &[ComposeService]) -> Option<Vec<String>> {
+    fn visit<'a>(
+        name: &'a str,
+        services: &'a [ComposeService],
+        path: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+    ) -> Option<Vec<String>> {
+        if let Some(start) = path.iter().position(|n| *n == name) {
+            let mut cycle: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+            cycle.push(name.to_string());
+            return Some(cycle);
+        }
+        if !done.insert(name) {
+            return None;
+        }
+        path.push(name);
+        let service = services.iter().find(|s| s.name == name)?;
+        for dependency in &service.depends_on {
+            if let Some(cycle) = visit(&dependency.service, services, path, done) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        None
+    }
+
+    let mut done = HashSet::new();
+    services.iter().find_map(|s| visit(&s.name, services, &mut Vec::new(), &mut done))
+}
+
+/// Compose project names: lowercase letters, digits, `-` and `_`, starting with a letter or digit
+fn project_name(raw: &str) -> String {
+    raw.to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .skip_while(|c| !c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Variables for interpolation: the project's `.env` file, overridden by the process environment
+fn project_env(working_dir: &Path) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    if let Ok(content) = std::fs::read_to_string(working_dir.join(".env")) {
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim();
+                let value = value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+                    .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                    .unwrap_or(value);
+                env.insert(key.trim().trim_start_matches("export ").to_string(), value.to_string());
+            }
+        }
+    }
+    env.extend(std::env::vars());
+    env
+}
+
+/// Parses and merges `files` (later ones override earlier ones) and checks the result.
+/// Problems in the files are returned as diagnostics; only unreadable files are errors.
+pub fn load_project(files: &[String], profiles: &[String]) -> Result<ComposeProject, String> {
+    let first = files.first().ok_or("En az bir compose dosyası gerekli")?;
+    let working_dir = Path::new(first).parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("."));
+    let env = project_env(&working_dir);
+
+    let mut diagnostics = Vec::new();
+    let mut name = None;
+    let mut services: Vec<ComposeService> = Vec::new();
+    let mut volumes = Vec::new();
+    for file in files {
+        let source = std::fs::read_to_string(file).map_err(|e| format!("Compose dosyası okunamadı {}: {}", file, e))?;
+        let (root, errors) = parse_yaml(&source)?;
+        diagnostics.extend(errors.into_iter().map(|(line, message)| ComposeDiagnostic { file: file.clone(), line, message }));
+
+        let compose = FileReader { file: file.clone(), env: &env, diagnostics: &mut diagnostics }.read(&root);
+        name = compose.name.or(name);
+        for volume in compose.volumes {
+            if !volumes.contains(&volume) {
+                volumes.push(volume);
+            }
+        }
+        for service in compose.services {
+            match services.iter_mut().find(|s| s.name == service.name) {
+                Some(base) => merge_service(base, service),
+                None => services.push(service),
+            }
+        }
+    }
+
+    for service in &mut services {
+        if let Some(build) = service.build.as_mut().filter(|b| b.context.is_empty()) {
+            build.context = ".".to_string();
+        }
+        service.enabled = service.profiles.is_empty() || service.profiles.iter().any(|p| profiles.contains(p));
+    }
+    let dir_name = working_dir.canonicalize().unwrap_or_else(|_| working_dir.clone())
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = name
+        .or_else(|| env.get("COMPOSE_PROJECT_NAME").cloned())
+        .unwrap_or(dir_name);
+
+    let mut project = ComposeProject {
+        name: project_name(&name),
+        working_dir: working_dir.to_string_lossy().to_string(),
+        files: files.to_vec(),
+        services,
+        volumes,
+        diagnostics,
+    };
+    validate(&mut project);
+    Ok(project)
+}
+
+/// Fills in each service's containers and status from the engine's compose labels
+fn apply_status(project: &mut ComposeProject, containers: &Value) {
+    for service in &mut project.services {
+        service.containers.clear();
+    }
+    for container in containers.as_array().into_iter().flatten() {
+        let labels = &container["Labels"];
+        let Some(service) = labels[SERVICE_LABEL].as_str()
+            .and_then(|name| project.services.iter_mut().find(|s| s.name == name))
+        else {
+            continue;
+        };
+        service.containers.push(ServiceContainer {
+            id: container["Id"].as_str().unwrap_or_default().chars().take(12).collect(),
+            name: container["Names"][0].as_str().unwrap_or_default().trim_start_matches('/').to_string(),
+            state: container["State"].as_str().unwrap_or_default().to_string(),
+            status: container["Status"].as_str().unwrap_or_default().to_string(),
+        });
+    }
+    for service in &mut project.services {
+        service.status = match service.containers.iter().find(|c| c.state == "running") {
+            Some(running) => running.state.clone(),
+            None => service.containers.first().map(|c| c.state.clone()).unwrap_or_else(|| "not_created".to_string()),
+        };
+    }
+}
+
+async fn refresh_status(project: &mut ComposeProject) {
+    let filters = serde_json::json!({ "label": [format!("{}={}", PROJECT_LABEL, project.name)] }).to_string();
+    let path = format!(
+        "/containers/json?all=true&filters={}",
+        url::form_urlencoded::byte_serialize(filters.as_bytes()).collect::<String>()
+    );
+    match DockerClient::from_env().get_json(&path).await {
+        Ok(containers) => apply_status(project, &containers),
+        Err(e) => warn!("⚠️ Compose servis durumları alınamadı: {}", e),
+    }
+}
+
+/// The `docker compose` arguments that select this project
+fn project_args(project: &ComposeProject, profiles: &[String]) -> Vec<String> {
+    let mut args = vec![
+        "compose".to_string(),
+        "--project-name".to_string(),
+        project.name.clone(),
+        "--project-directory".to_string(),
+        project.working_dir.clone(),
+    ];
+    for file in &project.files {
+        args.extend(["-f".to_string(), file.clone()]);
+    }
+    for profile in profiles {
+        args.extend(["--profile".to_string(), profile.clone()]);
+    }
+    args
+}
+
+/// Compose file sets in the workspace: the default file of each directory plus its override
+fn find_projects(workspace: &Path, depth: usize, projects: &mut Vec<Vec<String>>) {
+    if let Some(base) = COMPOSE_FILES.iter().map(|f| workspace.join(f)).find(|f| f.is_file()) {
+        let mut files = vec![base.to_string_lossy().to_string()];
+        let stem = base.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let extension = base.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+        let override_file = workspace.join(format!("{}.override.{}", stem, extension));
+        if override_file.is_file() {
+            files.push(override_file.to_string_lossy().to_string());
+        }
+        projects.push(files);
+    }
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(workspace) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| {
+            let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_str())
+        })
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        find_projects(&dir, depth - 1, projects);
+    }
+}
+
+/// Compose projects in the workspace, each as the list of files to pass to `-f`
+#[tauri::command]
+pub async fn docker_compose_find(workspace: String) -> Result<Vec<Vec<String>>, String> {
+    let mut projects = Vec::new();
+    find_projects(Path::new(&workspace), 2, &mut projects);
+    Ok(projects)
+}
+
+/// The merged project for `files` with the live status of every service
+#[tauri::command]
+pub async fn docker_compose_project(files: Vec<String>, profiles: Option<Vec<String>>) -> Result<ComposeProject, String> {
+    let mut project = load_project(&files, &profiles.unwrap_or_default())?;
+    refresh_status(&mut project).await;
+    Ok(project)
+}
+
+/// Runs `up`, `stop`, `restart` or `build` for one service (or all), or `down` for the project
+#[tauri::command]
+pub async fn docker_compose_action(
+    files: Vec<String>,
+    action: String,
+    service: Option<String>,
+    profiles: Option<Vec<String>>,
+) -> Result<String, String> {
+    let profiles = profiles.unwrap_or_default();
+    let project = load_project(&files, &profiles)?;
+    let mut args = project_args(&project, &profiles);
+    match action.as_str() {
+        "up" => args.extend(["up".to_string(), "-d".to_string()]),
+        "stop" | "restart" | "build" => args.push(action.clone()),
+        "down" if service.is_none() => args.push(action.clone()),
+        "down" => return Err("`down` tüm proje için çalışır, servis seçilemez".to_string()),
+        _ => return Err(format!("Geçersiz compose işlemi: {}", action)),
+    }
+    if matches!(action.as_str(), "up" | "restart" | "build") && !project.diagnostics.is_empty() {
+        let errors: Vec<String> = project.diagnostics.iter()
+            .map(|d| format!("{}:{}: {}", d.file, d.line, d.message))
+            .collect();
+        return Err(format!("Compose dosyasında hatalar var:\n{}", errors.join("\n")));
+    }
+    if let Some(service) = &service {
+        if !project.services.iter().any(|s| &s.name == service) {
+            return Err(format!("Servis bulunamadı: {}", service));
+        }
+        args.push(service.clone());
+    }
+
+    info!("🐳 docker compose {} {} ({})", action, service.as_deref().unwrap_or(""), project.name);
+    let output = tokio::process::Command::new("docker")
+        .args(&args)
+        .current_dir(&project.working_dir)
+        .output()
+        .await
+        .map_err(|e| format!("docker compose çalıştırılamadı: {}", e))?;
+
+    // Compose writes progress to stderr even when it succeeds
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    if output.status.success() {
+        Ok(text)
+    } else {
+        Err(text)
+    }
+}
+
+/// Follows the logs of every container of a service; returns one stream id per container
+#[tauri::command]
+pub async fn docker_compose_logs<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DockerState>,
+    files: Vec<String>,
+    service: String,
+    tail: Option<u32>,
+) -> Result<Vec<String>, String> {
+    let mut project = load_project(&files, &[])?;
+    refresh_status(&mut project).await;
+    let service = project.services.iter()
+        .find(|s| s.name == service)
+        .ok_or_else(|| format!("Servis bulunamadı: {}", service))?;
+    if service.containers.is_empty() {
+        return Err(format!("`{}` servisinin container'ı yok", service.name));
+    }
+    let mut streams = Vec::new();
+    for container in &service.containers {
+        streams.push(follow_logs(app.clone(), &state, &container.id, tail).await?);
+    }
+    Ok(streams)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_files(files: &[(&str, &str)]) -> (PathBuf, Vec<String>) {
+        let dir = std::env::temp_dir().join(format!("corex_compose_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths = files.iter()
+            .map(|(name, content)| {
+                let path = dir.join(name);
+                std::fs::write(&path, content).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        (dir, paths)
+    }
+
+    #[test]
+    fn test_parses_and_merges_override_files() {
+        let (dir, files) = write_files(&[
+            ("compose.yaml", r#"name: Shop_App
+x-logging: &restart
+  restart: always
+services:
+  web:
+    <<: *restart
+    build:
+      context: ./web
+      dockerfile: Dockerfile.dev
+    ports:
+      - "${WEB_PORT:-8080}:80"
+      - 127.0.0.1:9229:9229/udp
+      - target: 443
+        published: 8443
+    volumes:
+      - ./web:/app:ro
+      - cache:/root/.cache
+    depends_on:
+      db:
+        condition: service_healthy
+  db:
+    image: postgres:16  # pinned
+    volumes: [data:/var/lib/postgresql/data]
+  debug:
+    image: busybox
+    profiles: [tools]
+volumes:
+  data:
+  cache:
+"#),
+            ("compose.override.yaml", r#"services:
+  web:
+    build:
+      target: dev
+    ports:
+      - "3000"
+    volumes:
+      - ./src:/app
+"#),
+        ]);
+
+        let project = load_project(&files, &[]).unwrap();
+        assert_eq!(project.diagnostics, vec![]);
+        assert_eq!(project.name, "shop_app");
+        assert_eq!(project.volumes, vec!["data", "cache"]);
+
+        let web = &project.services[0];
+        assert_eq!((web.name.as_str(), web.line), ("web", 5));
+        assert_eq!(web.build, Some(BuildConfig {
+            context: "./web".to_string(),
+            dockerfile: Some("Dockerfile.dev".to_string()),
+            target: Some("dev".to_string()),
+        }));
+        let ports: Vec<(Option<&str>, Option<&str>, &str, &str)> = web.ports.iter()
+            .map(|p| (p.host_ip.as_deref(), p.published.as_deref(), p.target.as_str(), p.protocol.as_str()))
+            .collect();
+        assert_eq!(ports, vec![
+            (None, Some("8080"), "80", "tcp"),
+            (Some("127.0.0.1"), Some("9229"), "9229", "udp"),
+            (None, Some("8443"), "443", "tcp"),
+            (None, None, "3000", "tcp"),
+        ]);
+        // The override replaces the mount on /app
+        let volumes: Vec<(&str, Option<&str>, &str, bool)> = web.volumes.iter()
+            .map(|v| (v.kind.as_str(), v.source.as_deref(), v.target.as_str(), v.read_only))
+            .collect();
+        assert_eq!(volumes, vec![
+            ("volume", Some("cache"), "/root/.cache", false),
+            ("bind", Some("./src"), "/app", false),
+        ]);
+        assert_eq!(web.depends_on[0].service, "db");
+        assert_eq!(web.depends_on[0].condition, "service_healthy");
+        assert_eq!(web.depends_on[0].line, 19);
+
+        assert_eq!(project.services[1].image.as_deref(), Some("postgres:16"));
+        assert!(!project.services[2].enabled);
+        assert!(load_project(&files, &["tools".to_string()]).unwrap().services[2].enabled);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_diagnostics_point_to_yaml_lines() {
+        let (dir, files) = write_files(&[("docker-compose.yml", r#"services:
+  api:
+    build: .
+    ports:
+      - "80:70000"
+      - "not a port"
+    depends_on: [cache, worker]
+    volumes:
+      - logs:/var/log
+  worker:
+    depends_on:
+      api:
+        condition: service_ready
+  queue:
+    image: rabbitmq
+    depends_on: [queue]
+"#)]);
+
+        let project = load_project(&files, &[]).unwrap();
+        let diagnostics: Vec<(usize, &str)> = project.diagnostics.iter()
+            .map(|d| (d.line, d.message.as_str()))
+            .collect();
+        assert_eq!(diagnostics, vec![
+            (5, "Geçersiz port: 70000"),
+            (6, "Geçersiz port: not a port"),
+            (12, "Geçersiz depends_on koşulu `service_ready`; service_started, service_healthy, service_completed_successfully olmalı"),
+            (7, "`api` tanımsız bir servise bağlı: `cache`"),
+            (2, "`api` tanımsız bir volume kullanıyor: `logs`"),
+            (10, "`worker` servisinde `image` veya `build` olmalı"),
+            (14, "depends_on döngüsü: queue → queue"),
+        ]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_reports_yaml_syntax_errors_with_line() {
+        let unclosed = "services:\n  web:\n    image: nginx\n    ports: [\"80:80\"\n";
+        assert_eq!(parse_yaml(unclosed).unwrap().1, vec![(4, "YAML sözdizimi hatası: `\"80:80\"`".to_string())]);
+
+        let misindented = "services:\n  web:\n    image: nginx\n   bad: [\n  db:\n    image: redis\n";
+        assert_eq!(parse_yaml(misindented).unwrap().1, vec![(4, "YAML sözdizimi hatası: `bad: [`".to_string())]);
+
+        let unterminated = "services:\n  web:\n    image: \"nginx\n  db:\n    image: redis\n";
+        assert_eq!(parse_yaml(unterminated).unwrap().1, vec![(3, "YAML sözdizimi hatası: `\"nginx`".to_string())]);
+    }
+
+    #[test]
+    fn test_interpolates_variables() {
+        let env = HashMap::from([("TAG".to_string(), "1.2".to_string()), ("EMPTY".to_string(), String::new())]);
+        assert_eq!(interpolate("app:${TAG}", &env).unwrap(), "app:1.2");
+        assert_eq!(interpolate("$TAG-$$HOME", &env).unwrap(), "1.2-$HOME");
+        assert_eq!(interpolate("${EMPTY:-fallback}/${EMPTY-kept}", &env).unwrap(), "fallback/");
+        assert_eq!(interpolate("${MISSING}", &env).unwrap(), "");
+        assert_eq!(interpolate("${MISSING:?set it}", &env).unwrap_err(), "MISSING: set it");
+    }
+
+    #[test]
+    fn test_status_comes_from_compose_labels() {
+        let (dir, files) = write_files(&[("compose.yaml", "services:\n  web:\n    image: nginx\n  db:\n    image: postgres\n")]);
+        let mut project = load_project(&files, &[]).unwrap();
+        let containers = serde_json::json!([
+            { "Id": "aaaaaaaaaaaaaaaa", "Names": ["/shop-web-1"], "State": "exited", "Status": "Exited (0)",
+              "Labels": { PROJECT_LABEL: project.name, SERVICE_LABEL: "web" } },
+            { "Id": "bbbbbbbbbbbbbbbb", "Names": ["/shop-web-2"], "State": "running", "Status": "Up 1 minute",
+              "Labels": { PROJECT_LABEL: project.name, SERVICE_LABEL: "web" } }
+        ]);
+        apply_status(&mut project, &containers);
+        assert_eq!(project.services[0].status, "running");
+        assert_eq!(project.services[0].containers.len(), 2);
+        assert_eq!(project.services[0].containers[1].name, "shop-web-2");
+        assert_eq!(project.services[1].status, "not_created");
+
+        let args = project_args(&project, &["tools".to_string()]);
+        assert_eq!(&args[args.len() - 4..], &["-f".to_string(), files[0].clone(), "--profile".to_string(), "tools".to_string()]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,4 +1,42 @@
+// src-tauri/src/debug.rs
+// Debug Adapter Protocol client: launches adapters, tracks sessions and forwards stop/output events
+
+use crate::lsp::{encode_message, read_message};
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use tokio::sync::oneshot;
+
+/// How long a single request may wait for the adapter's response
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Startup waits on the debuggee too (debugpy answers `launch` only after configurationDone)
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to keep retrying the TCP connection to a freshly spawned adapter
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Placeholder in TCP adapter arguments, replaced with the chosen port
+const PORT_PLACEHOLDER: &str = "{port}";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DebugAdapterConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Talk to the adapter over TCP on this port instead of stdio; 0 picks a free port
+    pub port: Option<u16>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DebugConfig {
@@ -6,15 +44,166 @@ pub struct DebugConfig {
     pub request: String,
     pub type_: String,
     pub additional_props: Option<serde_json::Value>,
+    /// Overrides the adapter picked from `type_`
+    #[serde(default)]
+    pub adapter: Option<DebugAdapterConfig>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Breakpoint {
     pub id: String,
     pub path: String,
     pub line: u32,
     pub condition: Option<String>,
+    /// Adapter-specific hit count expression, e.g. "5" or ">= 3"
+    #[serde(default)]
+    pub hit_condition: Option<String>,
+    /// Logpoint: print this (with `{expr}` interpolation) instead of stopping
+    #[serde(default)]
+    pub log_message: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
+    /// Line the adapter actually bound, when it differs from `line`
+    #[serde(default)]
+    pub bound_line: Option<u32>,
+    /// Why the breakpoint is not verified
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FunctionBreakpoint {
+    pub id: String,
+    pub name: String,
+    pub condition: Option<String>,
+    #[serde(default)]
+    pub hit_condition: Option<String>,
+    #[serde(default)]
     pub verified: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExceptionFilter {
+    pub filter: String,
+    pub label: String,
+    pub default: bool,
+}
+
+impl Breakpoint {
+    /// Record one entry of a `setBreakpoints` response or `breakpoint` event
+    fn apply(&mut self, result: &Value) {
+        if result["verified"].as_bool().unwrap_or(false) {
+            self.verified = true;
+            self.bound_line = result["line"].as_u64().map(|line| line as u32);
+            self.message = None;
+        } else if !self.verified {
+            self.message = result["message"].as_str().map(str::to_string);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.verified = false;
+        self.bound_line = None;
+        self.message = None;
+    }
+}
+
+impl FunctionBreakpoint {
+    fn apply(&mut self, result: &Value) {
+        if result["verified"].as_bool().unwrap_or(false) {
+            self.verified = true;
+            self.message = None;
+        } else if !self.verified {
+            self.message = result["message"].as_str().map(str::to_string);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.verified = false;
+        self.message = None;
+    }
+}
+
+/// A workspace's breakpoints, persisted in `.corex/breakpoints.json`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BreakpointRegistry {
+    #[serde(default)]
+    pub breakpoints: Vec<Breakpoint>,
+    #[serde(default)]
+    pub function_breakpoints: Vec<FunctionBreakpoint>,
+    /// Enabled exception filters; `None` means the adapter's defaults
+    #[serde(default)]
+    pub exception_filters: Option<Vec<String>>,
+}
+
+impl BreakpointRegistry {
+    fn file(workspace: &Path) -> PathBuf {
+        workspace.join(".corex").join("breakpoints.json")
+    }
+
+    /// Load the saved registry; verification is per session, so it starts cleared
+    pub fn load(workspace: &Path) -> Result<Self, String> {
+        let file = Self::file(workspace);
+        if !file.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&file)
+            .map_err(|e| format!("Breakpoint dosyası okunamadı: {}", e))?;
+        let mut registry: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Breakpoint dosyası bozuk ({}): {}", file.display(), e))?;
+        registry.breakpoints.iter_mut().for_each(Breakpoint::reset);
+        registry.function_breakpoints.iter_mut().for_each(FunctionBreakpoint::reset);
+        Ok(registry)
+    }
+
+    pub fn save(&self, workspace: &Path) -> Result<(), String> {
+        let file = Self::file(workspace);
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Klasör oluşturulamadı: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&file, content).map_err(|e| format!("Breakpoint dosyası yazılamadı: {}", e))
+    }
+
+    fn in_file(&self, path: &str) -> Vec<Breakpoint> {
+        self.breakpoints.iter().filter(|bp| bp.path == path).cloned().collect()
+    }
+
+    fn files(&self) -> BTreeSet<String> {
+        self.breakpoints.iter().map(|bp| bp.path.clone()).collect()
+    }
+
+    /// Replace stored breakpoints with updated copies (matched by id)
+    fn update(&mut self, updated: &[Breakpoint]) {
+        for bp in updated {
+            if let Some(stored) = self.breakpoints.iter_mut().find(|s| s.id == bp.id) {
+                *stored = bp.clone();
+            }
+        }
+    }
+
+    /// Drop the breakpoint with `id`, after the adapter reported it `removed`
+    fn remove(&mut self, id: &str) -> bool {
+        let before = self.breakpoints.len() + self.function_breakpoints.len();
+        self.breakpoints.retain(|bp| bp.id != id);
+        self.function_breakpoints.retain(|bp| bp.id != id);
+        self.breakpoints.len() + self.function_breakpoints.len() != before
+    }
+
+    /// Apply a `breakpoint` event to whichever breakpoint has `id`; returns the updated one
+    fn apply_event(&mut self, id: &str, result: &Value) -> Option<Value> {
+        if let Some(bp) = self.breakpoints.iter_mut().find(|bp| bp.id == id) {
+            bp.reset();
+            bp.apply(result);
+            return serde_json::to_value(&*bp).ok();
+        }
+        let bp = self.function_breakpoints.iter_mut().find(|bp| bp.id == id)?;
+        bp.reset();
+        bp.apply(result);
+        serde_json::to_value(&*bp).ok()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -22,57 +211,1069 @@ pub struct Variable {
     pub name: String,
     pub value: String,
     pub type_: String,
-    pub variables_reference: u32,
+    pub variables_reference: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StackFrame {
+    pub id: i64,
+    pub name: String,
+    pub source_path: Option<String>,
+    pub line: i64,
+    pub column: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Scope {
+    pub name: String,
+    pub variables_reference: i64,
+    pub expensive: bool,
+}
+
+/// Adapter for a debug `type`, like the `type` field of a VS Code launch config
+pub fn default_adapter(type_: &str) -> Option<DebugAdapterConfig> {
+    let (command, args, port): (&str, &[&str], Option<u16>) = match type_ {
+        "lldb" | "lldb-dap" | "cppdbg" | "rust" => ("lldb-dap", &[], None),
+        "python" | "debugpy" => ("python", &["-m", "debugpy.adapter"], None),
+        // vscode-js-debug only serves DAP over TCP
+        "node" | "pwa-node" => ("js-debug-adapter", &[PORT_PLACEHOLDER, "127.0.0.1"], Some(0)),
+        _ => return None,
+    };
+    Some(DebugAdapterConfig {
+        command: command.to_string(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+        port,
+    })
+}
+
+struct DebugSession {
+    id: String,
+    /// Whose breakpoint registry this session uses
+    workspace: String,
+    config: DebugConfig,
+    writer: Mutex<Box<dyn Write + Send>>,
+    child: Mutex<Option<Child>>,
+    seq: AtomicI64,
+    pending: Mutex<HashMap<i64, oneshot::Sender<Value>>>,
+    capabilities: Mutex<Value>,
+    /// Thread of the last `stopped` event, the default target of step/continue
+    stopped_thread: Mutex<Option<i64>>,
+    /// Fired by the adapter's `initialized` event
+    initialized: Mutex<Option<oneshot::Sender<()>>>,
+    /// Adapter breakpoint ids to registry ids, for `breakpoint` events
+    breakpoint_ids: Mutex<HashMap<i64, String>>,
+}
+
+impl DebugSession {
+    fn write(&self, message: &Value) -> Result<(), String> {
+        let mut writer = self.writer.lock().map_err(|e| e.to_string())?;
+        writer.write_all(&encode_message(message))
+            .and_then(|_| writer.flush())
+            .map_err(|e| format!("Debug adapter'a yazılamadı: {}", e))
+    }
+
+    /// Send a request and return the receiver for its response
+    fn send_request(&self, command: &str, arguments: Value) -> Result<oneshot::Receiver<Value>, String> {
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().map_err(|e| e.to_string())?.insert(seq, tx);
+        self.write(&json!({
+            "seq": seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments
+        }))?;
+        Ok(rx)
+    }
+
+    /// Send a request and wait for its response body
+    async fn request(&self, command: &str, arguments: Value) -> Result<Value, String> {
+        let rx = self.send_request(command, arguments)?;
+        await_response(command, rx, REQUEST_TIMEOUT).await
+    }
+
+    fn capabilities(&self) -> Value {
+        self.capabilities.lock().map(|c| c.clone()).unwrap_or(Value::Null)
+    }
+
+    fn remember_breakpoint(&self, result: &Value, id: &str) {
+        if let (Some(adapter_id), Ok(mut ids)) = (result["id"].as_i64(), self.breakpoint_ids.lock()) {
+            ids.insert(adapter_id, id.to_string());
+        }
+    }
+
+    fn kill(&self) {
+        if let Ok(mut child) = self.child.lock() {
+            if let Some(child) = child.as_mut() {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+    }
+}
+
+async fn await_response(command: &str, rx: oneshot::Receiver<Value>, timeout: Duration) -> Result<Value, String> {
+    let response = tokio::time::timeout(timeout, rx)
+        .await
+        .map_err(|_| format!("Debug adapter '{}' isteğine yanıt vermedi", command))?
+        .map_err(|_| "Debug adapter kapandı".to_string())?;
+    check_response(response)
+}
+
+/// The body of a successful response, or the adapter's error message
+fn check_response(response: Value) -> Result<Value, String> {
+    if response["success"].as_bool().unwrap_or(false) {
+        return Ok(response.get("body").cloned().unwrap_or(Value::Null));
+    }
+    let command = response["command"].as_str().unwrap_or("?");
+    let message = response["body"]["error"]["format"].as_str()
+        .or_else(|| response["message"].as_str())
+        .unwrap_or("bilinmeyen hata");
+    Err(format!("Debug adapter '{}' hatası: {}", command, message))
+}
+
+/// Active sessions and each workspace's breakpoint registry
+#[derive(Default)]
+pub struct DebugState {
+    sessions: Mutex<HashMap<String, Arc<DebugSession>>>,
+    registries: Mutex<HashMap<String, BreakpointRegistry>>,
+}
+
+impl DebugState {
+    fn session(&self, session_id: &str) -> Result<Arc<DebugSession>, String> {
+        self.sessions.lock().map_err(|e| e.to_string())?
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| format!("Debug oturumu bulunamadı: {}", session_id))
+    }
+
+    fn sessions_for(&self, workspace: &str) -> Result<Vec<Arc<DebugSession>>, String> {
+        let sessions = self.sessions.lock().map_err(|e| e.to_string())?;
+        Ok(sessions.values().filter(|s| s.workspace == workspace).cloned().collect())
+    }
+
+    /// Run `f` on a workspace's registry, loading it from disk on first use
+    fn with_registry<T>(&self, workspace: &str, f: impl FnOnce(&mut BreakpointRegistry) -> T) -> Result<T, String> {
+        let mut registries = self.registries.lock().map_err(|e| e.to_string())?;
+        let registry = match registries.entry(workspace.to_string()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(BreakpointRegistry::load(Path::new(workspace))?),
+        };
+        Ok(f(registry))
+    }
+
+    /// Like `with_registry`, then write the registry back to disk
+    fn update_registry<T>(&self, workspace: &str, f: impl FnOnce(&mut BreakpointRegistry) -> T) -> Result<T, String> {
+        self.with_registry(workspace, |registry| {
+            let result = f(registry);
+            registry.save(Path::new(workspace)).map(|_| result)
+        })?
+    }
+}
+
+/// `launch`/`attach` arguments: the config's extra properties plus its name and type
+fn launch_arguments(config: &DebugConfig) -> Value {
+    let mut arguments = match &config.additional_props {
+        Some(Value::Object(props)) => props.clone(),
+        _ => serde_json::Map::new(),
+    };
+    arguments.entry("name").or_insert_with(|| json!(config.name));
+    arguments.entry("type").or_insert_with(|| json!(config.type_));
+    arguments.entry("request").or_insert_with(|| json!(config.request));
+    Value::Object(arguments)
+}
+
+/// Capability a breakpoint needs that the adapter does not advertise
+fn unsupported_feature(condition: bool, hit_condition: bool, log_message: bool, capabilities: &Value) -> Option<&'static str> {
+    let supports = |name: &str| capabilities[name].as_bool().unwrap_or(false);
+    if condition && !supports("supportsConditionalBreakpoints") {
+        Some("Adapter koşullu breakpoint desteklemiyor")
+    } else if hit_condition && !supports("supportsHitConditionalBreakpoints") {
+        Some("Adapter hit count koşulu desteklemiyor")
+    } else if log_message && !supports("supportsLogPoints") {
+        Some("Adapter logpoint desteklemiyor")
+    } else {
+        None
+    }
+}
+
+/// `setBreakpoints` arguments and the indices of the breakpoints actually sent
+///
+/// Breakpoints needing a feature the adapter lacks are left out rather than sent as
+/// plain breakpoints, which would stop where the user asked for a log or a condition.
+fn set_breakpoints_arguments(path: &str, breakpoints: &[Breakpoint], capabilities: &Value) -> (Vec<usize>, Value) {
+    let mut sent = Vec::new();
+    let mut lines = Vec::new();
+    for (i, bp) in breakpoints.iter().enumerate() {
+        if unsupported_feature(bp.condition.is_some(), bp.hit_condition.is_some(), bp.log_message.is_some(), capabilities).is_some() {
+            continue;
+        }
+        let mut source_bp = json!({ "line": bp.line });
+        if let Some(condition) = &bp.condition {
+            source_bp["condition"] = json!(condition);
+        }
+        if let Some(hit_condition) = &bp.hit_condition {
+            source_bp["hitCondition"] = json!(hit_condition);
+        }
+        if let Some(log_message) = &bp.log_message {
+            source_bp["logMessage"] = json!(log_message);
+        }
+        sent.push(i);
+        lines.push(source_bp);
+    }
+
+    let arguments = json!({
+        "source": { "path": path },
+        "breakpoints": lines,
+        "sourceModified": false
+    });
+    (sent, arguments)
+}
+
+/// Exception filters to enable: the stored choice, or the adapter's defaults, limited to what it offers
+fn active_exception_filters(stored: Option<&[String]>, available: &[ExceptionFilter]) -> Vec<String> {
+    match stored {
+        Some(stored) => stored.iter()
+            .filter(|f| available.iter().any(|a| &a.filter == *f))
+            .cloned()
+            .collect(),
+        None => available.iter().filter(|a| a.default).map(|a| a.filter.clone()).collect(),
+    }
+}
+
+fn exception_filters(capabilities: &Value) -> Vec<ExceptionFilter> {
+    capabilities["exceptionBreakpointFilters"].as_array().into_iter().flatten()
+        .map(|f| ExceptionFilter {
+            filter: f["filter"].as_str().unwrap_or("").to_string(),
+            label: f["label"].as_str().unwrap_or("").to_string(),
+            default: f["default"].as_bool().unwrap_or(false),
+        })
+        .collect()
+}
+
+/// Send one file's breakpoints to the given sessions and store the adapters' answers
+async fn sync_source_file(
+    state: &DebugState,
+    workspace: &str,
+    path: &str,
+    sessions: &[Arc<DebugSession>],
+) -> Result<Vec<Breakpoint>, String> {
+    let mut breakpoints = state.with_registry(workspace, |r| r.in_file(path))?;
+    breakpoints.iter_mut().for_each(Breakpoint::reset);
+
+    for session in sessions {
+        let capabilities = session.capabilities();
+        let (sent, arguments) = set_breakpoints_arguments(path, &breakpoints, &capabilities);
+        for bp in breakpoints.iter_mut().filter(|bp| !bp.verified) {
+            let feature = unsupported_feature(bp.condition.is_some(), bp.hit_condition.is_some(), bp.log_message.is_some(), &capabilities);
+            if let Some(reason) = feature {
+                bp.message = Some(reason.to_string());
+            }
+        }
+
+        match session.request("setBreakpoints", arguments).await {
+            Ok(body) => {
+                let results = body["breakpoints"].as_array().cloned().unwrap_or_default();
+                for (result, &i) in results.iter().zip(&sent) {
+                    breakpoints[i].apply(result);
+                    session.remember_breakpoint(result, &breakpoints[i].id);
+                }
+            }
+            Err(e) => warn!("⚠️ Breakpoint gönderilemedi [{}]: {}", session.id, e),
+        }
+    }
+
+    state.update_registry(workspace, |r| r.update(&breakpoints))?;
+    Ok(breakpoints)
+}
+
+/// One `setFunctionBreakpoints` entry; unset options are left out rather than sent as null
+fn function_breakpoint_argument(bp: &FunctionBreakpoint) -> Value {
+    let mut argument = json!({ "name": bp.name });
+    if let Some(condition) = &bp.condition {
+        argument["condition"] = json!(condition);
+    }
+    if let Some(hit_condition) = &bp.hit_condition {
+        argument["hitCondition"] = json!(hit_condition);
+    }
+    argument
+}
+
+async fn sync_function_breakpoints(
+    state: &DebugState,
+    workspace: &str,
+    sessions: &[Arc<DebugSession>],
+) -> Result<Vec<FunctionBreakpoint>, String> {
+    let mut breakpoints = state.with_registry(workspace, |r| r.function_breakpoints.clone())?;
+    breakpoints.iter_mut().for_each(FunctionBreakpoint::reset);
+
+    for session in sessions {
+        let capabilities = session.capabilities();
+        if !capabilities["supportsFunctionBreakpoints"].as_bool().unwrap_or(false) {
+            for bp in breakpoints.iter_mut().filter(|bp| !bp.verified) {
+                bp.message = Some("Adapter fonksiyon breakpoint desteklemiyor".to_string());
+            }
+            continue;
+        }
+
+        let mut sent = Vec::new();
+        let mut arguments = Vec::new();
+        for (i, bp) in breakpoints.iter_mut().enumerate() {
+            if let Some(reason) = unsupported_feature(bp.condition.is_some(), bp.hit_condition.is_some(), false, &capabilities) {
+                if !bp.verified {
+                    bp.message = Some(reason.to_string());
+                }
+                continue;
+            }
+            sent.push(i);
+            arguments.push(function_breakpoint_argument(bp));
+        }
+
+        match session.request("setFunctionBreakpoints", json!({ "breakpoints": arguments })).await {
+            Ok(body) => {
+                let results = body["breakpoints"].as_array().cloned().unwrap_or_default();
+                for (result, &i) in results.iter().zip(&sent) {
+                    breakpoints[i].apply(result);
+                    session.remember_breakpoint(result, &breakpoints[i].id);
+                }
+            }
+            Err(e) => warn!("⚠️ Fonksiyon breakpoint gönderilemedi [{}]: {}", session.id, e),
+        }
+    }
+
+    state.update_registry(workspace, |r| {
+        for bp in &breakpoints {
+            if let Some(stored) = r.function_breakpoints.iter_mut().find(|s| s.id == bp.id) {
+                *stored = bp.clone();
+            }
+        }
+    })?;
+    Ok(breakpoints)
+}
+
+async fn sync_exception_breakpoints(state: &DebugState, workspace: &str, sessions: &[Arc<DebugSession>]) -> Result<(), String> {
+    let stored = state.with_registry(workspace, |r| r.exception_filters.clone())?;
+    for session in sessions {
+        let available = exception_filters(&session.capabilities());
+        if available.is_empty() {
+            continue;
+        }
+        let filters = active_exception_filters(stored.as_deref(), &available);
+        if let Err(e) = session.request("setExceptionBreakpoints", json!({ "filters": filters })).await {
+            warn!("⚠️ Exception breakpoint gönderilemedi [{}]: {}", session.id, e);
+        }
+    }
+    Ok(())
+}
+
+fn free_port() -> Result<u16, String> {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| format!("Boş port bulunamadı: {}", e))
+}
+
+type AdapterIo = (Child, Box<dyn Read + Send>, Box<dyn Write + Send>);
+
+/// Spawn the adapter and open its DAP stream (stdio, or TCP for server-style adapters)
+async fn spawn_adapter(adapter: &DebugAdapterConfig, cwd: Option<&str>) -> Result<AdapterIo, String> {
+    let port = match adapter.port {
+        Some(0) => Some(free_port()?),
+        port => port,
+    };
+
+    let mut cmd = Command::new(&adapter.command);
+    match port {
+        Some(port) => cmd.args(adapter.args.iter().map(|a| a.replace(PORT_PLACEHOLDER, &port.to_string()))),
+        None => cmd.args(&adapter.args),
+    };
+    if let Some(cwd) = cwd {
+        cmd.current_dir(cwd);
+    }
+    cmd.stdin(if port.is_some() { Stdio::null() } else { Stdio::piped() });
+    cmd.stdout(if port.is_some() { Stdio::null() } else { Stdio::piped() });
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd.spawn()
+        .map_err(|e| format!("Debug adapter başlatılamadı ({}): {}", adapter.command, e))?;
+
+    if let Some(stderr) = child.stderr.take() {
+        let command = adapter.command.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                warn!("[DAP {}] stderr: {}", command, line);
+            }
+        });
+    }
+
+    let Some(port) = port else {
+        let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
+        let stdin = child.stdin.take().ok_or("Failed to open stdin")?;
+        return Ok((child, Box::new(stdout), Box::new(stdin)));
+    };
+
+    let started = Instant::now();
+    let stream = loop {
+        match TcpStream::connect(("127.0.0.1", port)) {
+            Ok(stream) => break stream,
+            Err(e) if started.elapsed() >= CONNECT_TIMEOUT => {
+                let _ = child.kill();
+                return Err(format!("Debug adapter'a bağlanılamadı (port {}): {}", port, e));
+            }
+            Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+        }
+    };
+    let writer = stream.try_clone().map_err(|e| e.to_string())?;
+    Ok((child, Box::new(stream), Box::new(writer)))
+}
+
+fn emit_event<R: Runtime>(app: &AppHandle<R>, event: &str, payload: Value) {
+    if let Err(e) = app.emit(event, payload) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+}
+
+/// Drop the session from state; true if it was still registered
+fn end_session<R: Runtime>(app: &AppHandle<R>, session_id: &str) -> bool {
+    app.try_state::<DebugState>()
+        .and_then(|state| state.sessions.lock().ok().and_then(|mut s| s.remove(session_id)))
+        .is_some()
+}
+
+fn read_loop<R: Runtime>(app: AppHandle<R>, session: Arc<DebugSession>, stream: Box<dyn Read + Send>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        match read_message(&mut reader) {
+            Ok(Some(message)) => handle_message(&app, &session, message),
+            Ok(None) => break,
+            Err(e) => {
+                error!("❌ [DAP {}] okuma hatası: {}", session.id, e);
+                break;
+            }
+        }
+    }
+
+    // Pending requests fail once their senders are dropped
+    if let Ok(mut pending) = session.pending.lock() {
+        pending.clear();
+    }
+    session.kill();
+    if end_session(&app, &session.id) {
+        info!("🛑 Debug adapter kapandı: {}", session.id);
+        emit_event(&app, "debug-terminated", json!({ "session_id": session.id }));
+    }
+}
+
+fn handle_message<R: Runtime>(app: &AppHandle<R>, session: &Arc<DebugSession>, message: Value) {
+    let body = message.get("body").cloned().unwrap_or(Value::Null);
+    match message["type"].as_str() {
+        Some("response") => {
+            let sender = message["request_seq"].as_i64()
+                .and_then(|seq| session.pending.lock().ok()?.remove(&seq));
+            if let Some(sender) = sender {
+                let _ = sender.send(message);
+            }
+        }
+        Some("event") => match message["event"].as_str().unwrap_or("") {
+            "initialized" => {
+                if let Some(sender) = session.initialized.lock().ok().and_then(|mut s| s.take()) {
+                    let _ = sender.send(());
+                }
+            }
+            "stopped" => {
+                let thread_id = body["threadId"].as_i64();
+                if let Ok(mut stopped) = session.stopped_thread.lock() {
+                    *stopped = thread_id.or(*stopped);
+                }
+                emit_event(app, "debug-stopped", json!({
+                    "session_id": session.id,
+                    "reason": body["reason"],
+                    "thread_id": thread_id,
+                    "description": body.get("description"),
+                    "text": body.get("text"),
+                    "all_threads_stopped": body["allThreadsStopped"].as_bool().unwrap_or(false),
+                    "hit_breakpoint_ids": body.get("hitBreakpointIds")
+                }));
+            }
+            "continued" => {
+                emit_event(app, "debug-continued", json!({
+                    "session_id": session.id,
+                    "thread_id": body["threadId"]
+                }));
+            }
+            "output" => {
+                emit_event(app, "debug-output", json!({
+                    "session_id": session.id,
+                    "category": body["category"].as_str().unwrap_or("console"),
+                    "output": body["output"].as_str().unwrap_or(""),
+                    "source_path": body["source"]["path"].as_str(),
+                    "line": body["line"].as_i64()
+                }));
+            }
+            "exited" => {
+                emit_event(app, "debug-exited", json!({
+                    "session_id": session.id,
+                    "exit_code": body["exitCode"]
+                }));
+            }
+            "breakpoint" => {
+                let adapter_id = body["breakpoint"]["id"].as_i64();
+                let id = adapter_id.and_then(|id| session.breakpoint_ids.lock().ok()?.get(&id).cloned());
+                let (Some(id), Some(state)) = (id, app.try_state::<DebugState>()) else {
+                    return;
+                };
+                if body["reason"] == "removed" {
+                    if let (Some(adapter_id), Ok(mut ids)) = (adapter_id, session.breakpoint_ids.lock()) {
+                        ids.remove(&adapter_id);
+                    }
+                    match state.update_registry(&session.workspace, |r| r.remove(&id)) {
+                        Ok(true) => emit_event(app, "debug-breakpoint-removed", json!({
+                            "session_id": session.id,
+                            "id": id
+                        })),
+                        Ok(false) => {}
+                        Err(e) => warn!("⚠️ Breakpoint silinemedi: {}", e),
+                    }
+                    return;
+                }
+                let updated = state.update_registry(&session.workspace, |r| r.apply_event(&id, &body["breakpoint"]));
+                match updated {
+                    Ok(Some(breakpoint)) => emit_event(app, "debug-breakpoint-changed", json!({
+                        "session_id": session.id,
+                        "breakpoint": breakpoint
+                    })),
+                    Ok(None) => {}
+                    Err(e) => warn!("⚠️ Breakpoint güncellenemedi: {}", e),
+                }
+            }
+            "terminated" => {
+                // The debuggee is gone; let the adapter shut down too
+                let _ = session.send_request("disconnect", json!({ "terminateDebuggee": false }));
+                if end_session(app, &session.id) {
+                    info!("🏁 Debug oturumu bitti: {}", session.id);
+                    emit_event(app, "debug-terminated", json!({ "session_id": session.id }));
+                }
+            }
+            _ => {}
+        },
+        // Reverse requests (runInTerminal, startDebugging) are not supported
+        Some("request") => {
+            let command = message["command"].as_str().unwrap_or("");
+            let seq = session.seq.fetch_add(1, Ordering::Relaxed);
+            let _ = session.write(&json!({
+                "seq": seq,
+                "type": "response",
+                "request_seq": message["seq"],
+                "command": command,
+                "success": false,
+                "message": format!("'{}' desteklenmiyor", command)
+            }));
+        }
+        _ => {}
+    }
+}
+
+/// initialize -> launch/attach -> (initialized) breakpoints -> configurationDone
+async fn configure_session(
+    state: &DebugState,
+    session: &Arc<DebugSession>,
+    initialized: oneshot::Receiver<()>,
+) -> Result<(), String> {
+    let capabilities = session.request("initialize", json!({
+        "clientID": "corex",
+        "clientName": "CorexAI",
+        "adapterID": session.config.type_,
+        "locale": "tr",
+        "linesStartAt1": true,
+        "columnsStartAt1": true,
+        "pathFormat": "path",
+        "supportsVariableType": true,
+        "supportsRunInTerminalRequest": false
+    })).await?;
+    *session.capabilities.lock().map_err(|e| e.to_string())? = capabilities.clone();
+
+    // The launch response may only come after configurationDone, so don't wait for it yet
+    let launch = session.send_request(&session.config.request, launch_arguments(&session.config))?;
+
+    tokio::time::timeout(STARTUP_TIMEOUT, initialized)
+        .await
+        .map_err(|_| "Debug adapter 'initialized' olayını göndermedi".to_string())?
+        .map_err(|_| "Debug adapter kapandı".to_string())?;
+
+    // Re-send the workspace's saved breakpoints before the debuggee starts running
+    let sessions = std::slice::from_ref(session);
+    let files = state.with_registry(&session.workspace, |r| r.files())?;
+    for path in &files {
+        sync_source_file(state, &session.workspace, path, sessions).await?;
+    }
+    sync_function_breakpoints(state, &session.workspace, sessions).await?;
+    sync_exception_breakpoints(state, &session.workspace, sessions).await?;
+
+    if capabilities["supportsConfigurationDoneRequest"].as_bool().unwrap_or(false) {
+        session.request("configurationDone", json!({})).await?;
+    }
+
+    await_response(&session.config.request, launch, STARTUP_TIMEOUT).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn start_debug_session<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DebugState>,
+    workspace: String,
+    config: DebugConfig,
+) -> Result<String, String> {
+    if config.request != "launch" && config.request != "attach" {
+        return Err(format!("Geçersiz request: {} (launch veya attach olmalı)", config.request));
+    }
+    let adapter = config.adapter.clone()
+        .or_else(|| default_adapter(&config.type_))
+        .ok_or_else(|| format!("'{}' için debug adapter bulunamadı", config.type_))?;
+
+    info!("🐞 Debug oturumu başlatılıyor: {} ({})", config.name, adapter.command);
+    let cwd = config.additional_props.as_ref()
+        .and_then(|props| props["cwd"].as_str())
+        .map(str::to_string);
+    let (child, reader, writer) = spawn_adapter(&adapter, cwd.as_deref()).await?;
+
+    let session_id = format!("session_{}", uuid::Uuid::new_v4().simple());
+    let (initialized_tx, initialized_rx) = oneshot::channel();
+    let session = Arc::new(DebugSession {
+        id: session_id.clone(),
+        workspace,
+        config,
+        writer: Mutex::new(writer),
+        child: Mutex::new(Some(child)),
+        seq: AtomicI64::new(1),
+        pending: Mutex::new(HashMap::new()),
+        capabilities: Mutex::new(Value::Null),
+        stopped_thread: Mutex::new(None),
+        initialized: Mutex::new(Some(initialized_tx)),
+        breakpoint_ids: Mutex::new(HashMap::new()),
+    });
+    state.sessions.lock().map_err(|e| e.to_string())?.insert(session_id.clone(), session.clone());
+
+    let app_clone = app.clone();
+    let session_clone = session.clone();
+    std::thread::spawn(move || read_loop(app_clone, session_clone, reader));
+
+    if let Err(e) = configure_session(&state, &session, initialized_rx).await {
+        error!("❌ Debug oturumu başlatılamadı: {}", e);
+        state.sessions.lock().map_err(|e| e.to_string())?.remove(&session_id);
+        session.kill();
+        return Err(e);
+    }
+
+    info!("✅ Debug oturumu hazır: {}", session_id);
+    Ok(session_id)
 }
 
 #[tauri::command]
-pub async fn start_debug_session(_config: DebugConfig) -> Result<String, String> {
-    // Şimdilik mock oturum ID'si döndürüyoruz
-    Ok("session_12345".to_string())
+pub async fn stop_debug_session(state: State<'_, DebugState>, session_id: String) -> Result<(), String> {
+    let session = state.session(&session_id)?;
+    state.sessions.lock().map_err(|e| e.to_string())?.remove(&session_id);
+
+    let terminate = session.config.request == "launch";
+    if let Err(e) = session.request("disconnect", json!({ "terminateDebuggee": terminate })).await {
+        warn!("⚠️ disconnect başarısız, adapter sonlandırılıyor: {}", e);
+    }
+    session.kill();
+    info!("🛑 Debug oturumu durduruldu: {}", session_id);
+    Ok(())
 }
 
+/// Add or replace the breakpoint at `path:line`; it is sent to the workspace's running sessions
+///
+/// `hit_condition` and `log_message` turn it into a hit-count breakpoint or a logpoint.
 #[tauri::command]
-pub async fn set_breakpoint(path: String, line: u32, condition: Option<String>) -> Result<Breakpoint, String> {
-    Ok(Breakpoint {
-        id: format!("bp_{}_{}", path, line),
-        path,
+pub async fn set_breakpoint(
+    state: State<'_, DebugState>,
+    workspace: String,
+    path: String,
+    line: u32,
+    condition: Option<String>,
+    hit_condition: Option<String>,
+    log_message: Option<String>,
+) -> Result<Breakpoint, String> {
+    let id = format!("bp_{}_{}", path, line);
+    let breakpoint = Breakpoint {
+        id: id.clone(),
+        path: path.clone(),
         line,
         condition,
-        verified: true,
-    })
+        hit_condition,
+        log_message,
+        verified: false,
+        bound_line: None,
+        message: None,
+    };
+    state.update_registry(&workspace, |r| {
+        r.breakpoints.retain(|bp| bp.id != id);
+        r.breakpoints.push(breakpoint.clone());
+    })?;
+
+    let sessions = state.sessions_for(&workspace)?;
+    let synced = sync_source_file(&state, &workspace, &path, &sessions).await?;
+    Ok(synced.into_iter().find(|bp| bp.id == id).unwrap_or(breakpoint))
 }
 
 #[tauri::command]
-pub async fn remove_breakpoint(_id: String) -> Result<(), String> {
-    Ok(())
+pub async fn set_function_breakpoint(
+    state: State<'_, DebugState>,
+    workspace: String,
+    name: String,
+    condition: Option<String>,
+    hit_condition: Option<String>,
+) -> Result<FunctionBreakpoint, String> {
+    let id = format!("fn_{}", name);
+    let breakpoint = FunctionBreakpoint {
+        id: id.clone(),
+        name,
+        condition,
+        hit_condition,
+        verified: false,
+        message: None,
+    };
+    state.update_registry(&workspace, |r| {
+        r.function_breakpoints.retain(|bp| bp.id != id);
+        r.function_breakpoints.push(breakpoint.clone());
+    })?;
+
+    let sessions = state.sessions_for(&workspace)?;
+    let synced = sync_function_breakpoints(&state, &workspace, &sessions).await?;
+    Ok(synced.into_iter().find(|bp| bp.id == id).unwrap_or(breakpoint))
 }
 
+/// Remove a source or function breakpoint by id
 #[tauri::command]
-pub async fn debug_continue(_session_id: String) -> Result<(), String> {
-    Ok(())
+pub async fn remove_breakpoint(state: State<'_, DebugState>, workspace: String, id: String) -> Result<(), String> {
+    let removed_path = state.update_registry(&workspace, |r| {
+        if let Some(position) = r.breakpoints.iter().position(|bp| bp.id == id) {
+            return Ok(Some(r.breakpoints.remove(position).path));
+        }
+        if let Some(position) = r.function_breakpoints.iter().position(|bp| bp.id == id) {
+            r.function_breakpoints.remove(position);
+            return Ok(None);
+        }
+        Err(format!("Breakpoint bulunamadı: {}", id))
+    })??;
+
+    let sessions = state.sessions_for(&workspace)?;
+    match removed_path {
+        Some(path) => sync_source_file(&state, &workspace, &path, &sessions).await.map(|_| ()),
+        None => sync_function_breakpoints(&state, &workspace, &sessions).await.map(|_| ()),
+    }
 }
 
 #[tauri::command]
-pub async fn debug_step_over(_session_id: String) -> Result<(), String> {
-    Ok(())
+pub async fn list_breakpoints(state: State<'_, DebugState>, workspace: String) -> Result<BreakpointRegistry, String> {
+    state.with_registry(&workspace, |r| r.clone())
 }
 
+/// Choose which exception filters (e.g. "raised", "uncaught") stop the debuggee
 #[tauri::command]
-pub async fn debug_step_into(_session_id: String) -> Result<(), String> {
-    Ok(())
+pub async fn set_exception_breakpoints(
+    state: State<'_, DebugState>,
+    workspace: String,
+    filters: Vec<String>,
+) -> Result<(), String> {
+    state.update_registry(&workspace, |r| r.exception_filters = Some(filters))?;
+    let sessions = state.sessions_for(&workspace)?;
+    sync_exception_breakpoints(&state, &workspace, &sessions).await
 }
 
+/// Exception filters the session's adapter offers
 #[tauri::command]
-pub async fn debug_step_out(_session_id: String) -> Result<(), String> {
+pub async fn get_exception_filters(state: State<'_, DebugState>, session_id: String) -> Result<Vec<ExceptionFilter>, String> {
+    Ok(exception_filters(&state.session(&session_id)?.capabilities()))
+}
+
+/// Explicit thread, else the last stopped one, else the first thread the adapter reports
+async fn resolve_thread(session: &DebugSession, thread_id: Option<i64>) -> Result<i64, String> {
+    if let Some(thread_id) = thread_id {
+        return Ok(thread_id);
+    }
+    let stopped = *session.stopped_thread.lock().map_err(|e| e.to_string())?;
+    if let Some(thread_id) = stopped {
+        return Ok(thread_id);
+    }
+    let body = session.request("threads", json!({})).await?;
+    body["threads"][0]["id"].as_i64().ok_or_else(|| "Debug adapter thread bildirmedi".to_string())
+}
+
+async fn thread_request(state: &DebugState, session_id: &str, thread_id: Option<i64>, command: &str) -> Result<(), String> {
+    let session = state.session(session_id)?;
+    let thread_id = resolve_thread(&session, thread_id).await?;
+    session.request(command, json!({ "threadId": thread_id })).await?;
     Ok(())
 }
 
 #[tauri::command]
-pub async fn get_variables(_session_id: String, _frame_id: u32) -> Result<Vec<Variable>, String> {
-    Ok(vec![])
+pub async fn debug_continue(state: State<'_, DebugState>, session_id: String, thread_id: Option<i64>) -> Result<(), String> {
+    thread_request(&state, &session_id, thread_id, "continue").await
+}
+
+#[tauri::command]
+pub async fn debug_step_over(state: State<'_, DebugState>, session_id: String, thread_id: Option<i64>) -> Result<(), String> {
+    thread_request(&state, &session_id, thread_id, "next").await
+}
+
+#[tauri::command]
+pub async fn debug_step_into(state: State<'_, DebugState>, session_id: String, thread_id: Option<i64>) -> Result<(), String> {
+    thread_request(&state, &session_id, thread_id, "stepIn").await
+}
+
+#[tauri::command]
+pub async fn debug_step_out(state: State<'_, DebugState>, session_id: String, thread_id: Option<i64>) -> Result<(), String> {
+    thread_request(&state, &session_id, thread_id, "stepOut").await
+}
+
+#[tauri::command]
+pub async fn get_stack_trace(
+    state: State<'_, DebugState>,
+    session_id: String,
+    thread_id: Option<i64>,
+) -> Result<Vec<StackFrame>, String> {
+    let session = state.session(&session_id)?;
+    let thread_id = resolve_thread(&session, thread_id).await?;
+    let body = session.request("stackTrace", json!({ "threadId": thread_id })).await?;
+
+    Ok(body["stackFrames"].as_array().into_iter().flatten()
+        .map(|frame| StackFrame {
+            id: frame["id"].as_i64().unwrap_or(0),
+            name: frame["name"].as_str().unwrap_or("").to_string(),
+            source_path: frame["source"]["path"].as_str().map(str::to_string),
+            line: frame["line"].as_i64().unwrap_or(0),
+            column: frame["column"].as_i64().unwrap_or(0),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_scopes(state: State<'_, DebugState>, session_id: String, frame_id: i64) -> Result<Vec<Scope>, String> {
+    let session = state.session(&session_id)?;
+    let body = session.request("scopes", json!({ "frameId": frame_id })).await?;
+
+    Ok(body["scopes"].as_array().into_iter().flatten()
+        .map(|scope| Scope {
+            name: scope["name"].as_str().unwrap_or("").to_string(),
+            variables_reference: scope["variablesReference"].as_i64().unwrap_or(0),
+            expensive: scope["expensive"].as_bool().unwrap_or(false),
+        })
+        .collect())
+}
+
+/// Children of `variables_reference`, or the first cheap scope (locals) of `frame_id`
+#[tauri::command]
+pub async fn get_variables(
+    state: State<'_, DebugState>,
+    session_id: String,
+    frame_id: Option<i64>,
+    variables_reference: Option<i64>,
+) -> Result<Vec<Variable>, String> {
+    let session = state.session(&session_id)?;
+
+    let reference = match (variables_reference, frame_id) {
+        (Some(reference), _) => reference,
+        (None, Some(frame_id)) => {
+            let body = session.request("scopes", json!({ "frameId": frame_id })).await?;
+            let scopes = body["scopes"].as_array().cloned().unwrap_or_default();
+            match scopes.iter().find(|s| !s["expensive"].as_bool().unwrap_or(false)).or(scopes.first()) {
+                Some(scope) => scope["variablesReference"].as_i64().unwrap_or(0),
+                None => return Ok(Vec::new()),
+            }
+        }
+        (None, None) => return Err("frame_id veya variables_reference gerekli".to_string()),
+    };
+
+    let body = session.request("variables", json!({ "variablesReference": reference })).await?;
+    Ok(body["variables"].as_array().into_iter().flatten()
+        .map(|variable| Variable {
+            name: variable["name"].as_str().unwrap_or("").to_string(),
+            value: variable["value"].as_str().unwrap_or("").to_string(),
+            type_: variable["type"].as_str().unwrap_or("").to_string(),
+            variables_reference: variable["variablesReference"].as_i64().unwrap_or(0),
+        })
+        .collect())
 }
 
 #[tauri::command]
-pub async fn evaluate_expression(_session_id: String, expression: String) -> Result<String, String> {
-    Ok(format!("Evaluated: {}", expression))
+pub async fn evaluate_expression(
+    state: State<'_, DebugState>,
+    session_id: String,
+    expression: String,
+    frame_id: Option<i64>,
+) -> Result<String, String> {
+    let session = state.session(&session_id)?;
+    let mut arguments = json!({ "expression": expression, "context": "repl" });
+    if let Some(frame_id) = frame_id {
+        arguments["frameId"] = json!(frame_id);
+    }
+    let body = session.request("evaluate", arguments).await?;
+    Ok(body["result"].as_str().unwrap_or("").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(props: Option<Value>) -> DebugConfig {
+        DebugConfig {
+            name: "Run app".to_string(),
+            request: "launch".to_string(),
+            type_: "python".to_string(),
+            additional_props: props,
+            adapter: None,
+        }
+    }
+
+    #[test]
+    fn test_launch_arguments_keep_explicit_props() {
+        let args = launch_arguments(&config(Some(json!({ "program": "main.py", "type": "debugpy" }))));
+        assert_eq!(args["program"], "main.py");
+        assert_eq!(args["type"], "debugpy");
+        assert_eq!(args["name"], "Run app");
+        assert_eq!(args["request"], "launch");
+
+        assert_eq!(launch_arguments(&config(None))["type"], "python");
+    }
+
+    #[test]
+    fn test_default_adapters() {
+        let python = default_adapter("python").unwrap();
+        assert_eq!(python.args, vec!["-m", "debugpy.adapter"]);
+        assert_eq!(python.port, None);
+
+        let node = default_adapter("pwa-node").unwrap();
+        assert_eq!(node.port, Some(0));
+        assert!(node.args.iter().any(|a| a == PORT_PLACEHOLDER));
+
+        assert!(default_adapter("cobol").is_none());
+    }
+
+    fn breakpoint(id: &str, line: u32) -> Breakpoint {
+        Breakpoint {
+            id: id.to_string(),
+            path: "/w/a.py".to_string(),
+            line,
+            condition: None,
+            hit_condition: None,
+            log_message: None,
+            verified: false,
+            bound_line: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_set_breakpoints_arguments_skip_unsupported_features() {
+        let breakpoints = vec![
+            breakpoint("a", 3),
+            Breakpoint { condition: Some("x > 1".into()), hit_condition: Some(">= 2".into()), ..breakpoint("b", 9) },
+            Breakpoint { log_message: Some("x = {x}".into()), ..breakpoint("c", 12) },
+        ];
+
+        let full = json!({
+            "supportsConditionalBreakpoints": true,
+            "supportsHitConditionalBreakpoints": true,
+            "supportsLogPoints": true
+        });
+        let (sent, args) = set_breakpoints_arguments("/w/a.py", &breakpoints, &full);
+        assert_eq!(sent, vec![0, 1, 2]);
+        assert_eq!(args["source"]["path"], "/w/a.py");
+        assert_eq!(args["breakpoints"], json!([
+            { "line": 3 },
+            { "line": 9, "condition": "x > 1", "hitCondition": ">= 2" },
+            { "line": 12, "logMessage": "x = {x}" }
+        ]));
+
+        // A logpoint the adapter can't handle must not become a stopping breakpoint
+        let (sent, args) = set_breakpoints_arguments("/w/a.py", &breakpoints, &json!({ "supportsConditionalBreakpoints": true }));
+        assert_eq!(sent, vec![0]);
+        assert_eq!(args["breakpoints"], json!([{ "line": 3 }]));
+    }
+
+    #[test]
+    fn test_breakpoint_records_bound_line() {
+        let mut bp = breakpoint("a", 3);
+        bp.apply(&json!({ "verified": false, "message": "No code at line 3" }));
+        assert!(!bp.verified);
+        assert_eq!(bp.message.as_deref(), Some("No code at line 3"));
+
+        bp.apply(&json!({ "id": 7, "verified": true, "line": 5 }));
+        assert!(bp.verified);
+        assert_eq!(bp.bound_line, Some(5));
+        assert_eq!(bp.message, None);
+    }
+
+    #[test]
+    fn test_removed_breakpoints_and_function_arguments() {
+        let function_bp = |id: &str, condition: Option<&str>| FunctionBreakpoint {
+            id: id.to_string(),
+            name: "main".to_string(),
+            condition: condition.map(str::to_string),
+            hit_condition: None,
+            verified: false,
+            message: None,
+        };
+        assert_eq!(function_breakpoint_argument(&function_bp("f", None)), json!({ "name": "main" }));
+        assert_eq!(function_breakpoint_argument(&function_bp("f", Some("x"))), json!({ "name": "main", "condition": "x" }));
+
+        let mut registry = BreakpointRegistry {
+            breakpoints: vec![breakpoint("a", 3), breakpoint("b", 5)],
+            function_breakpoints: vec![function_bp("f", None)],
+            exception_filters: None,
+        };
+        assert!(registry.remove("a"));
+        assert!(registry.remove("f"));
+        assert!(!registry.remove("a"));
+        assert_eq!(registry.breakpoints.iter().map(|bp| bp.id.as_str()).collect::<Vec<_>>(), vec!["b"]);
+        assert!(registry.function_breakpoints.is_empty());
+    }
+
+    #[test]
+    fn test_registry_persists_without_verification() {
+        let workspace = std::env::temp_dir().join(format!("corex_breakpoints_{}", uuid::Uuid::new_v4()));
+        let mut registry = BreakpointRegistry {
+            breakpoints: vec![Breakpoint { verified: true, bound_line: Some(4), ..breakpoint("a", 3) }],
+            function_breakpoints: Vec::new(),
+            exception_filters: Some(vec!["uncaught".to_string()]),
+        };
+        registry.save(&workspace).unwrap();
+
+        let loaded = BreakpointRegistry::load(&workspace).unwrap();
+        registry.breakpoints[0].reset();
+        assert_eq!(loaded, registry);
+        assert_eq!(BreakpointRegistry::load(&workspace.join("missing")).unwrap(), BreakpointRegistry::default());
+
+        let _ = std::fs::remove_dir_all(&workspace);
+    }
+
+    #[test]
+    fn test_exception_filter_selection() {
+        let available = exception_filters(&json!({
+            "exceptionBreakpointFilters": [
+                { "filter": "raised", "label": "Raised Exceptions", "default": false },
+                { "filter": "uncaught", "label": "Uncaught Exceptions", "default": true }
+            ]
+        }));
+        assert_eq!(active_exception_filters(None, &available), vec!["uncaught"]);
+
+        let stored = vec!["raised".to_string(), "userUnhandled".to_string()];
+        assert_eq!(active_exception_filters(Some(&stored), &available), vec!["raised"]);
+        assert!(active_exception_filters(Some(&[]), &available).is_empty());
+    }
+
+    #[test]
+    fn test_check_response() {
+        let ok = json!({ "type": "response", "success": true, "command": "threads", "body": { "threads": [] } });
+        assert_eq!(check_response(ok).unwrap(), json!({ "threads": [] }));
+
+        let failed = json!({
+            "type": "response", "success": false, "command": "evaluate",
+            "message": "evaluate failed", "body": { "error": { "format": "name 'y' is not defined" } }
+        });
+        assert_eq!(check_response(failed).unwrap_err(), "Debug adapter 'evaluate' hatası: name 'y' is not defined");
+    }
 }
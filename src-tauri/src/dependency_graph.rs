@@ -0,0 +1,691 @@
+// src-tauri/src/dependency_graph.rs
+// Workspace-wide import graph: resolves imports to files and answers dependents / cycle queries
+
+use crate::commands::analyze_file;
+use crate::symbol_index::{SymbolIndex, SymbolIndexState};
+use crate::tree_sitter_parser::FileAnalysis;
+use crate::workspace_indexer::collect_workspace_files;
+use log::info;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use tauri::State;
+
+/// Files whose imports we know how to resolve
+const GRAPH_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "py", "rs", "go"];
+
+/// Every source language the parser extracts definitions from; config formats are left out
+const SYMBOL_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "py", "rs", "go",
+    "java", "c", "h", "hh", "hpp", "hxx", "cc", "cpp", "cxx", "cs",
+];
+
+/// Extensions tried for extension-less TS/JS imports, in order
+const SCRIPT_EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "js", "jsx", "mts", "cts", "mjs", "cjs"];
+
+/// The graph built for the most recent workspace
+#[derive(Default)]
+pub struct DependencyGraphState {
+    graph: Mutex<Option<DependencyGraph>>,
+}
+
+impl DependencyGraphState {
+    /// Fill `dependencies`/`dependents` if the file is part of the current graph
+    pub fn fill(&self, analysis: &mut FileAnalysis) {
+        if let Ok(graph) = self.graph.lock() {
+            if let Some(graph) = graph.as_ref() {
+                graph.fill(analysis);
+            }
+        }
+    }
+
+    /// Run a query against the current graph
+    pub fn with_graph<T>(&self, f: impl FnOnce(&DependencyGraph) -> T) -> Result<T, String> {
+        let graph = self.graph.lock().map_err(|e| e.to_string())?;
+        let graph = graph.as_ref()
+            .ok_or("Bağımlılık grafiği oluşturulmamış, önce build_dependency_graph çağırın")?;
+        Ok(f(graph))
+    }
+}
+
+/// `go.mod` location and module path, for resolving in-module Go imports
+#[derive(Debug, Clone)]
+pub struct GoModule {
+    pub dir: PathBuf,
+    pub path: String,
+}
+
+/// Directed import graph between workspace files
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    root: PathBuf,
+    /// file -> files it imports
+    dependencies: HashMap<String, BTreeSet<String>>,
+    /// file -> files importing it
+    dependents: HashMap<String, BTreeSet<String>>,
+}
+
+/// Maps normalized paths back to the path strings the analyses use
+struct FileIndex {
+    files: HashMap<PathBuf, String>,
+}
+
+impl FileIndex {
+    fn get(&self, path: &Path) -> Option<&String> {
+        self.files.get(&normalize(path))
+    }
+
+    fn first_of<I: IntoIterator<Item = PathBuf>>(&self, candidates: I) -> Option<String> {
+        candidates.into_iter().find_map(|c| self.get(&c).cloned())
+    }
+}
+
+impl DependencyGraph {
+    /// Build the graph from already-parsed files
+    pub fn from_analyses(root: &Path, go_module: Option<&GoModule>, analyses: &[FileAnalysis]) -> Self {
+        // Files in languages without import resolution only feed the symbol index
+        let analyses: Vec<&FileAnalysis> = analyses.iter()
+            .filter(|a| is_graph_file(Path::new(&a.file_path)))
+            .collect();
+        let index = FileIndex {
+            files: analyses.iter()
+                .map(|a| (normalize(Path::new(&a.file_path)), a.file_path.clone()))
+                .collect(),
+        };
+
+        let mut graph = DependencyGraph {
+            root: root.to_path_buf(),
+            ..Default::default()
+        };
+
+        for analysis in analyses {
+            let file = Path::new(&analysis.file_path);
+            let mut targets = BTreeSet::new();
+            for import in &analysis.imports {
+                targets.extend(resolve_import(root, go_module, &index, file, import));
+            }
+            targets.remove(&analysis.file_path);
+
+            for target in &targets {
+                graph.dependents.entry(target.clone()).or_default().insert(analysis.file_path.clone());
+            }
+            graph.dependencies.insert(analysis.file_path.clone(), targets);
+        }
+
+        graph
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.dependencies.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.dependencies.values().map(|d| d.len()).sum()
+    }
+
+    pub fn dependencies_of(&self, file: &str) -> Vec<String> {
+        self.dependencies.get(file).map(|d| d.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    pub fn dependents_of(&self, file: &str) -> Vec<String> {
+        self.dependents.get(file).map(|d| d.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    /// Copy both edge directions into an analysis of a file in this graph
+    pub fn fill(&self, analysis: &mut FileAnalysis) {
+        if self.dependencies.contains_key(&analysis.file_path) {
+            analysis.dependencies = self.dependencies_of(&analysis.file_path);
+            analysis.dependents = self.dependents_of(&analysis.file_path);
+        }
+    }
+
+    /// Every file that reaches `file` through one or more imports
+    pub fn transitive_dependents(&self, file: &str) -> Vec<String> {
+        Self::reachable(&self.dependents, file)
+    }
+
+    /// Every file `file` reaches through one or more imports
+    pub fn transitive_dependencies(&self, file: &str) -> Vec<String> {
+        Self::reachable(&self.dependencies, file)
+    }
+
+    fn reachable(edges: &HashMap<String, BTreeSet<String>>, file: &str) -> Vec<String> {
+        let mut seen = BTreeSet::new();
+        let mut queue = VecDeque::from([file.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            for next in edges.get(&current).into_iter().flatten() {
+                if next != file && seen.insert(next.clone()) {
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    /// Import cycles, one sorted list of files per strongly connected component
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let mut nodes: Vec<&String> = self.dependencies.keys().collect();
+        nodes.sort();
+
+        let mut tarjan = Tarjan::default();
+        for node in nodes {
+            if !tarjan.index.contains_key(node.as_str()) {
+                tarjan.connect(self, node);
+            }
+        }
+
+        let mut cycles: Vec<Vec<String>> = tarjan.components
+            .into_iter()
+            .filter(|c| c.len() > 1)
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+}
+
+/// Tarjan's strongly connected components
+#[derive(Default)]
+struct Tarjan<'g> {
+    next_index: usize,
+    index: HashMap<&'g str, usize>,
+    low_link: HashMap<&'g str, usize>,
+    stack: Vec<&'g str>,
+    on_stack: BTreeSet<&'g str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'g> Tarjan<'g> {
+    /// Iterative, so a long import chain can't overflow the stack: `work` holds every node
+    /// being visited together with its remaining dependencies
+    fn connect(&mut self, graph: &'g DependencyGraph, root: &'g str) {
+        let dependencies = |node: &'g str| graph.dependencies.get(node).into_iter().flatten();
+        self.open(root);
+        let mut work = vec![(root, dependencies(root))];
+
+        while let Some((node, remaining)) = work.last_mut() {
+            let node = *node;
+            if let Some(next) = remaining.next() {
+                let next = next.as_str();
+                if !self.index.contains_key(next) {
+                    self.open(next);
+                    work.push((next, dependencies(next)));
+                } else if self.on_stack.contains(next) {
+                    let low = self.low_link[node].min(self.index[next]);
+                    self.low_link.insert(node, low);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some((parent, _)) = work.last() {
+                let low = self.low_link[parent].min(self.low_link[node]);
+                self.low_link.insert(parent, low);
+            }
+            if self.low_link[node] == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn open(&mut self, node: &'g str) {
+        self.index.insert(node, self.next_index);
+        self.low_link.insert(node, self.next_index);
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack.insert(node);
+    }
+}
+
+// ---------- import resolution ----------
+
+fn resolve_import(root: &Path, go_module: Option<&GoModule>, index: &FileIndex, file: &Path, import: &str) -> Vec<String> {
+    let extension = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+    match extension {
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => resolve_script(index, file, import).into_iter().collect(),
+        "py" => resolve_python(root, index, file, import).into_iter().collect(),
+        "rs" => resolve_rust(index, file, import),
+        "go" => resolve_go(go_module, index, file, import),
+        _ => Vec::new(),
+    }
+}
+
+/// Relative specifiers only; bare specifiers are packages
+fn resolve_script(index: &FileIndex, file: &Path, spec: &str) -> Option<String> {
+    if !spec.starts_with('.') {
+        return None;
+    }
+    let base = file.parent()?.join(spec);
+
+    let mut candidates = vec![base.clone()];
+    // ESM-style TypeScript imports name the emitted `.js` file
+    if let Some(stem) = spec.strip_suffix(".js").or_else(|| spec.strip_suffix(".mjs")) {
+        let stem = file.parent()?.join(stem);
+        candidates.extend(["ts", "tsx", "mts"].iter().map(|ext| with_suffix(&stem, ext)));
+    }
+    candidates.extend(SCRIPT_EXTENSIONS.iter().map(|ext| with_suffix(&base, ext)));
+    candidates.extend(SCRIPT_EXTENSIONS.iter().map(|ext| base.join(format!("index.{}", ext))));
+
+    index.first_of(candidates)
+}
+
+/// `.models` / `..pkg.mod` relative to the file, `a.b` from the workspace root
+fn resolve_python(root: &Path, index: &FileIndex, file: &Path, module: &str) -> Option<String> {
+    let dots = module.chars().take_while(|c| *c == '.').count();
+    let rest: Vec<&str> = module[dots..].split('.').filter(|s| !s.is_empty()).collect();
+
+    let bases: Vec<PathBuf> = if dots > 0 {
+        let mut base = file.parent()?.to_path_buf();
+        for _ in 1..dots {
+            base = base.parent()?.to_path_buf();
+        }
+        vec![base]
+    } else {
+        // Script-style sibling imports, then the usual project layouts
+        vec![file.parent()?.to_path_buf(), root.to_path_buf(), root.join("src")]
+    };
+
+    bases.into_iter().find_map(|base| {
+        let module_path = rest.iter().fold(base, |path, segment| path.join(segment));
+        index.first_of([
+            with_suffix(&module_path, "py"),
+            module_path.join("__init__.py"),
+        ])
+    })
+}
+
+/// `mod foo`, `crate::a::b`, `super::x`, `self::y`, and 2015-style `a::b`
+fn resolve_rust(index: &FileIndex, file: &Path, import: &str) -> Vec<String> {
+    if let Some(name) = import.strip_prefix("mod ") {
+        let dir = rust_module_dir(file);
+        return index.first_of([dir.join(format!("{}.rs", name)), dir.join(name).join("mod.rs")])
+            .into_iter()
+            .collect();
+    }
+
+    let crate_root = rust_crate_root(index, file);
+    let mut resolved: Vec<String> = expand_use_tree(import)
+        .into_iter()
+        .filter_map(|path| {
+            let mut segments: Vec<&str> = path.split("::").filter(|s| !s.is_empty() && *s != "*").collect();
+            // Paths anchored at crate/self/super stay inside this crate
+            let (base, anchored) = match segments.first().copied() {
+                Some("crate") => {
+                    segments.remove(0);
+                    (crate_root.clone()?, true)
+                }
+                Some("self") => {
+                    segments.remove(0);
+                    (rust_module_dir(file), true)
+                }
+                Some("super") => {
+                    let mut dir = rust_module_dir(file);
+                    while segments.first() == Some(&"super") {
+                        segments.remove(0);
+                        dir = dir.parent()?.to_path_buf();
+                    }
+                    (dir, true)
+                }
+                // Binary crates and 2015 code name top-level modules directly
+                Some(_) => (crate_root.clone()?, false),
+                None => return None,
+            };
+
+            // The longest prefix that is a module file wins; the rest are items
+            for len in (1..=segments.len()).rev() {
+                let module_path = segments[..len].iter().fold(base.clone(), |path, segment| path.join(segment));
+                if let Some(found) = index.first_of([with_suffix(&module_path, "rs"), module_path.join("mod.rs")]) {
+                    return Some(found);
+                }
+            }
+
+            // `use super::*`, `use crate::Item`: the item lives in the base module's own file
+            if !anchored {
+                return None;
+            }
+            index.first_of([
+                base.join("mod.rs"),
+                with_suffix(&base, "rs"),
+                base.join("lib.rs"),
+                base.join("main.rs"),
+            ])
+        })
+        .collect();
+    resolved.sort();
+    resolved.dedup();
+    resolved
+}
+
+fn is_rust_module_root(path: &Path) -> bool {
+    matches!(path.file_name().and_then(|n| n.to_str()), Some("mod.rs" | "lib.rs" | "main.rs"))
+}
+
+/// Directory holding the submodules of `file`'s module
+fn rust_module_dir(file: &Path) -> PathBuf {
+    let parent = file.parent().unwrap_or(Path::new("")).to_path_buf();
+    if is_rust_module_root(file) {
+        return parent;
+    }
+    match file.file_stem() {
+        Some(stem) => parent.join(stem),
+        None => parent,
+    }
+}
+
+/// Nearest ancestor directory with a `lib.rs` or `main.rs`
+fn rust_crate_root(index: &FileIndex, file: &Path) -> Option<PathBuf> {
+    file.ancestors()
+        .skip(1)
+        .find(|dir| index.get(&dir.join("lib.rs")).is_some() || index.get(&dir.join("main.rs")).is_some())
+        .map(|dir| dir.to_path_buf())
+}
+
+/// `a::{b, c::{d, e as f}}` -> ["a::b", "a::c::d", "a::c::e"]
+fn expand_use_tree(tree: &str) -> Vec<String> {
+    let tree = tree.trim();
+    let Some(open) = tree.find('{') else {
+        let path = tree.split(" as ").next().unwrap_or(tree);
+        return vec![path.split_whitespace().collect()];
+    };
+    let prefix: String = tree[..open].split_whitespace().collect();
+    let inner = tree[open + 1..].trim_end().strip_suffix('}').unwrap_or(&tree[open + 1..]);
+
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&inner[start..]);
+
+    let mut paths = Vec::new();
+    for item in items.into_iter().map(str::trim).filter(|item| !item.is_empty()) {
+        for rest in expand_use_tree(item) {
+            paths.push(match rest.as_str() {
+                "self" => prefix.trim_end_matches("::").to_string(),
+                _ => format!("{}{}", prefix, rest),
+            });
+        }
+    }
+    paths
+}
+
+/// In-module packages only: every non-test `.go` file in the package directory
+fn resolve_go(go_module: Option<&GoModule>, index: &FileIndex, file: &Path, import: &str) -> Vec<String> {
+    let Some(module) = go_module else { return Vec::new() };
+    let relative = if import == module.path {
+        ""
+    } else {
+        match import.strip_prefix(&format!("{}/", module.path)) {
+            Some(rest) => rest,
+            None => return Vec::new(),
+        }
+    };
+
+    let package_dir = normalize(&module.dir.join(relative));
+    let mut files: Vec<String> = index.files.iter()
+        .filter(|(path, _)| {
+            path.parent() == Some(package_dir.as_path())
+                && path.extension().and_then(|e| e.to_str()) == Some("go")
+                && !path.to_string_lossy().ends_with("_test.go")
+                && path.as_path() != normalize(file)
+        })
+        .map(|(_, original)| original.clone())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Read the module path from `<root>/go.mod`
+fn read_go_module(root: &Path) -> Option<GoModule> {
+    let content = std::fs::read_to_string(root.join("go.mod")).ok()?;
+    let path = content.lines()
+        .find_map(|line| line.trim().strip_prefix("module "))?
+        .trim()
+        .trim_matches('"')
+        .to_string();
+    Some(GoModule { dir: root.to_path_buf(), path })
+}
+
+fn with_suffix(path: &Path, extension: &str) -> PathBuf {
+    let mut os = path.as_os_str().to_owned();
+    os.push(".");
+    os.push(extension);
+    PathBuf::from(os)
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| extensions.contains(&ext))
+        .unwrap_or(false)
+}
+
+fn is_graph_file(path: &Path) -> bool {
+    has_extension(path, GRAPH_EXTENSIONS)
+}
+
+/// Parse every file under `root` with one of `extensions`
+async fn analyze_workspace(root: &Path, extensions: &[&str]) -> Vec<FileAnalysis> {
+    let mut analyses = Vec::new();
+    for path in collect_workspace_files(root).into_iter().filter(|p| has_extension(p, extensions)) {
+        match analyze_file(&path.to_string_lossy()).await {
+            Ok(analysis) => analyses.push(analysis),
+            Err(e) => info!("ℹ️ Grafik dışı bırakıldı [{}]: {}", path.display(), e),
+        }
+    }
+    analyses
+}
+
+/// Build a graph for `root` without replacing the one in `DependencyGraphState`
+pub async fn graph_for_workspace(root: &Path) -> DependencyGraph {
+    let analyses = analyze_workspace(root, GRAPH_EXTENSIONS).await;
+    DependencyGraph::from_analyses(root, read_go_module(root).as_ref(), &analyses)
+}
+
+// ---------- commands ----------
+
+#[derive(Debug, Serialize)]
+pub struct DependencyGraphSummary {
+    pub root: String,
+    pub files: usize,
+    pub edges: usize,
+    pub cycles: Vec<Vec<String>>,
+    /// Definitions in the symbol index built from the same parse
+    pub symbols: usize,
+}
+
+/// Parse every source file under `root` and build its import graph
+///
+/// The workspace symbol index used for go-to-definition is rebuilt from the same parse, which
+/// also covers languages the graph cannot resolve imports for (Java, C/C++, C#).
+#[tauri::command]
+pub async fn build_dependency_graph(
+    root: String,
+    state: State<'_, DependencyGraphState>,
+    symbol_state: State<'_, SymbolIndexState>,
+) -> Result<DependencyGraphSummary, String> {
+    info!("🕸️ Bağımlılık grafiği oluşturuluyor: {}", root);
+
+    let root_path = PathBuf::from(&root);
+    if !root_path.is_dir() {
+        return Err(format!("Klasör bulunamadı: {}", root));
+    }
+
+    let analyses = analyze_workspace(&root_path, SYMBOL_EXTENSIONS).await;
+    let graph = DependencyGraph::from_analyses(&root_path, read_go_module(&root_path).as_ref(), &analyses);
+    let symbol_index = SymbolIndex::from_analyses(&analyses);
+    let summary = DependencyGraphSummary {
+        root,
+        files: graph.file_count(),
+        edges: graph.edge_count(),
+        cycles: graph.find_cycles(),
+        symbols: symbol_index.len(),
+    };
+
+    info!("✅ Grafik hazır: {} dosya, {} bağımlılık, {} döngü", summary.files, summary.edges, summary.cycles.len());
+    *state.graph.lock().map_err(|e| e.to_string())? = Some(graph);
+    symbol_state.replace(symbol_index)?;
+    Ok(summary)
+}
+
+/// Files that import `file_path` directly or through other files
+#[tauri::command]
+pub async fn get_transitive_dependents(
+    file_path: String,
+    state: State<'_, DependencyGraphState>,
+) -> Result<Vec<String>, String> {
+    state.with_graph(|graph| graph.transitive_dependents(&file_path))
+}
+
+/// Import cycles in the current graph
+#[tauri::command]
+pub async fn find_dependency_cycles(state: State<'_, DependencyGraphState>) -> Result<Vec<Vec<String>>, String> {
+    state.with_graph(|graph| graph.find_cycles())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis(path: &str, imports: &[&str]) -> FileAnalysis {
+        FileAnalysis {
+            file_path: path.to_string(),
+            symbols: Vec::new(),
+            imports: imports.iter().map(|i| i.to_string()).collect(),
+            exports: Vec::new(),
+            complexity: 0,
+            metrics: Default::default(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolves_each_language() {
+        let analyses = vec![
+            analysis("/ws/web/app.ts", &["./util", "../shared/index.js", "react"]),
+            analysis("/ws/web/util.tsx", &[]),
+            analysis("/ws/shared/index.ts", &[]),
+            analysis("/ws/py/app.py", &[".models", "py.helpers", "os"]),
+            analysis("/ws/py/models/__init__.py", &[]),
+            analysis("/ws/py/helpers.py", &[]),
+            analysis("/ws/src/main.rs", &["mod commands", "commands::{run, Config}", "std::fmt"]),
+            analysis("/ws/src/commands.rs", &["crate::db::{self, pool::Pool}", "super::main_helper"]),
+            analysis("/ws/src/db/mod.rs", &[]),
+            analysis("/ws/src/db/pool.rs", &["super::*"]),
+            analysis("/ws/cmd/main.go", &["example.com/m/pkg/store", "fmt"]),
+            analysis("/ws/pkg/store/store.go", &[]),
+            analysis("/ws/pkg/store/cache.go", &[]),
+            analysis("/ws/pkg/store/store_test.go", &[]),
+        ];
+        let go = GoModule { dir: PathBuf::from("/ws"), path: "example.com/m".to_string() };
+        let graph = DependencyGraph::from_analyses(Path::new("/ws"), Some(&go), &analyses);
+
+        assert_eq!(graph.dependencies_of("/ws/web/app.ts"), vec!["/ws/shared/index.ts", "/ws/web/util.tsx"]);
+        assert_eq!(graph.dependencies_of("/ws/py/app.py"), vec!["/ws/py/helpers.py", "/ws/py/models/__init__.py"]);
+        assert_eq!(graph.dependencies_of("/ws/src/main.rs"), vec!["/ws/src/commands.rs"]);
+        // `super::main_helper` is an item of main.rs itself
+        assert_eq!(graph.dependencies_of("/ws/src/commands.rs"), vec!["/ws/src/db/mod.rs", "/ws/src/db/pool.rs", "/ws/src/main.rs"]);
+        assert_eq!(graph.dependencies_of("/ws/src/db/pool.rs"), vec!["/ws/src/db/mod.rs"]);
+        assert_eq!(graph.dependencies_of("/ws/cmd/main.go"), vec!["/ws/pkg/store/cache.go", "/ws/pkg/store/store.go"]);
+
+        let mut filled = analysis("/ws/src/db/mod.rs", &[]);
+        graph.fill(&mut filled);
+        assert_eq!(filled.dependents, vec!["/ws/src/commands.rs", "/ws/src/db/pool.rs"]);
+        assert_eq!(graph.find_cycles(), vec![vec!["/ws/src/commands.rs", "/ws/src/main.rs"]]);
+    }
+
+    #[test]
+    fn test_graph_skips_languages_without_import_resolution() {
+        let analyses = vec![
+            analysis("/ws/a.ts", &["./b"]),
+            analysis("/ws/b.ts", &[]),
+            analysis("/ws/Main.java", &["com.example.Util"]),
+            analysis("/ws/util.cpp", &["util.h"]),
+        ];
+        let graph = DependencyGraph::from_analyses(Path::new("/ws"), None, &analyses);
+
+        assert_eq!(graph.file_count(), 2);
+        assert!(graph.dependencies_of("/ws/Main.java").is_empty());
+        assert!(has_extension(Path::new("/ws/Main.java"), SYMBOL_EXTENSIONS));
+        assert!(!has_extension(Path::new("/ws/package.json"), SYMBOL_EXTENSIONS));
+    }
+
+    #[test]
+    fn test_transitive_dependents_and_cycles() {
+        let analyses = vec![
+            analysis("/ws/a.ts", &["./b"]),
+            analysis("/ws/b.ts", &["./c"]),
+            analysis("/ws/c.ts", &["./a"]),
+            analysis("/ws/d.ts", &["./c"]),
+            analysis("/ws/e.ts", &["./d"]),
+        ];
+        let graph = DependencyGraph::from_analyses(Path::new("/ws"), None, &analyses);
+
+        assert_eq!(graph.transitive_dependents("/ws/d.ts"), vec!["/ws/e.ts"]);
+        assert_eq!(graph.transitive_dependents("/ws/c.ts"), vec!["/ws/a.ts", "/ws/b.ts", "/ws/d.ts", "/ws/e.ts"]);
+        assert_eq!(graph.find_cycles(), vec![vec!["/ws/a.ts", "/ws/b.ts", "/ws/c.ts"]]);
+    }
+
+    #[test]
+    fn test_cycles_in_a_long_import_chain() {
+        // m0 -> m1 -> ... -> m49999 -> m49998
+        let count = 50_000;
+        let analyses: Vec<FileAnalysis> = (0..count)
+            .map(|i| {
+                let next = if i + 1 < count { i + 1 } else { i - 1 };
+                analysis(&format!("/ws/m{}.ts", i), &[&format!("./m{}", next)])
+            })
+            .collect();
+        let graph = DependencyGraph::from_analyses(Path::new("/ws"), None, &analyses);
+
+        assert_eq!(graph.find_cycles(), vec![vec!["/ws/m49998.ts", "/ws/m49999.ts"]]);
+    }
+
+    #[test]
+    fn test_expand_use_tree() {
+        assert_eq!(expand_use_tree("crate::a::{b, c::{d, e as f}}"), vec!["crate::a::b", "crate::a::c::d", "crate::a::c::e"]);
+        assert_eq!(expand_use_tree("crate::db::{self, Pool}"), vec!["crate::db", "crate::db::Pool"]);
+        assert_eq!(expand_use_tree("std::io::Result as IoResult"), vec!["std::io::Result"]);
+    }
+}
@@ -0,0 +1,812 @@
+// src-tauri/src/devcontainer.rs
+// Dev containers: reads `.devcontainer/devcontainer.json`, builds or pulls the image and starts
+// a container with the workspace mounted. While a workspace is in container mode its commands
+// and test runs go through `docker exec`.
+//
+// Container mode is recorded in `.corex/container.json` rather than in managed state, so it
+// survives a restart of the app.
+
+use crate::docker::DockerClient;
+use crate::launch_config::strip_jsonc;
+use log::{error, info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Label the devcontainer CLI and VS Code put on containers, so they find ours and we find theirs
+const LOCAL_FOLDER_LABEL: &str = "devcontainer.local_folder";
+/// Hash of the resolved config the container was created from
+const CONFIG_LABEL: &str = "corex.devcontainer.config";
+
+/// Name in the build context archive of a Dockerfile that lies outside the context
+const CONTEXT_DOCKERFILE: &str = ".corex.Dockerfile";
+
+/// Keeps a container with an overridden command alive until it is stopped
+const KEEP_ALIVE: &str = "echo Container started; trap 'exit 0' 15; while sleep 1 & wait $!; do :; done";
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DevContainerBuild {
+    pub dockerfile: Option<String>,
+    pub context: Option<String>,
+    pub args: BTreeMap<String, String>,
+    pub target: Option<String>,
+}
+
+/// The parts of devcontainer.json we act on
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DevContainerConfig {
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub build: Option<DevContainerBuild>,
+    /// Pre-`build` spelling of `build.dockerfile`
+    #[serde(rename = "dockerFile")]
+    pub docker_file: Option<String>,
+    pub context: Option<String>,
+    pub docker_compose_file: Option<Value>,
+    pub workspace_folder: Option<String>,
+    pub workspace_mount: Option<String>,
+    pub mounts: Vec<Value>,
+    pub container_env: BTreeMap<String, String>,
+    pub remote_env: BTreeMap<String, Option<String>>,
+    pub container_user: Option<String>,
+    pub remote_user: Option<String>,
+    pub run_args: Vec<String>,
+    pub forward_ports: Vec<Value>,
+    pub override_command: Option<bool>,
+    pub post_create_command: Option<Value>,
+    pub post_start_command: Option<Value>,
+}
+
+/// A workspace in container mode
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DevContainerSession {
+    pub workspace: String,
+    pub container_id: String,
+    pub image: String,
+    /// Where the workspace is mounted inside the container
+    pub workspace_folder: String,
+    pub remote_user: Option<String>,
+    pub remote_env: Vec<(String, String)>,
+}
+
+/// Collected output of a command run in the container
+#[derive(Debug, Default)]
+pub struct ExecOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i64,
+}
+
+/// devcontainer.json in the places the spec looks, first match wins
+fn find_config(workspace: &Path) -> Option<PathBuf> {
+    let folder = workspace.join(".devcontainer");
+    let candidates = [folder.join("devcontainer.json"), workspace.join(".devcontainer.json")];
+    if let Some(found) = candidates.into_iter().find(|p| p.is_file()) {
+        return Some(found);
+    }
+    // `.devcontainer/<name>/devcontainer.json` for repos with several configurations
+    let mut nested: Vec<PathBuf> = std::fs::read_dir(&folder).ok()?
+        .flatten()
+        .map(|e| e.path().join("devcontainer.json"))
+        .filter(|p| p.is_file())
+        .collect();
+    nested.sort();
+    nested.into_iter().next()
+}
+
+fn basename(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "workspace".to_string())
+}
+
+/// `${localWorkspaceFolder}`, `${localWorkspaceFolderBasename}`, `${containerWorkspaceFolder}`,
+/// `${containerWorkspaceFolderBasename}`, `${localEnv:NAME[:default]}` and
+/// `${containerEnv:NAME[:default]}` (only when `container_env` is known)
+fn substitute(text: &str, variables: &HashMap<&str, String>, container_env: Option<&HashMap<String, String>>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            result.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let expression = &rest[start + 2..start + end];
+        let whole = &rest[start..start + end + 1];
+        rest = &rest[start + end + 1..];
+
+        let mut parts = expression.splitn(3, ':');
+        let value = match (parts.next(), parts.next(), parts.next()) {
+            (Some("localEnv" | "env"), Some(name), default) => {
+                Some(std::env::var(name).ok().or(default.map(str::to_string)).unwrap_or_default())
+            }
+            (Some("containerEnv"), Some(name), default) => container_env.map(|env| {
+                env.get(name).cloned().or(default.map(str::to_string)).unwrap_or_default()
+            }),
+            (Some(name), None, _) => variables.get(name).cloned(),
+            _ => None,
+        };
+        // Unknown variables are left for a later pass (or the user) to see
+        result.push_str(value.as_deref().unwrap_or(whole));
+    }
+    result.push_str(rest);
+    result
+}
+
+fn substitute_value(value: &mut Value, variables: &HashMap<&str, String>) {
+    match value {
+        Value::String(text) => *text = substitute(text, variables, None),
+        Value::Array(items) => items.iter_mut().for_each(|item| substitute_value(item, variables)),
+        Value::Object(map) => map.values_mut().for_each(|item| substitute_value(item, variables)),
+        _ => {}
+    }
+}
+
+/// Reads the workspace's devcontainer.json with its variables resolved; returns the config,
+/// its path and the config hash that decides whether an existing container still fits
+pub fn load_config(workspace: &Path) -> Result<(DevContainerConfig, PathBuf, String), String> {
+    let path = find_config(workspace)
+        .ok_or_else(|| format!("devcontainer.json bulunamadı: {}", workspace.display()))?;
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("{} okunamadı: {}", path.display(), e))?;
+    let mut raw: Value = serde_json::from_str(&strip_jsonc(&text))
+        .map_err(|e| format!("{} geçersiz (satır {}): {}", path.display(), e.line(), e))?;
+
+    let folder = raw["workspaceFolder"].as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("/workspaces/{}", basename(workspace)));
+    let variables = HashMap::from([
+        ("localWorkspaceFolder", workspace.to_string_lossy().to_string()),
+        ("localWorkspaceFolderBasename", basename(workspace)),
+        ("containerWorkspaceFolder", folder.clone()),
+        ("containerWorkspaceFolderBasename", basename(Path::new(&folder))),
+    ]);
+    substitute_value(&mut raw, &variables);
+
+    let hash = hex::encode(Sha256::digest(raw.to_string().as_bytes()))[..16].to_string();
+    let mut config: DevContainerConfig = serde_json::from_value(raw)
+        .map_err(|e| format!("{} geçersiz: {}", path.display(), e))?;
+    if config.docker_compose_file.is_some() {
+        return Err("dockerComposeFile tabanlı devcontainer yapılandırmaları desteklenmiyor".to_string());
+    }
+    if config.image.is_none() && config.build.is_none() && config.docker_file.is_none() {
+        return Err(format!("{}: `image` veya `build.dockerfile` gerekli", path.display()));
+    }
+    config.workspace_folder = Some(folder);
+    Ok((config, path, hash))
+}
+
+impl DevContainerSession {
+    fn file(workspace: &Path) -> PathBuf {
+        workspace.join(".corex").join("container.json")
+    }
+
+    /// The saved session of the workspace containing `path`, without checking its container
+    fn saved(path: &Path) -> Option<Self> {
+        let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        path.ancestors().find_map(|dir| {
+            let content = std::fs::read_to_string(Self::file(dir)).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+    }
+
+    /// The session of the workspace containing `path`, if that workspace is in container mode
+    /// and its container is running. A session whose container is gone or stopped is cleared,
+    /// so commands fall back to the host.
+    pub async fn find(path: &Path) -> Option<Self> {
+        let session = Self::saved(path)?;
+        match DockerClient::from_env().inspect_container(&session.container_id).await {
+            Ok(Some(container)) if container["State"]["Running"].as_bool() == Some(true) => Some(session),
+            Ok(_) => {
+                warn!("⚠️ Dev container çalışmıyor, komutlar host'ta çalışacak: {}", session.container_id);
+                if let Err(e) = std::fs::remove_file(Self::file(Path::new(&session.workspace))) {
+                    warn!("⚠️ Container oturumu silinemedi: {}", e);
+                }
+                None
+            }
+            Err(e) => {
+                warn!("⚠️ Dev container durumu alınamadı, komutlar host'ta çalışacak: {}", e);
+                None
+            }
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let file = Self::file(Path::new(&self.workspace));
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&file, content).map_err(|e| format!("Container oturumu kaydedilemedi: {}", e))
+    }
+
+    /// `path` as seen from inside the container, when it is under the workspace
+    fn container_path(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.workspace).ok()?;
+        let mut mapped = self.workspace_folder.trim_end_matches('/').to_string();
+        for component in relative.components() {
+            mapped.push('/');
+            mapped.push_str(&component.as_os_str().to_string_lossy());
+        }
+        Some(mapped)
+    }
+
+    /// Container workspace paths in `text`, such as a report written in the container, mapped
+    /// back to the host
+    pub fn host_paths(&self, text: &str) -> String {
+        let folder = format!("{}/", self.workspace_folder.trim_end_matches('/'));
+        text.replace(&folder, &format!("{}/", self.workspace.trim_end_matches(['/', '\\'])))
+    }
+
+    /// Workspace paths in an argument, alone or as `--flag=path`, rewritten for the container
+    fn container_arg(&self, arg: &str) -> String {
+        if let Some(mapped) = self.container_path(Path::new(arg)) {
+            return mapped;
+        }
+        match arg.split_once('=') {
+            Some((flag, value)) => match self.container_path(Path::new(value)) {
+                Some(mapped) => format!("{}={}", flag, mapped),
+                None => arg.to_string(),
+            },
+            None => arg.to_string(),
+        }
+    }
+
+    /// Engine API exec config that runs `program` in the container path of `cwd`
+    fn exec_config(&self, program: &str, args: &[String], cwd: &Path, env: &[(String, String)]) -> Value {
+        let cwd = std::fs::canonicalize(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+        let cmd: Vec<String> = std::iter::once(program.to_string())
+            .chain(args.iter().map(|arg| self.container_arg(arg)))
+            .collect();
+        let env: Vec<String> = self.remote_env.iter().chain(env).map(|(key, value)| format!("{}={}", key, value)).collect();
+        let mut config = json!({
+            "Cmd": cmd,
+            "WorkingDir": self.container_path(&cwd).unwrap_or_else(|| self.workspace_folder.clone()),
+            "Env": env,
+        });
+        if let Some(user) = &self.remote_user {
+            config["User"] = json!(user);
+        }
+        config
+    }
+
+    /// Runs `program` in the container, in the container path of `cwd`, passing each output
+    /// line to `on_line` as `(stream, line)`. Returns the exit code.
+    pub async fn exec(
+        &self,
+        client: &DockerClient,
+        program: &str,
+        args: &[String],
+        cwd: &Path,
+        env: &[(String, String)],
+        on_line: impl FnMut(&str, &str),
+    ) -> Result<i64, String> {
+        client.exec(&self.container_id, self.exec_config(program, args, cwd, env), on_line).await
+    }
+
+    /// `exec` through the default Docker client, collecting the output
+    pub async fn output(&self, program: &str, args: &[String], cwd: &Path) -> Result<ExecOutput, String> {
+        let (mut stdout, mut stderr) = (String::new(), String::new());
+        let exit_code = self
+            .exec(&DockerClient::from_env(), program, args, cwd, &[], |stream, line| {
+                let buffer = if stream == "stderr" { &mut stderr } else { &mut stdout };
+                buffer.push_str(line);
+                buffer.push('\n');
+            })
+            .await?;
+        Ok(ExecOutput { stdout, stderr, exit_code })
+    }
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, workspace: &Path, stage: &str, line: &str) {
+    if let Err(e) = app.emit("devcontainer-progress", json!({
+        "workspace": workspace.to_string_lossy(),
+        "stage": stage,
+        "line": line
+    })) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+}
+
+/// Lifecycle commands are a string for the shell, an argv array, or an object of either
+fn lifecycle_commands(command: &Value) -> Vec<(String, Vec<String>)> {
+    match command {
+        Value::String(script) => vec![("sh".to_string(), vec!["-c".to_string(), script.clone()])],
+        Value::Array(argv) => {
+            let argv: Vec<String> = argv.iter().filter_map(|a| a.as_str().map(str::to_string)).collect();
+            match argv.split_first() {
+                Some((program, args)) => vec![(program.clone(), args.to_vec())],
+                None => Vec::new(),
+            }
+        }
+        Value::Object(commands) => commands.values().flat_map(lifecycle_commands).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// A `--mount` style spec (`type=bind,source=a,target=b,readonly`) as an Engine API mount
+fn parse_mount(spec: &str) -> Value {
+    let mut mount = json!({ "Type": "volume" });
+    for field in spec.split(',') {
+        let (key, value) = field.split_once('=').unwrap_or((field, "true"));
+        let key = match key.trim() {
+            "type" => "Type",
+            "source" | "src" => "Source",
+            "target" | "destination" | "dst" => "Target",
+            "consistency" => "Consistency",
+            "readonly" | "ro" => {
+                mount["ReadOnly"] = json!(value != "false" && value != "0");
+                continue;
+            }
+            _ => continue,
+        };
+        mount[key] = json!(value.trim());
+    }
+    mount
+}
+
+/// `runArgs` are `docker run` flags; those with an Engine API equivalent are applied, the
+/// rest are skipped with a warning
+fn apply_run_args(run_args: &[String], config: &mut Value) {
+    let mut args = run_args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match flag {
+            "--privileged" => config["HostConfig"]["Privileged"] = json!(true),
+            "--init" => config["HostConfig"]["Init"] = json!(true),
+            "--cap-add" | "--cap-drop" | "--security-opt" | "--add-host" | "-e" | "--env" | "--network" | "--net" => {
+                let Some(value) = inline.or_else(|| args.next().cloned()) else {
+                    break;
+                };
+                let (section, key) = match flag {
+                    "--cap-add" => ("HostConfig", "CapAdd"),
+                    "--cap-drop" => ("HostConfig", "CapDrop"),
+                    "--security-opt" => ("HostConfig", "SecurityOpt"),
+                    "--add-host" => ("HostConfig", "ExtraHosts"),
+                    "--network" | "--net" => {
+                        config["HostConfig"]["NetworkMode"] = json!(value);
+                        continue;
+                    }
+                    _ => ("", "Env"),
+                };
+                let list = match section {
+                    "" => &mut config[key],
+                    section => &mut config[section][key],
+                };
+                match list.as_array_mut() {
+                    Some(values) => values.push(json!(value)),
+                    None => *list = json!([value]),
+                }
+            }
+            _ => warn!("⚠️ devcontainer runArgs desteklenmiyor, atlandı: {}", arg),
+        }
+    }
+}
+
+/// Engine API config for a new container of `config`, labelled so later opens find it
+fn create_config(workspace: &Path, config: &DevContainerConfig, config_file: &Path, hash: &str, image: &str) -> Value {
+    let folder = config.workspace_folder.clone().unwrap_or_default();
+    let workspace_mount = config.workspace_mount.clone()
+        .unwrap_or_else(|| format!("type=bind,source={},target={}", workspace.display(), folder));
+    let mut mounts = vec![parse_mount(&workspace_mount)];
+    for mount in &config.mounts {
+        let spec = match mount {
+            Value::String(mount) => mount.clone(),
+            Value::Object(fields) => fields.iter()
+                .filter_map(|(key, value)| value.as_str().map(|v| format!("{}={}", key, v)))
+                .collect::<Vec<_>>()
+                .join(","),
+            _ => continue,
+        };
+        mounts.push(parse_mount(&spec));
+    }
+
+    // `host:port` entries point at other compose services; only plain ports are published
+    let mut exposed = serde_json::Map::new();
+    let mut bindings = serde_json::Map::new();
+    for port in config.forward_ports.iter().filter_map(Value::as_u64) {
+        exposed.insert(format!("{}/tcp", port), json!({}));
+        bindings.insert(format!("{}/tcp", port), json!([{ "HostIp": "127.0.0.1", "HostPort": port.to_string() }]));
+    }
+
+    let mut create = json!({
+        "Image": image,
+        "Labels": {
+            LOCAL_FOLDER_LABEL: workspace.display().to_string(),
+            "devcontainer.config_file": config_file.display().to_string(),
+            CONFIG_LABEL: hash,
+        },
+        "Env": config.container_env.iter().map(|(key, value)| format!("{}={}", key, value)).collect::<Vec<_>>(),
+        "ExposedPorts": exposed,
+        "HostConfig": {
+            "Mounts": mounts,
+            "PortBindings": bindings,
+        },
+    });
+    if let Some(user) = &config.container_user {
+        create["User"] = json!(user);
+    }
+    if config.override_command.unwrap_or(true) {
+        create["Entrypoint"] = json!(["/bin/sh"]);
+        create["Cmd"] = json!(["-c", KEEP_ALIVE]);
+    }
+    apply_run_args(&config.run_args, &mut create);
+    create
+}
+
+/// `.dockerignore` entries as anchored regexes; `*` and `?` stay within a path segment,
+/// `**` spans any number of them. Negations (`!`) are not supported.
+fn dockerignore_patterns(context: &Path) -> Vec<Regex> {
+    let content = std::fs::read_to_string(context.join(".dockerignore")).unwrap_or_default();
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| {
+            let pattern = regex::escape(line.trim_matches('/'))
+                .replace(r"\*\*/", "(.*/)?")
+                .replace(r"\*\*", ".*")
+                .replace(r"\*", "[^/]*")
+                .replace(r"\?", "[^/]");
+            Regex::new(&format!("^{}(/.*)?$", pattern)).ok()
+        })
+        .collect()
+}
+
+/// Tar archive of the build context for the Engine API and the Dockerfile's path in it.
+/// A Dockerfile outside the context (or excluded by `.dockerignore`) is added under
+/// `CONTEXT_DOCKERFILE`.
+fn build_context(context: &Path, dockerfile: &Path) -> Result<(Vec<u8>, String), String> {
+    let ignored = dockerignore_patterns(context);
+    let relative = |path: &Path| -> Option<String> {
+        let parts: Vec<String> = path.strip_prefix(context).ok()?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        Some(parts.join("/"))
+    };
+    let is_ignored = |name: &str| ignored.iter().any(|pattern| pattern.is_match(name));
+
+    let mut archive = tar::Builder::new(Vec::new());
+    archive.follow_symlinks(false);
+    let entries = walkdir::WalkDir::new(context)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| relative(entry.path()).is_some_and(|name| !is_ignored(&name)));
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Build context okunamadı: {}", e))?;
+        let Some(name) = relative(entry.path()) else {
+            continue;
+        };
+        archive.append_path_with_name(entry.path(), &name)
+            .map_err(|e| format!("Build context paketlenemedi [{}]: {}", name, e))?;
+    }
+
+    let dockerfile_name = match relative(dockerfile) {
+        Some(name) if !is_ignored(&name) => name,
+        _ => {
+            archive.append_path_with_name(dockerfile, CONTEXT_DOCKERFILE)
+                .map_err(|e| format!("Dockerfile paketlenemedi [{}]: {}", dockerfile.display(), e))?;
+            CONTEXT_DOCKERFILE.to_string()
+        }
+    };
+    let archive = archive.into_inner().map_err(|e| format!("Build context paketlenemedi: {}", e))?;
+    Ok((archive, dockerfile_name))
+}
+
+/// The image for `config`: built from its Dockerfile, or pulled when not present locally
+async fn prepare_image<R: Runtime>(
+    app: &AppHandle<R>,
+    client: &DockerClient,
+    workspace: &Path,
+    config: &DevContainerConfig,
+    config_file: &Path,
+) -> Result<String, String> {
+    let base = config_file.parent().unwrap_or(workspace);
+    let build = config.build.clone().or_else(|| {
+        config.docker_file.clone().map(|dockerfile| DevContainerBuild {
+            dockerfile: Some(dockerfile),
+            context: config.context.clone(),
+            ..Default::default()
+        })
+    });
+    if let Some(build) = build.filter(|b| b.dockerfile.is_some()) {
+        let tag = format!(
+            "corex-dev-{}-{}",
+            basename(workspace).to_lowercase().replace(|c: char| !c.is_ascii_alphanumeric(), "-"),
+            &hex::encode(Sha256::digest(workspace.to_string_lossy().as_bytes()))[..8]
+        );
+        let dockerfile = base.join(build.dockerfile.clone().unwrap_or_default());
+        let context = base.join(build.context.clone().unwrap_or_else(|| ".".to_string()));
+        info!("🔨 Devcontainer imajı derleniyor: {}", tag);
+        let (archive, dockerfile) = tokio::task::spawn_blocking(move || build_context(&context, &dockerfile))
+            .await
+            .map_err(|e| format!("Build context hazırlanamadı: {}", e))??;
+        client
+            .build_image(&archive, &dockerfile, &tag, &build.args, build.target.as_deref(), |line| {
+                emit_progress(app, workspace, "build", line)
+            })
+            .await?;
+        return Ok(tag);
+    }
+
+    let image = config.image.clone().ok_or("devcontainer.json: `image` gerekli")?;
+    if !client.image_exists(&image).await? {
+        info!("📥 Devcontainer imajı çekiliyor: {}", image);
+        client.pull_image(&image, |line| emit_progress(app, workspace, "pull", line)).await?;
+    }
+    Ok(image)
+}
+
+/// `(id, running, config hash)` of the container an earlier open left for this workspace
+async fn existing_container(client: &DockerClient, workspace: &Path) -> Result<Option<(String, bool, String)>, String> {
+    let filters = json!({ "label": [format!("{}={}", LOCAL_FOLDER_LABEL, workspace.display())] }).to_string();
+    let path = format!(
+        "/containers/json?all=true&filters={}",
+        url::form_urlencoded::byte_serialize(filters.as_bytes()).collect::<String>()
+    );
+    let containers = client.get_json(&path).await?;
+    Ok(containers.as_array().and_then(|c| c.first()).map(|container| (
+        container["Id"].as_str().unwrap_or_default().to_string(),
+        container["State"] == "running",
+        container["Labels"][CONFIG_LABEL].as_str().unwrap_or_default().to_string(),
+    )))
+}
+
+async fn run_lifecycle<R: Runtime>(
+    app: &AppHandle<R>,
+    client: &DockerClient,
+    session: &DevContainerSession,
+    stage: &str,
+    command: Option<&Value>,
+) -> Result<(), String> {
+    let workspace = Path::new(&session.workspace);
+    for (program, args) in command.map(lifecycle_commands).unwrap_or_default() {
+        info!("⚙️ Devcontainer {}: {} {}", stage, program, args.join(" "));
+        let mut errors = Vec::new();
+        let code = session
+            .exec(client, &program, &args, workspace, &[], |stream, line| {
+                emit_progress(app, workspace, stage, line);
+                if stream == "stderr" {
+                    errors.push(line.to_string());
+                }
+            })
+            .await?;
+        if code != 0 {
+            let tail = errors.len().saturating_sub(20);
+            return Err(format!("Devcontainer {} başarısız ({} çıkış kodu {}): {}", stage, program, code, errors[tail..].join("\n")));
+        }
+    }
+    Ok(())
+}
+
+/// Brings up the workspace's dev container (reusing one whose config hasn't changed) and puts
+/// the workspace in container mode
+#[tauri::command]
+pub async fn devcontainer_open<R: Runtime>(app: AppHandle<R>, workspace: String) -> Result<DevContainerSession, String> {
+    let workspace = std::fs::canonicalize(&workspace).map_err(|e| format!("Workspace bulunamadı {}: {}", workspace, e))?;
+    let (config, config_file, hash) = load_config(&workspace)?;
+    let client = DockerClient::from_env();
+
+    let mut created = false;
+    let container_id = match existing_container(&client, &workspace).await? {
+        Some((id, running, existing_hash)) if existing_hash == hash => {
+            if !running {
+                client.container_action(&id, "start").await?;
+            }
+            id
+        }
+        existing => {
+            if let Some((id, ..)) = existing {
+                info!("♻️ devcontainer.json değişti, container yeniden oluşturuluyor: {}", id);
+                client.container_action(&id, "remove").await?;
+            }
+            let image = prepare_image(&app, &client, &workspace, &config, &config_file).await?;
+            let id = client.create_container(&create_config(&workspace, &config, &config_file, &hash, &image)).await?;
+            client.container_action(&id, "start").await?;
+            created = true;
+            id
+        }
+    };
+
+    let container = client.inspect_container(&container_id).await?
+        .ok_or_else(|| format!("Container bulunamadı: {}", container_id))?;
+    let container_env: HashMap<String, String> = container["Config"]["Env"].as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|pair| pair.split_once('=').map(|(k, v)| (k.to_string(), v.to_string())))
+        .collect();
+    let remote_env = config.remote_env.iter()
+        .filter_map(|(key, value)| {
+            // null unsets a variable; leaving it out does the same for exec
+            let value = value.as_ref()?;
+            Some((key.clone(), substitute(value, &HashMap::new(), Some(&container_env))))
+        })
+        .collect();
+
+    let session = DevContainerSession {
+        workspace: workspace.to_string_lossy().to_string(),
+        container_id,
+        image: container["Config"]["Image"].as_str().unwrap_or_default().to_string(),
+        workspace_folder: config.workspace_folder.clone().unwrap_or_default(),
+        remote_user: config.remote_user.clone().or(config.container_user.clone()),
+        remote_env,
+    };
+    if created {
+        run_lifecycle(&app, &client, &session, "postCreate", config.post_create_command.as_ref()).await?;
+    }
+    run_lifecycle(&app, &client, &session, "postStart", config.post_start_command.as_ref()).await?;
+    session.save()?;
+    info!("🐳 Workspace container modunda: {} -> {}", session.workspace, session.container_id);
+    Ok(session)
+}
+
+/// Leaves container mode; with `stop` the container is stopped too
+#[tauri::command]
+pub async fn devcontainer_close(workspace: String, stop: Option<bool>) -> Result<(), String> {
+    let Some(session) = DevContainerSession::saved(Path::new(&workspace)) else {
+        return Ok(());
+    };
+    std::fs::remove_file(DevContainerSession::file(Path::new(&session.workspace)))
+        .map_err(|e| format!("Container oturumu kapatılamadı: {}", e))?;
+    if stop.unwrap_or(false) {
+        if let Err(e) = DockerClient::from_env().container_action(&session.container_id, "stop").await {
+            warn!("⚠️ Container durdurulamadı: {}", e);
+        }
+    }
+    info!("🔌 Workspace container modundan çıktı: {}", session.workspace);
+    Ok(())
+}
+
+/// The container session of the workspace and its devcontainer.json, if any
+#[tauri::command]
+pub async fn devcontainer_status(workspace: String) -> Result<Value, String> {
+    let path = Path::new(&workspace);
+    let config = find_config(path).map(|_| load_config(path).map(|(config, ..)| config));
+    Ok(json!({
+        "session": DevContainerSession::find(path).await,
+        "config": match &config {
+            Some(Ok(config)) => json!(config),
+            _ => Value::Null,
+        },
+        "error": match config {
+            Some(Err(e)) => Some(e),
+            _ => None,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loads_jsonc_config_with_variables() {
+        let workspace = std::env::temp_dir().join(format!("corex_devcontainer_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(workspace.join(".devcontainer")).unwrap();
+        std::fs::write(workspace.join(".devcontainer").join("devcontainer.json"), r#"{
+            // Rust toolchain
+            "name": "api",
+            "build": { "dockerfile": "Dockerfile", "args": { "VARIANT": "1.80", }, },
+            /* mounted at the default location */
+            "mounts": ["source=${localWorkspaceFolderBasename}-cache,target=/cache,type=volume"],
+            "remoteEnv": { "PATH": "${containerEnv:PATH}:/cache/bin", "UNSET": null },
+            "postCreateCommand": { "deps": "cargo fetch", "tools": ["rustup", "component", "add", "clippy"] },
+            "url": "http://example.com//not-a-comment",
+        }"#).unwrap();
+        let workspace = std::fs::canonicalize(&workspace).unwrap();
+        let name = basename(&workspace);
+
+        let (config, file, hash) = load_config(&workspace).unwrap();
+        assert!(file.ends_with(".devcontainer/devcontainer.json"));
+        assert_eq!(hash.len(), 16);
+        assert_eq!(config.name.as_deref(), Some("api"));
+        assert_eq!(config.build.as_ref().unwrap().args["VARIANT"], "1.80");
+        assert_eq!(config.workspace_folder, Some(format!("/workspaces/{}", name)));
+        assert_eq!(config.mounts[0], json!(format!("source={}-cache,target=/cache,type=volume", name)));
+        // containerEnv is only known once the container exists
+        assert_eq!(config.remote_env["PATH"].as_deref(), Some("${containerEnv:PATH}:/cache/bin"));
+        assert_eq!(config.remote_env["UNSET"], None);
+        assert_eq!(lifecycle_commands(config.post_create_command.as_ref().unwrap()), vec![
+            ("sh".to_string(), vec!["-c".to_string(), "cargo fetch".to_string()]),
+            ("rustup".to_string(), vec!["component".to_string(), "add".to_string(), "clippy".to_string()]),
+        ]);
+
+        let env = HashMap::from([("PATH".to_string(), "/usr/bin".to_string())]);
+        assert_eq!(substitute(config.remote_env["PATH"].as_deref().unwrap(), &HashMap::new(), Some(&env)), "/usr/bin:/cache/bin");
+
+        let config = DevContainerConfig {
+            forward_ports: vec![json!(3000), json!("db:5432")],
+            run_args: vec!["--cap-add=SYS_PTRACE".to_string(), "--security-opt".to_string(), "seccomp=unconfined".to_string()],
+            ..config
+        };
+        let create = create_config(&workspace, &config, &file, &hash, "corex-dev-api");
+        assert_eq!(create["Image"], "corex-dev-api");
+        assert_eq!(create["Labels"][CONFIG_LABEL], hash);
+        assert_eq!(create["HostConfig"]["Mounts"], json!([
+            { "Type": "bind", "Source": workspace.display().to_string(), "Target": format!("/workspaces/{}", name) },
+            { "Type": "volume", "Source": format!("{}-cache", name), "Target": "/cache" },
+        ]));
+        assert_eq!(create["HostConfig"]["PortBindings"], json!({ "3000/tcp": [{ "HostIp": "127.0.0.1", "HostPort": "3000" }] }));
+        assert_eq!(create["HostConfig"]["CapAdd"], json!(["SYS_PTRACE"]));
+        assert_eq!(create["HostConfig"]["SecurityOpt"], json!(["seccomp=unconfined"]));
+        assert_eq!(create["Entrypoint"], json!(["/bin/sh"]));
+        let _ = std::fs::remove_dir_all(workspace);
+    }
+
+    #[test]
+    fn test_commands_run_in_the_container_path() {
+        let workspace = std::env::temp_dir().join(format!("corex_devcontainer_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(workspace.join("web").join("src")).unwrap();
+        let workspace = std::fs::canonicalize(&workspace).unwrap();
+        let session = DevContainerSession {
+            workspace: workspace.to_string_lossy().to_string(),
+            container_id: "4f3c2b1a0e9d".to_string(),
+            image: "node:20".to_string(),
+            workspace_folder: "/workspaces/shop".to_string(),
+            remote_user: Some("node".to_string()),
+            remote_env: vec![("CI".to_string(), "1".to_string())],
+        };
+        assert_eq!(DevContainerSession::saved(&workspace.join("web")), None);
+        session.save().unwrap();
+        assert_eq!(DevContainerSession::saved(&workspace.join("web").join("src")), Some(session.clone()));
+
+        let file = workspace.join("web").join("src").join("cart.test.ts");
+        let report = workspace.join(".corex").join("report.json");
+        let config = session.exec_config(
+            "npx",
+            &["jest".to_string(), format!("--outputFile={}", report.display()), file.to_string_lossy().to_string()],
+            &workspace.join("web"),
+            &[("NODE_ENV".to_string(), "test".to_string())],
+        );
+        assert_eq!(config, json!({
+            "Cmd": ["npx", "jest", "--outputFile=/workspaces/shop/.corex/report.json", "/workspaces/shop/web/src/cart.test.ts"],
+            "WorkingDir": "/workspaces/shop/web",
+            "Env": ["CI=1", "NODE_ENV=test"],
+            "User": "node",
+        }));
+        assert_eq!(
+            session.host_paths("SF:/workspaces/shop/web/src/cart.ts\nSF:/workspaces/shopping/x.ts"),
+            format!("SF:{}/web/src/cart.ts\nSF:/workspaces/shopping/x.ts", workspace.display())
+        );
+        let _ = std::fs::remove_dir_all(workspace);
+    }
+
+    #[test]
+    fn test_build_context_honours_dockerignore() {
+        let workspace = std::env::temp_dir().join(format!("corex_devcontainer_{}", uuid::Uuid::new_v4()));
+        for dir in ["src", "target/debug", "web/node_modules/x", ".devcontainer"] {
+            std::fs::create_dir_all(workspace.join(dir)).unwrap();
+        }
+        for file in ["src/main.rs", "target/debug/app", "web/node_modules/x/index.js", "web/app.log", ".devcontainer/Dockerfile"] {
+            std::fs::write(workspace.join(file), "x").unwrap();
+        }
+        std::fs::write(workspace.join(".dockerignore"), "# build output\n/target/\n**/node_modules\n*/*.log\n").unwrap();
+
+        let names = |archive: &[u8]| -> Vec<String> {
+            let mut names: Vec<String> = tar::Archive::new(archive).entries().unwrap()
+                .map(|entry| entry.unwrap().path().unwrap().to_string_lossy().trim_end_matches('/').to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let (archive, dockerfile) = build_context(&workspace, &workspace.join(".devcontainer/Dockerfile")).unwrap();
+        assert_eq!(dockerfile, ".devcontainer/Dockerfile");
+        assert_eq!(names(&archive), vec![".devcontainer", ".devcontainer/Dockerfile", ".dockerignore", "src", "src/main.rs", "web"]);
+
+        // The context is `src`, the Dockerfile lives outside it
+        let (archive, dockerfile) = build_context(&workspace.join("src"), &workspace.join(".devcontainer/Dockerfile")).unwrap();
+        assert_eq!(dockerfile, CONTEXT_DOCKERFILE);
+        assert_eq!(names(&archive), vec![CONTEXT_DOCKERFILE, "main.rs"]);
+        let _ = std::fs::remove_dir_all(workspace);
+    }
+}
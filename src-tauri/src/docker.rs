@@ -1,9 +1,20 @@
-// Docker integration disabled due to dependency issues
-// Re-enable when bollard crate issues are resolved
+// Docker Engine API client.
+// Talks HTTP/1.1 to the local engine socket (/var/run/docker.sock, or the docker_engine
+// named pipe on Windows) directly, so no Docker SDK crate is needed.
 
+use log::{error, info, warn};
 use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Runtime, State};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct ContainerInfo {
     pub id: String,
     pub name: String,
@@ -12,7 +23,7 @@ pub struct ContainerInfo {
     pub state: String,
 }
 
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default, Debug)]
 pub struct ImageInfo {
     pub id: String,
     pub repository: String,
@@ -21,46 +32,1132 @@ pub struct ImageInfo {
     pub created: i64,
 }
 
+#[cfg(unix)]
+const DEFAULT_ENDPOINT: &str = "/var/run/docker.sock";
+#[cfg(windows)]
+const DEFAULT_ENDPOINT: &str = r"\\.\pipe\docker_engine";
+
+/// Size of the reads used for bodies without chunked encoding
+const READ_BUFFER: usize = 8 * 1024;
+
+/// Anything the Engine API can be spoken over
+trait EngineStream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> EngineStream for T {}
+
+/// Status line and headers of an Engine API response
+#[derive(Debug)]
+struct ResponseHead {
+    status: u16,
+    /// Header names are lowercased
+    headers: HashMap<String, String>,
+}
+
+impl ResponseHead {
+    fn is_chunked(&self) -> bool {
+        self.headers.get("transfer-encoding")
+            .map(|v| v.to_ascii_lowercase().contains("chunked"))
+            .unwrap_or(false)
+    }
+
+    fn content_length(&self) -> Option<usize> {
+        self.headers.get("content-length").and_then(|v| v.trim().parse().ok())
+    }
+}
+
+/// A response whose body has not been read yet
+struct EngineResponse {
+    head: ResponseHead,
+    reader: BufReader<Box<dyn EngineStream>>,
+    /// Bytes left of a `Content-Length` body
+    remaining: Option<usize>,
+    done: bool,
+}
+
+impl EngineResponse {
+    /// Next piece of the body as it arrives, `None` at the end
+    async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, String> {
+        if self.done {
+            return Ok(None);
+        }
+        let chunk = if self.head.is_chunked() {
+            read_chunk(&mut self.reader).await?
+        } else {
+            let limit = self.remaining.map(|r| r.min(READ_BUFFER)).unwrap_or(READ_BUFFER);
+            let mut buffer = vec![0; limit];
+            let read = match limit {
+                0 => 0,
+                _ => self.reader.read(&mut buffer).await.map_err(|e| format!("Docker yanıtı okunamadı: {}", e))?,
+            };
+            buffer.truncate(read);
+            if let Some(remaining) = self.remaining.as_mut() {
+                *remaining -= read;
+            }
+            (read > 0).then_some(buffer)
+        };
+        self.done = chunk.is_none();
+        Ok(chunk)
+    }
+
+    async fn body(mut self) -> Result<Vec<u8>, String> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.next_chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
+async fn read_head<S: AsyncRead + Unpin>(reader: &mut BufReader<S>) -> Result<ResponseHead, String> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await.map_err(|e| format!("Docker yanıtı okunamadı: {}", e))?;
+    // HTTP/1.1 200 OK
+    let status = line.split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("Geçersiz Docker yanıtı: {:?}", line.trim()))?;
+
+    let mut headers = HashMap::new();
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).await.map_err(|e| format!("Docker yanıtı okunamadı: {}", e))?;
+        let header = line.trim_end();
+        if read == 0 || header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+    Ok(ResponseHead { status, headers })
+}
+
+/// One chunk of a chunked body; `None` after the terminating zero-size chunk
+async fn read_chunk<S: AsyncRead + Unpin>(reader: &mut BufReader<S>) -> Result<Option<Vec<u8>>, String> {
+    let mut line = String::new();
+    let read = reader.read_line(&mut line).await.map_err(|e| format!("Docker yanıtı okunamadı: {}", e))?;
+    if read == 0 {
+        return Ok(None);
+    }
+    let size = line.trim().split(';').next().unwrap_or("");
+    let size = usize::from_str_radix(size, 16)
+        .map_err(|_| format!("Geçersiz chunk boyutu: {:?}", line.trim()))?;
+    if size == 0 {
+        return Ok(None);
+    }
+    let mut chunk = vec![0; size + 2];
+    reader.read_exact(&mut chunk).await.map_err(|e| format!("Docker yanıtı okunamadı: {}", e))?;
+    chunk.truncate(size);
+    Ok(Some(chunk))
+}
+
+/// Message of an Engine API error body (`{"message": "..."}`)
+fn engine_error(status: u16, body: &[u8]) -> String {
+    let message = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|v| v["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| String::from_utf8_lossy(body).trim().to_string());
+    format!("Docker API hatası ({}): {}", status, message)
+}
+
+/// Container ids, names and image references go into the URL path as they are,
+/// so anything that could change the request is refused
+fn path_segment(id: &str) -> Result<&str, String> {
+    let valid = !id.is_empty()
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || "_.-:/@".contains(c))
+        && !id.split('/').any(|part| part == "..");
+    if valid {
+        Ok(id)
+    } else {
+        Err(format!("Geçersiz Docker id: {}", id))
+    }
+}
+
+fn short_id(id: &str) -> String {
+    id.trim_start_matches("sha256:").chars().take(12).collect()
+}
+
+fn container_info(container: &Value) -> ContainerInfo {
+    let text = |key: &str| container[key].as_str().unwrap_or("").to_string();
+    ContainerInfo {
+        id: short_id(container["Id"].as_str().unwrap_or("")),
+        name: container["Names"][0].as_str().unwrap_or("").trim_start_matches('/').to_string(),
+        image: text("Image"),
+        status: text("Status"),
+        state: text("State"),
+    }
+}
+
+/// One entry per tag, like `docker images`; untagged images show as `<none>`
+fn image_infos(image: &Value) -> Vec<ImageInfo> {
+    let base = ImageInfo {
+        id: short_id(image["Id"].as_str().unwrap_or("")),
+        repository: "<none>".to_string(),
+        tag: "<none>".to_string(),
+        size: image["Size"].as_i64().unwrap_or(0),
+        created: image["Created"].as_i64().unwrap_or(0),
+    };
+    let tags: Vec<&str> = image["RepoTags"].as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter(|t| *t != "<none>:<none>")
+        .collect();
+    if tags.is_empty() {
+        return vec![base];
+    }
+    tags.into_iter()
+        .map(|reference| {
+            // The registry part may hold a port too: localhost:5000/app:1.0
+            let (repository, tag) = match reference.rsplit_once(':') {
+                Some((repository, tag)) if !tag.contains('/') => (repository, tag),
+                _ => (reference, "latest"),
+            };
+            ImageInfo {
+                id: base.id.clone(),
+                repository: repository.to_string(),
+                tag: tag.to_string(),
+                ..base
+            }
+        })
+        .collect()
+}
+
+/// Docker Engine API over the local socket
+#[derive(Debug, Clone)]
+pub struct DockerClient {
+    endpoint: PathBuf,
+}
+
+impl DockerClient {
+    pub fn new(endpoint: impl Into<PathBuf>) -> Self {
+        Self { endpoint: endpoint.into() }
+    }
+
+    /// `DOCKER_HOST` when it names a unix socket or named pipe, the platform default otherwise
+    pub fn from_env() -> Self {
+        let endpoint = std::env::var("DOCKER_HOST").ok().and_then(|host| {
+            if let Some(path) = host.strip_prefix("unix://") {
+                Some(path.to_string())
+            } else {
+                host.strip_prefix("npipe://").map(|path| path.replace('/', "\\"))
+            }
+        });
+        Self::new(endpoint.unwrap_or_else(|| DEFAULT_ENDPOINT.to_string()))
+    }
+
+    async fn connect(&self) -> Result<Box<dyn EngineStream>, String> {
+        let unreachable = |e: std::io::Error| {
+            format!("Docker'a bağlanılamadı ({}): {}. Docker çalışıyor mu?", self.endpoint.display(), e)
+        };
+        #[cfg(unix)]
+        let stream = tokio::net::UnixStream::connect(&self.endpoint).await.map_err(unreachable)?;
+        #[cfg(windows)]
+        let stream = tokio::net::windows::named_pipe::ClientOptions::new()
+            .open(&self.endpoint)
+            .map_err(unreachable)?;
+        Ok(Box::new(stream))
+    }
+
+    /// Send a request and read the response head; the body is left on the stream.
+    /// `upgrade` asks the engine to hand the connection over, as exec attach does.
+    async fn send(&self, method: &str, path: &str, body: Option<&Value>, upgrade: bool) -> Result<EngineResponse, String> {
+        let body = body.map(Value::to_string).unwrap_or_default();
+        self.send_body(method, path, "application/json", body.as_bytes(), upgrade).await
+    }
+
+    /// `send` with a body of any type, such as the tar archive of a build context
+    async fn send_body(&self, method: &str, path: &str, content_type: &str, body: &[u8], upgrade: bool) -> Result<EngineResponse, String> {
+        let mut stream = self.connect().await?;
+        let connection = if upgrade { "Upgrade\r\nUpgrade: tcp" } else { "close" };
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: docker\r\nUser-Agent: corex\r\nConnection: {}\r\nContent-Length: {}\r\n",
+            method,
+            path,
+            connection,
+            body.len()
+        );
+        if !body.is_empty() {
+            request.push_str(&format!("Content-Type: {}\r\n", content_type));
+        }
+        request.push_str("\r\n");
+        let mut request = request.into_bytes();
+        request.extend_from_slice(body);
+        stream.write_all(&request).await.map_err(|e| format!("Docker isteği gönderilemedi: {}", e))?;
+        stream.flush().await.map_err(|e| format!("Docker isteği gönderilemedi: {}", e))?;
+
+        let mut reader = BufReader::new(stream);
+        let head = read_head(&mut reader).await?;
+        let remaining = if head.is_chunked() { None } else { head.content_length() };
+        Ok(EngineResponse { head, reader, remaining, done: false })
+    }
+
+    /// Whole response body; error statuses become the engine's message. 304 (already in
+    /// that state) counts as success.
+    async fn request(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Vec<u8>, String> {
+        let response = self.send(method, path, body, false).await?;
+        let status = response.head.status;
+        let body = response.body().await?;
+        if !(200..300).contains(&status) && status != 304 {
+            return Err(engine_error(status, &body));
+        }
+        Ok(body)
+    }
+
+    async fn request_json(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value, String> {
+        let body = self.request(method, path, body).await?;
+        serde_json::from_slice(&body).map_err(|e| format!("Docker yanıtı çözümlenemedi: {}", e))
+    }
+
+    pub(crate) async fn get_json(&self, path: &str) -> Result<Value, String> {
+        self.request_json("GET", path, None).await
+    }
+
+    /// A response whose body is read as it streams in, or the engine's error
+    async fn stream(&self, path: &str) -> Result<EngineResponse, String> {
+        let response = self.send("GET", path, None, false).await?;
+        let status = response.head.status;
+        if !(200..300).contains(&status) {
+            return Err(engine_error(status, &response.body().await?));
+        }
+        Ok(response)
+    }
+
+    pub async fn list_containers(&self) -> Result<Vec<ContainerInfo>, String> {
+        let containers = self.get_json("/containers/json?all=true").await?;
+        Ok(containers.as_array().into_iter().flatten().map(container_info).collect())
+    }
+
+    pub async fn list_images(&self) -> Result<Vec<ImageInfo>, String> {
+        let images = self.get_json("/images/json").await?;
+        Ok(images.as_array().into_iter().flatten().flat_map(image_infos).collect())
+    }
+
+    /// start, stop, restart, pause, unpause, kill or remove
+    pub async fn container_action(&self, id: &str, action: &str) -> Result<(), String> {
+        let id = path_segment(id)?;
+        match action {
+            "start" | "stop" | "restart" | "pause" | "unpause" | "kill" => {
+                self.request("POST", &format!("/containers/{}/{}", id, action), None).await?;
+            }
+            "remove" => {
+                self.request("DELETE", &format!("/containers/{}?force=true", id), None).await?;
+            }
+            _ => return Err(format!("Geçersiz container işlemi: {}", action)),
+        }
+        Ok(())
+    }
+
+    pub async fn remove_image(&self, id: &str) -> Result<(), String> {
+        self.request("DELETE", &format!("/images/{}", path_segment(id)?), None).await?;
+        Ok(())
+    }
+
+    /// `GET` a JSON document that may not exist; 404 is `None`
+    async fn get_optional(&self, path: &str) -> Result<Option<Value>, String> {
+        let response = self.send("GET", path, None, false).await?;
+        let status = response.head.status;
+        let body = response.body().await?;
+        match status {
+            404 => Ok(None),
+            200..=299 => serde_json::from_slice(&body)
+                .map(Some)
+                .map_err(|e| format!("Docker yanıtı çözümlenemedi: {}", e)),
+            _ => Err(engine_error(status, &body)),
+        }
+    }
+
+    /// Full `docker inspect` document of a container, `None` when it does not exist
+    pub async fn inspect_container(&self, id: &str) -> Result<Option<Value>, String> {
+        self.get_optional(&format!("/containers/{}/json", path_segment(id)?)).await
+    }
+
+    pub async fn image_exists(&self, image: &str) -> Result<bool, String> {
+        Ok(self.get_optional(&format!("/images/{}/json", path_segment(image)?)).await?.is_some())
+    }
+
+    /// Create a container from an Engine API config without starting it; returns its id
+    pub async fn create_container(&self, config: &Value) -> Result<String, String> {
+        let created = self.request_json("POST", "/containers/create", Some(config)).await?;
+        created["Id"].as_str()
+            .map(str::to_string)
+            .ok_or_else(|| "Docker container id döndürmedi".to_string())
+    }
+
+    /// Pull an image, `latest` when the reference has no tag; progress lines go to `on_line`
+    pub async fn pull_image(&self, image: &str, on_line: impl FnMut(&str)) -> Result<(), String> {
+        let reference = path_segment(image)?;
+        let (name, tag) = match reference.rsplit_once(':') {
+            _ if reference.contains('@') => (reference, None),
+            Some((name, tag)) if !tag.contains('/') => (name, Some(tag)),
+            _ => (reference, Some("latest")),
+        };
+        let mut path = format!("/images/create?fromImage={}", query_value(name));
+        if let Some(tag) = tag {
+            path.push_str(&format!("&tag={}", query_value(tag)));
+        }
+        let response = self.send("POST", &path, None, false).await?;
+        json_progress(response, on_line).await
+    }
+
+    /// Build `tag` from a tar archive of the build context; `dockerfile` is its path inside
+    /// the archive. Build output lines go to `on_line`.
+    pub async fn build_image(
+        &self,
+        context: &[u8],
+        dockerfile: &str,
+        tag: &str,
+        build_args: &BTreeMap<String, String>,
+        target: Option<&str>,
+        on_line: impl FnMut(&str),
+    ) -> Result<(), String> {
+        let mut path = format!("/build?dockerfile={}&t={}&rm=true", query_value(dockerfile), query_value(tag));
+        if !build_args.is_empty() {
+            path.push_str(&format!("&buildargs={}", query_value(&json!(build_args).to_string())));
+        }
+        if let Some(target) = target {
+            path.push_str(&format!("&target={}", query_value(target)));
+        }
+        let response = self.send_body("POST", &path, "application/x-tar", context, false).await?;
+        json_progress(response, on_line).await
+    }
+
+    /// Run a command in a container without a TTY and wait for it to exit. `config` holds
+    /// `Cmd` and optionally `WorkingDir`, `User` and `Env`; output arrives line by line as
+    /// `(stream, line)`. Returns the exit code.
+    pub async fn exec(&self, id: &str, mut config: Value, mut on_line: impl FnMut(&str, &str)) -> Result<i64, String> {
+        config["AttachStdout"] = json!(true);
+        config["AttachStderr"] = json!(true);
+        config["Tty"] = json!(false);
+        let created = self.request_json("POST", &format!("/containers/{}/exec", path_segment(id)?), Some(&config)).await?;
+        let exec_id = created["Id"].as_str().ok_or("Docker exec id döndürmedi")?.to_string();
+
+        let mut response = self
+            .send("POST", &format!("/exec/{}/start", exec_id), Some(&json!({ "Detach": false, "Tty": false })), false)
+            .await?;
+        let status = response.head.status;
+        if !(200..300).contains(&status) {
+            return Err(engine_error(status, &response.body().await?));
+        }
+
+        // The connection stays open until the process exits
+        let mut demuxer = LogDemuxer::default();
+        let mut pending: HashMap<&'static str, Vec<u8>> = HashMap::new();
+        while let Some(chunk) = response.next_chunk().await? {
+            for (stream, data) in demuxer.push(&chunk) {
+                let buffer = pending.entry(stream).or_default();
+                buffer.extend_from_slice(&data);
+                while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=end).collect();
+                    on_line(stream, String::from_utf8_lossy(&line).trim_end());
+                }
+            }
+        }
+        for (stream, rest) in pending.into_iter().filter(|(_, rest)| !rest.is_empty()) {
+            on_line(stream, String::from_utf8_lossy(&rest).trim_end());
+        }
+
+        let inspect = self.get_json(&format!("/exec/{}/json", exec_id)).await?;
+        inspect["ExitCode"].as_i64().ok_or_else(|| format!("Exec çıkış kodu alınamadı: {}", exec_id))
+    }
+}
+
+fn query_value(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Reads the JSON-lines progress of a build or pull. The engine answers 200 up front and
+/// reports failures inside the stream, so an `error` message fails the call.
+async fn json_progress(mut response: EngineResponse, mut on_line: impl FnMut(&str)) -> Result<(), String> {
+    let status = response.head.status;
+    if !(200..300).contains(&status) {
+        return Err(engine_error(status, &response.body().await?));
+    }
+
+    let mut handle = |line: &[u8]| -> Result<(), String> {
+        let Ok(message) = serde_json::from_slice::<Value>(line) else {
+            return Ok(());
+        };
+        if let Some(error) = message["error"].as_str() {
+            return Err(format!("Docker işlemi başarısız: {}", error.trim()));
+        }
+        for text in progress_text(&message).iter().flat_map(|t| t.lines()) {
+            if !text.trim().is_empty() {
+                on_line(text);
+            }
+        }
+        Ok(())
+    };
+
+    let mut pending = Vec::new();
+    while let Some(chunk) = response.next_chunk().await? {
+        pending.extend_from_slice(&chunk);
+        while let Some(end) = pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            handle(&line)?;
+        }
+    }
+    handle(&pending)
+}
+
+/// Build output, or `layer: status` for pulls; download ticks (which carry a progress
+/// bar) are skipped so they don't flood the UI
+fn progress_text(message: &Value) -> Option<String> {
+    if let Some(stream) = message["stream"].as_str() {
+        return Some(stream.to_string());
+    }
+    if message.get("progress").is_some() {
+        return None;
+    }
+    let status = message["status"].as_str()?;
+    Some(match message["id"].as_str() {
+        Some(id) => format!("{}: {}", id, status),
+        None => status.to_string(),
+    })
+}
+
+/// Splits the engine's multiplexed log stream (8-byte header: stream, 0, 0, 0, big-endian
+/// size) into `(stream, payload)` frames; containers with a TTY send raw output instead
+#[derive(Debug, Default)]
+struct LogDemuxer {
+    buffer: Vec<u8>,
+}
+
+impl LogDemuxer {
+    fn push(&mut self, data: &[u8]) -> Vec<(&'static str, Vec<u8>)> {
+        self.buffer.extend_from_slice(data);
+        let mut frames = Vec::new();
+        while self.buffer.len() >= 8 {
+            let size = u32::from_be_bytes([self.buffer[4], self.buffer[5], self.buffer[6], self.buffer[7]]) as usize;
+            if self.buffer.len() < 8 + size {
+                break;
+            }
+            let stream = match self.buffer[0] {
+                0 => "stdin",
+                2 => "stderr",
+                _ => "stdout",
+            };
+            frames.push((stream, self.buffer[8..8 + size].to_vec()));
+            self.buffer.drain(..8 + size);
+        }
+        frames
+    }
+}
+
+/// The longest valid UTF-8 prefix of `pending`; a character cut between reads waits for the rest
+fn take_utf8(pending: &mut Vec<u8>) -> String {
+    let valid = match std::str::from_utf8(pending) {
+        Ok(text) => text.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        // Invalid bytes, not a cut: let them through as replacement characters
+        Err(_) => pending.len(),
+    };
+    let text = String::from_utf8_lossy(&pending[..valid]).to_string();
+    pending.drain(..valid);
+    text
+}
+
+/// One sample of `GET /containers/{id}/stats`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ContainerStats {
+    pub cpu_percent: f64,
+    /// Bytes, without the page cache
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    pub memory_percent: f64,
+    pub network_rx: u64,
+    pub network_tx: u64,
+}
+
+/// CPU and memory the way `docker stats` computes them
+fn container_stats(sample: &Value) -> ContainerStats {
+    let number = |value: &Value| value.as_u64().unwrap_or(0);
+    let cpu = &sample["cpu_stats"];
+    let previous = &sample["precpu_stats"];
+    let cpu_delta = number(&cpu["cpu_usage"]["total_usage"]).saturating_sub(number(&previous["cpu_usage"]["total_usage"]));
+    let system_delta = number(&cpu["system_cpu_usage"]).saturating_sub(number(&previous["system_cpu_usage"]));
+    let cpus = cpu["online_cpus"].as_u64()
+        .or_else(|| cpu["cpu_usage"]["percpu_usage"].as_array().map(|a| a.len() as u64))
+        .filter(|n| *n > 0)
+        .unwrap_or(1);
+    let cpu_percent = match system_delta {
+        0 => 0.0,
+        _ => cpu_delta as f64 / system_delta as f64 * cpus as f64 * 100.0,
+    };
+
+    let memory = &sample["memory_stats"];
+    // cgroup v2 reports inactive_file, v1 reports cache
+    let cache = memory["stats"]["inactive_file"].as_u64().or_else(|| memory["stats"]["cache"].as_u64()).unwrap_or(0);
+    let memory_usage = number(&memory["usage"]).saturating_sub(cache);
+    let memory_limit = number(&memory["limit"]);
+    let memory_percent = match memory_limit {
+        0 => 0.0,
+        limit => memory_usage as f64 / limit as f64 * 100.0,
+    };
+
+    let networks = sample["networks"].as_object();
+    let network_total = |key: &str| networks.into_iter().flat_map(|n| n.values()).map(|n| number(&n[key])).sum();
+    ContainerStats {
+        cpu_percent: (cpu_percent * 100.0).round() / 100.0,
+        memory_usage,
+        memory_limit,
+        memory_percent: (memory_percent * 100.0).round() / 100.0,
+        network_rx: network_total("rx_bytes"),
+        network_tx: network_total("tx_bytes"),
+    }
+}
+
+/// A running log, exec or stats stream
+struct ActiveStream {
+    task: JoinHandle<()>,
+    /// Keystrokes for an exec session
+    input: Option<mpsc::UnboundedSender<Vec<u8>>>,
+    exec_id: Option<String>,
+}
+
+/// Open streams by id, so the frontend can feed and cancel them
+#[derive(Default)]
+pub struct DockerState {
+    streams: Arc<Mutex<HashMap<String, ActiveStream>>>,
+}
+
+impl DockerState {
+    /// Register the stream built by `start` (which spawns its task) under one lock, so a task
+    /// that ends right away still finds its entry to remove
+    fn start(&self, stream_id: &str, start: impl FnOnce() -> ActiveStream) -> Result<(), String> {
+        let mut streams = self.streams.lock().map_err(|e| e.to_string())?;
+        streams.insert(stream_id.to_string(), start());
+        Ok(())
+    }
+
+    fn with_stream<T>(&self, stream_id: &str, f: impl FnOnce(&ActiveStream) -> T) -> Result<T, String> {
+        let streams = self.streams.lock().map_err(|e| e.to_string())?;
+        let stream = streams.get(stream_id).ok_or_else(|| format!("Stream bulunamadı: {}", stream_id))?;
+        Ok(f(stream))
+    }
+}
+
+/// Runs `work` as stream `stream_id`, then reports how it ended with `docker-stream-ended`
+/// and forgets the stream
+fn spawn_stream<R, F>(
+    app: AppHandle<R>,
+    streams: Arc<Mutex<HashMap<String, ActiveStream>>>,
+    stream_id: String,
+    kind: &'static str,
+    container: String,
+    work: F,
+) -> JoinHandle<()>
+where
+    R: Runtime,
+    F: Future<Output = Result<Option<i64>, String>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let result = work.await;
+        if let Ok(mut streams) = streams.lock() {
+            streams.remove(&stream_id);
+        }
+        let (exit_code, error) = match result {
+            Ok(code) => (code, None),
+            Err(e) => {
+                warn!("⚠️ Docker {} akışı hata ile bitti [{}]: {}", kind, container, e);
+                (None, Some(e))
+            }
+        };
+        if let Err(e) = app.emit("docker-stream-ended", json!({
+            "stream_id": stream_id,
+            "kind": kind,
+            "container": container,
+            "exit_code": exit_code,
+            "error": error
+        })) {
+            error!("❌ Event emit hatası: {}", e);
+        }
+    })
+}
+
 #[tauri::command]
 pub async fn docker_list_containers() -> Result<Vec<ContainerInfo>, String> {
-    Err("Docker integration temporarily disabled".to_string())
+    DockerClient::from_env().list_containers().await
 }
 
 #[tauri::command]
 pub async fn docker_list_images() -> Result<Vec<ImageInfo>, String> {
-    Err("Docker integration temporarily disabled".to_string())
+    DockerClient::from_env().list_images().await
+}
+
+#[tauri::command]
+pub async fn docker_container_action(id: String, action: String) -> Result<(), String> {
+    info!("🐳 Container {}: {}", action, id);
+    DockerClient::from_env().container_action(&id, &action).await
 }
 
 #[tauri::command]
-pub async fn docker_container_action(_id: String, _action: String) -> Result<(), String> {
-    Err("Docker integration temporarily disabled".to_string())
+pub async fn docker_remove_image(id: String) -> Result<(), String> {
+    info!("🐳 Image siliniyor: {}", id);
+    DockerClient::from_env().remove_image(&id).await
 }
 
+/// Follow a container's logs; lines arrive as `docker-log` events until the container
+/// stops or `docker_stop_stream` is called. Returns the stream id.
 #[tauri::command]
-pub async fn docker_remove_image(_id: String) -> Result<(), String> {
-    Err("Docker integration temporarily disabled".to_string())
+pub async fn docker_follow_logs<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DockerState>,
+    id: String,
+    tail: Option<u32>,
+) -> Result<String, String> {
+    follow_logs(app, &state, &id, tail).await
+}
+
+pub(crate) async fn follow_logs<R: Runtime>(
+    app: AppHandle<R>,
+    state: &DockerState,
+    id: &str,
+    tail: Option<u32>,
+) -> Result<String, String> {
+    let client = DockerClient::from_env();
+    let container = path_segment(id)?.to_string();
+    let tty = client.get_json(&format!("/containers/{}/json", container)).await?["Config"]["Tty"]
+        .as_bool()
+        .unwrap_or(false);
+    let path = format!(
+        "/containers/{}/logs?follow=true&stdout=true&stderr=true&tail={}",
+        container,
+        tail.unwrap_or(200)
+    );
+    let mut response = client.stream(&path).await?;
+
+    let stream_id = uuid::Uuid::new_v4().to_string();
+    let emitter = app.clone();
+    let (log_stream, log_container) = (stream_id.clone(), container.clone());
+    let work = async move {
+        let mut demuxer = LogDemuxer::default();
+        let mut pending = Vec::new();
+        while let Some(chunk) = response.next_chunk().await? {
+            let frames = if tty {
+                pending.extend_from_slice(&chunk);
+                vec![("stdout", take_utf8(&mut pending).into_bytes())]
+            } else {
+                demuxer.push(&chunk)
+            };
+            for (stream, data) in frames.into_iter().filter(|(_, data)| !data.is_empty()) {
+                if let Err(e) = emitter.emit("docker-log", json!({
+                    "stream_id": log_stream,
+                    "container": log_container,
+                    "stream": stream,
+                    "text": String::from_utf8_lossy(&data)
+                })) {
+                    error!("❌ Event emit hatası: {}", e);
+                }
+            }
+        }
+        Ok(None)
+    };
+
+    info!("📜 Container logları izleniyor: {}", container);
+    state.start(&stream_id, || ActiveStream {
+        task: spawn_stream(app, state.streams.clone(), stream_id.clone(), "logs", container, work),
+        input: None,
+        exec_id: None,
+    })?;
+    Ok(stream_id)
 }
 
+/// Start `command` inside a container with a TTY. Output arrives as `docker-exec-output`,
+/// input goes through `docker_exec_input`. Returns the stream id.
 #[tauri::command]
-pub async fn docker_compose_action(path: String, action: String) -> Result<String, String> {
-    use std::process::Command;
-    
-    let args = match action.as_str() {
-        "up" => vec!["compose", "up", "-d"],
-        "down" => vec!["compose", "down"],
-        "restart" => vec!["compose", "restart"],
-        _ => return Err("Invalid compose action".to_string()),
+pub async fn docker_exec_start<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DockerState>,
+    id: String,
+    command: Vec<String>,
+    working_dir: Option<String>,
+) -> Result<String, String> {
+    if command.is_empty() {
+        return Err("Çalıştırılacak komut boş".to_string());
+    }
+    let client = DockerClient::from_env();
+    let container = path_segment(&id)?.to_string();
+    let mut config = json!({
+        "AttachStdin": true,
+        "AttachStdout": true,
+        "AttachStderr": true,
+        "Tty": true,
+        "Cmd": command
+    });
+    if let Some(dir) = working_dir {
+        config["WorkingDir"] = json!(dir);
+    }
+    let created = client.request_json("POST", &format!("/containers/{}/exec", container), Some(&config)).await?;
+    let exec_id = created["Id"].as_str().ok_or("Docker exec id döndürmedi")?.to_string();
+
+    let response = client
+        .send("POST", &format!("/exec/{}/start", exec_id), Some(&json!({ "Detach": false, "Tty": true })), true)
+        .await?;
+    if response.head.status != 101 && response.head.status != 200 {
+        let status = response.head.status;
+        return Err(engine_error(status, &response.body().await?));
+    }
+
+    let stream_id = uuid::Uuid::new_v4().to_string();
+    let (input, mut inputs) = mpsc::unbounded_channel::<Vec<u8>>();
+    let (mut reader, mut writer) = tokio::io::split(response.reader);
+    let emitter = app.clone();
+    let exec_stream = stream_id.clone();
+    let exec = exec_id.clone();
+    let work = async move {
+        let forward_input = async {
+            while let Some(data) = inputs.recv().await {
+                if writer.write_all(&data).await.is_err() || writer.flush().await.is_err() {
+                    break;
+                }
+            }
+        };
+        let forward_output = async {
+            let mut buffer = vec![0; READ_BUFFER];
+            let mut pending = Vec::new();
+            while let Ok(read) = reader.read(&mut buffer).await {
+                if read == 0 {
+                    break;
+                }
+                pending.extend_from_slice(&buffer[..read]);
+                let data = take_utf8(&mut pending);
+                if data.is_empty() {
+                    continue;
+                }
+                if let Err(e) = emitter.emit("docker-exec-output", json!({ "stream_id": exec_stream, "data": data })) {
+                    error!("❌ Event emit hatası: {}", e);
+                }
+            }
+        };
+        tokio::select! {
+            _ = forward_input => {}
+            _ = forward_output => {}
+        }
+        let inspect = client.get_json(&format!("/exec/{}/json", exec)).await?;
+        Ok(inspect["ExitCode"].as_i64())
     };
 
-    let output = Command::new("docker")
-        .args(&args)
-        .current_dir(std::path::Path::new(&path).parent().unwrap_or(std::path::Path::new(".")))
-        .output()
-        .map_err(|e| e.to_string())?;
+    info!("💻 Container içinde komut başlatıldı [{}]: {}", container, exec_id);
+    state.start(&stream_id, || ActiveStream {
+        task: spawn_stream(app, state.streams.clone(), stream_id.clone(), "exec", container, work),
+        input: Some(input),
+        exec_id: Some(exec_id),
+    })?;
+    Ok(stream_id)
+}
 
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
+/// Send terminal input to an exec session
+#[tauri::command]
+pub async fn docker_exec_input(state: State<'_, DockerState>, stream_id: String, data: String) -> Result<(), String> {
+    let input = state.with_stream(&stream_id, |stream| stream.input.clone())?
+        .ok_or_else(|| format!("Stream girdi kabul etmiyor: {}", stream_id))?;
+    input.send(data.into_bytes()).map_err(|_| "Exec oturumu kapanmış".to_string())
+}
+
+/// Resize the TTY of an exec session to the frontend terminal
+#[tauri::command]
+pub async fn docker_exec_resize(state: State<'_, DockerState>, stream_id: String, rows: u16, cols: u16) -> Result<(), String> {
+    let exec_id = state.with_stream(&stream_id, |stream| stream.exec_id.clone())?
+        .ok_or_else(|| format!("Stream bir exec oturumu değil: {}", stream_id))?;
+    DockerClient::from_env()
+        .request("POST", &format!("/exec/{}/resize?h={}&w={}", exec_id, rows, cols), None)
+        .await?;
+    Ok(())
+}
+
+/// Stream CPU, memory and network usage as `docker-stats` events, about once a second
+#[tauri::command]
+pub async fn docker_stream_stats<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DockerState>,
+    id: String,
+) -> Result<String, String> {
+    let container = path_segment(&id)?.to_string();
+    let mut response = DockerClient::from_env()
+        .stream(&format!("/containers/{}/stats?stream=true", container))
+        .await?;
+
+    let stream_id = uuid::Uuid::new_v4().to_string();
+    let emitter = app.clone();
+    let (stats_stream, stats_container) = (stream_id.clone(), container.clone());
+    let work = async move {
+        // One JSON document per line, not aligned with chunks
+        let mut pending = Vec::new();
+        while let Some(chunk) = response.next_chunk().await? {
+            pending.extend_from_slice(&chunk);
+            while let Some(end) = pending.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                let Ok(sample) = serde_json::from_slice::<Value>(&line) else {
+                    continue;
+                };
+                if let Err(e) = emitter.emit("docker-stats", json!({
+                    "stream_id": stats_stream,
+                    "container": stats_container,
+                    "stats": container_stats(&sample)
+                })) {
+                    error!("❌ Event emit hatası: {}", e);
+                }
+            }
+        }
+        Ok(None)
+    };
+
+    state.start(&stream_id, || ActiveStream {
+        task: spawn_stream(app, state.streams.clone(), stream_id.clone(), "stats", container, work),
+        input: None,
+        exec_id: None,
+    })?;
+    Ok(stream_id)
+}
+
+/// Cancel a log, exec or stats stream; closing an exec connection hangs up its process
+#[tauri::command]
+pub async fn docker_stop_stream<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DockerState>,
+    stream_id: String,
+) -> Result<(), String> {
+    let stream = state.streams.lock().map_err(|e| e.to_string())?.remove(&stream_id);
+    let Some(stream) = stream else {
+        return Ok(());
+    };
+    stream.task.abort();
+    if let Err(e) = app.emit("docker-stream-ended", json!({
+        "stream_id": stream_id,
+        "cancelled": true
+    })) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tokio::net::UnixListener;
+
+    /// Fake engine: answers each connection with the next canned response and
+    /// returns the request lines it saw
+    fn fake_engine(socket: &Path, responses: Vec<String>) -> JoinHandle<Vec<String>> {
+        let listener = UnixListener::bind(socket).unwrap();
+        tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                requests.push(line.trim().to_string());
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).await.unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                }
+                reader.get_mut().write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        })
+    }
+
+    fn socket_path() -> PathBuf {
+        std::env::temp_dir().join(format!("corex_docker_{}.sock", uuid::Uuid::new_v4()))
+    }
+
+    fn json_response(status: &str, body: &str) -> String {
+        format!("HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body)
+    }
+
+    #[tokio::test]
+    async fn test_lists_containers_and_images() {
+        let socket = socket_path();
+        let containers = r#"[{"Id":"4f3c2b1a0e9d8c7b6a5f","Names":["/web"],"Image":"nginx:1.25","State":"running","Status":"Up 2 hours"}]"#;
+        // Chunked, split in the middle of the JSON
+        let (first, second) = containers.split_at(20);
+        let chunked = format!(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            first.len(), first, second.len(), second
+        );
+        let images = r#"[
+            {"Id":"sha256:9a8b7c6d5e4f3a2b1c0d","RepoTags":["nginx:1.25","localhost:5000/app:dev"],"Size":187000000,"Created":1700000000},
+            {"Id":"sha256:0123456789abcdef0123","RepoTags":null,"Size":5,"Created":1}
+        ]"#;
+        let server = fake_engine(&socket, vec![chunked, json_response("200 OK", images)]);
+
+        let client = DockerClient::new(&socket);
+        let containers = client.list_containers().await.unwrap();
+        assert_eq!(containers.len(), 1);
+        assert_eq!(containers[0].id, "4f3c2b1a0e9d");
+        assert_eq!(containers[0].name, "web");
+        assert_eq!((containers[0].state.as_str(), containers[0].status.as_str()), ("running", "Up 2 hours"));
+
+        let images = client.list_images().await.unwrap();
+        let tags: Vec<(&str, &str, &str)> = images.iter()
+            .map(|i| (i.id.as_str(), i.repository.as_str(), i.tag.as_str()))
+            .collect();
+        assert_eq!(tags, vec![
+            ("9a8b7c6d5e4f", "nginx", "1.25"),
+            ("9a8b7c6d5e4f", "localhost:5000/app", "dev"),
+            ("0123456789ab", "<none>", "<none>"),
+        ]);
+        assert_eq!(images[0].size, 187000000);
+
+        assert_eq!(server.await.unwrap(), vec![
+            "GET /containers/json?all=true HTTP/1.1",
+            "GET /images/json HTTP/1.1",
+        ]);
+        let _ = std::fs::remove_file(&socket);
+    }
+
+    #[tokio::test]
+    async fn test_container_actions_and_errors() {
+        let socket = socket_path();
+        let server = fake_engine(&socket, vec![
+            "HTTP/1.1 204 No Content\r\n\r\n".to_string(),
+            "HTTP/1.1 304 Not Modified\r\n\r\n".to_string(),
+            "HTTP/1.1 204 No Content\r\n\r\n".to_string(),
+            json_response("409 Conflict", r#"{"message":"image is being used by running container 4f3c2b1a0e9d"}"#),
+        ]);
+
+        let client = DockerClient::new(&socket);
+        client.container_action("web", "pause").await.unwrap();
+        client.container_action("web", "start").await.unwrap();
+        client.container_action("4f3c2b1a0e9d", "remove").await.unwrap();
+        let error = client.remove_image("nginx:1.25").await.unwrap_err();
+        assert!(error.contains("409") && error.contains("being used by running container"), "{}", error);
+
+        assert!(client.container_action("web", "explode").await.is_err());
+        assert!(client.container_action("web/../../images/x", "start").await.is_err());
+        assert!(client.remove_image("nginx latest").await.is_err());
+
+        assert_eq!(server.await.unwrap(), vec![
+            "POST /containers/web/pause HTTP/1.1",
+            "POST /containers/web/start HTTP/1.1",
+            "DELETE /containers/4f3c2b1a0e9d?force=true HTTP/1.1",
+            "DELETE /images/nginx:1.25 HTTP/1.1",
+        ]);
+        let _ = std::fs::remove_file(&socket);
+
+        let missing = DockerClient::new(socket_path()).list_containers().await.unwrap_err();
+        assert!(missing.contains("Docker'a bağlanılamadı"), "{}", missing);
+    }
+
+    #[tokio::test]
+    async fn test_pull_build_and_exec() {
+        let socket = socket_path();
+        let pull = concat!(
+            "{\"status\":\"Pulling from library/alpine\",\"id\":\"3.20\"}\n",
+            "{\"status\":\"Downloading\",\"progress\":\"[==>  ]\",\"id\":\"a1b2\"}\n",
+            "{\"status\":\"Pull complete\",\"id\":\"a1b2\"}",
+        );
+        let build = concat!(
+            "{\"stream\":\"Step 1/2 : FROM alpine\\n\"}\n",
+            "{\"error\":\"RUN false returned a non-zero code: 1\"}\n",
+        );
+        let mut frames = Vec::new();
+        frames.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 9]);
+        frames.extend_from_slice(b"fetching\n");
+        frames.extend_from_slice(&[2, 0, 0, 0, 0, 0, 0, 4]);
+        frames.extend_from_slice(b"warn");
+        let exec_start = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/vnd.docker.multiplexed-stream\r\n\r\n{}",
+            String::from_utf8(frames).unwrap()
+        );
+        let server = fake_engine(&socket, vec![
+            json_response("404 Not Found", r#"{"message":"No such image: alpine:3.20"}"#),
+            json_response("200 OK", pull),
+            json_response("200 OK", build),
+            json_response("201 Created", r#"{"Id":"e1"}"#),
+            exec_start,
+            json_response("200 OK", r#"{"ExitCode":2}"#),
+        ]);
+
+        let client = DockerClient::new(&socket);
+        assert!(!client.image_exists("alpine:3.20").await.unwrap());
+
+        let mut lines = Vec::new();
+        client.pull_image("alpine:3.20", |line| lines.push(line.to_string())).await.unwrap();
+        assert_eq!(lines, vec!["3.20: Pulling from library/alpine", "a1b2: Pull complete"]);
+
+        let mut lines = Vec::new();
+        let args = BTreeMap::from([("VARIANT".to_string(), "1.80".to_string())]);
+        let error = client
+            .build_image(b"tar", ".devcontainer/Dockerfile", "corex-dev-api", &args, None, |line| lines.push(line.to_string()))
+            .await
+            .unwrap_err();
+        assert!(error.contains("non-zero code"), "{}", error);
+        assert_eq!(lines, vec!["Step 1/2 : FROM alpine"]);
+
+        let mut output = Vec::new();
+        let code = client
+            .exec("web", json!({ "Cmd": ["cargo", "fetch"] }), |stream, line| output.push(format!("{}: {}", stream, line)))
+            .await
+            .unwrap();
+        assert_eq!(code, 2);
+        assert_eq!(output, vec!["stdout: fetching", "stderr: warn"]);
+
+        assert_eq!(server.await.unwrap(), vec![
+            "GET /images/alpine:3.20/json HTTP/1.1",
+            "POST /images/create?fromImage=alpine&tag=3.20 HTTP/1.1",
+            "POST /build?dockerfile=.devcontainer%2FDockerfile&t=corex-dev-api&rm=true&buildargs=%7B%22VARIANT%22%3A%221.80%22%7D HTTP/1.1",
+            "POST /containers/web/exec HTTP/1.1",
+            "POST /exec/e1/start HTTP/1.1",
+            "GET /exec/e1/json HTTP/1.1",
+        ]);
+        let _ = std::fs::remove_file(&socket);
+    }
+
+    #[test]
+    fn test_demuxes_log_frames_across_reads() {
+        let mut frames = Vec::new();
+        frames.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 6]);
+        frames.extend_from_slice(b"ready\n");
+        frames.extend_from_slice(&[2, 0, 0, 0, 0, 0, 0, 5]);
+        frames.extend_from_slice(b"oops\n");
+
+        let mut demuxer = LogDemuxer::default();
+        // Cut inside the second header
+        assert_eq!(demuxer.push(&frames[..17]), vec![("stdout", b"ready\n".to_vec())]);
+        assert!(demuxer.push(&frames[17..20]).is_empty());
+        assert_eq!(demuxer.push(&frames[20..]), vec![("stderr", b"oops\n".to_vec())]);
+        assert!(demuxer.buffer.is_empty());
+    }
+
+    #[test]
+    fn test_keeps_split_utf8_for_next_read() {
+        let text = "çalışıyor".as_bytes();
+        // Cut inside "ı", which is two bytes
+        let cut = "çal".len() + 1;
+        let mut pending = text[..cut].to_vec();
+        assert_eq!(take_utf8(&mut pending), "çal");
+        assert_eq!(pending.len(), 1);
+
+        pending.extend_from_slice(&text[cut..]);
+        assert_eq!(take_utf8(&mut pending), "ışıyor");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_computes_stats_like_docker_cli() {
+        let sample = serde_json::json!({
+            "cpu_stats": { "cpu_usage": { "total_usage": 400_000_000u64 }, "system_cpu_usage": 10_000_000_000u64, "online_cpus": 4 },
+            "precpu_stats": { "cpu_usage": { "total_usage": 200_000_000u64 }, "system_cpu_usage": 8_000_000_000u64 },
+            "memory_stats": { "usage": 300, "limit": 1000, "stats": { "inactive_file": 100 } },
+            "networks": { "eth0": { "rx_bytes": 10, "tx_bytes": 20 }, "eth1": { "rx_bytes": 1, "tx_bytes": 2 } }
+        });
+        assert_eq!(container_stats(&sample), ContainerStats {
+            cpu_percent: 40.0,
+            memory_usage: 200,
+            memory_limit: 1000,
+            memory_percent: 20.0,
+            network_rx: 11,
+            network_tx: 22,
+        });
+
+        // First sample has no previous reading and a stopped container has no networks
+        let empty = container_stats(&serde_json::json!({ "memory_stats": {} }));
+        assert_eq!((empty.cpu_percent, empty.memory_percent, empty.network_rx), (0.0, 0.0, 0));
     }
 }
@@ -0,0 +1,152 @@
+// src-tauri/src/generation_queue.rs
+// Per-model admission queue for local generations
+
+use std::collections::VecDeque;
+use tokio::sync::oneshot;
+
+/// Outcome of asking a queue for a slot
+pub enum Admission {
+    /// A slot was free; the caller is now running
+    Run,
+    /// The caller has to wait; the receiver fires when a slot is handed over
+    /// and errors if the request is removed from the queue (cancelled)
+    Wait {
+        position: usize,
+        ready: oneshot::Receiver<()>,
+    },
+}
+
+struct Waiter {
+    request_id: String,
+    wake: oneshot::Sender<()>,
+}
+
+/// FIFO queue that lets at most `limit` generations run on a model at once
+pub struct GenerationQueue {
+    limit: usize,
+    running: usize,
+    waiting: VecDeque<Waiter>,
+}
+
+impl GenerationQueue {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            running: 0,
+            waiting: VecDeque::new(),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn running(&self) -> usize {
+        self.running
+    }
+
+    pub fn waiting(&self) -> Vec<String> {
+        self.waiting.iter().map(|w| w.request_id.clone()).collect()
+    }
+
+    pub fn admit(&mut self, request_id: &str) -> Admission {
+        if self.running < self.limit && self.waiting.is_empty() {
+            self.running += 1;
+            return Admission::Run;
+        }
+        let (wake, ready) = oneshot::channel();
+        self.waiting.push_back(Waiter {
+            request_id: request_id.to_string(),
+            wake,
+        });
+        Admission::Wait {
+            position: self.waiting.len(),
+            ready,
+        }
+    }
+
+    /// Release a running slot and hand it to the next waiter
+    pub fn finish(&mut self) {
+        self.running = self.running.saturating_sub(1);
+        self.promote();
+    }
+
+    /// Drop a queued request; its receiver resolves with an error
+    pub fn remove_waiting(&mut self, request_id: &str) -> bool {
+        let before = self.waiting.len();
+        self.waiting.retain(|w| w.request_id != request_id);
+        self.waiting.len() != before
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit.max(1);
+        self.promote();
+    }
+
+    fn promote(&mut self) {
+        while self.running < self.limit {
+            let Some(waiter) = self.waiting.pop_front() else {
+                break;
+            };
+            // A dropped receiver means the caller went away; skip it
+            if waiter.wake.send(()).is_ok() {
+                self.running += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait(admission: Admission) -> (usize, oneshot::Receiver<()>) {
+        match admission {
+            Admission::Wait { position, ready } => (position, ready),
+            Admission::Run => panic!("expected to wait"),
+        }
+    }
+
+    #[test]
+    fn test_runs_up_to_limit_then_queues_in_order() {
+        let mut queue = GenerationQueue::new(1);
+        assert!(matches!(queue.admit("a"), Admission::Run));
+        let (pos_b, mut b) = wait(queue.admit("b"));
+        let (pos_c, mut c) = wait(queue.admit("c"));
+        assert_eq!((pos_b, pos_c), (1, 2));
+
+        queue.finish();
+        assert!(b.try_recv().is_ok());
+        assert!(c.try_recv().is_err());
+        assert_eq!(queue.running(), 1);
+        assert_eq!(queue.waiting(), vec!["c".to_string()]);
+
+        queue.finish();
+        assert!(c.try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_removing_a_waiter_closes_its_channel() {
+        let mut queue = GenerationQueue::new(1);
+        assert!(matches!(queue.admit("a"), Admission::Run));
+        let (_, mut b) = wait(queue.admit("b"));
+        assert!(queue.remove_waiting("b"));
+        assert!(matches!(b.try_recv(), Err(oneshot::error::TryRecvError::Closed)));
+        queue.finish();
+        assert_eq!(queue.running(), 0);
+    }
+
+    #[test]
+    fn test_raising_the_limit_wakes_waiters_and_skips_dropped_ones() {
+        let mut queue = GenerationQueue::new(1);
+        assert!(matches!(queue.admit("a"), Admission::Run));
+        let (_, b) = wait(queue.admit("b"));
+        let (_, mut c) = wait(queue.admit("c"));
+        drop(b);
+
+        queue.set_limit(2);
+        assert!(c.try_recv().is_ok());
+        assert_eq!(queue.running(), 2);
+        assert!(queue.waiting().is_empty());
+    }
+}
@@ -1,16 +1,22 @@
 // GGUF System - Complete implementation in one file
 use llama_cpp_2::context::params::LlamaContextParams;
+use llama_cpp_2::context::LlamaContext;
 use llama_cpp_2::llama_backend::LlamaBackend;
 use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::params::LlamaModelParams;
 use llama_cpp_2::model::{LlamaModel, AddBos};
-use std::path::Path;
+use llama_cpp_2::token::LlamaToken;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use log::{info, error, warn};
-use tauri::State;
+use tauri::{AppHandle, Emitter, Manager, State};
 use serde_json::json;
 use base64::{Engine as _, engine::general_purpose}; // 🆕 Base64 decoding
-use rand::Rng; // 🆕 Random number generation for sampling
+
+use crate::generation_queue::{Admission, GenerationQueue};
+use crate::sampling::{Sampler, SamplingParams};
+use crate::streaming::StreamToken;
 
 use std::collections::HashMap;
 
@@ -26,6 +32,12 @@ pub struct GgufState {
     pub backend: Option<Arc<LlamaBackend>>,
     pub models: HashMap<String, Arc<LoadedModel>>, // Model path -> Model info
     pub backend_initialized: bool,
+    pub generations: HashMap<String, ActiveGeneration>, // Request id -> cancel flag
+    pub queues: HashMap<String, GenerationQueue>, // Model path -> admission queue
+    pub default_concurrency: usize,
+    pub conversations: HashMap<(String, String), ConversationWorker>, // (model path, conversation id) -> cached context
+    pub max_cached_contexts: usize,
+    conversation_clock: u64,
 }
 
 impl Default for GgufState {
@@ -34,6 +46,12 @@ impl Default for GgufState {
             backend: None,
             models: HashMap::new(),
             backend_initialized: false,
+            generations: HashMap::new(),
+            queues: HashMap::new(),
+            default_concurrency: 1,
+            conversations: HashMap::new(),
+            max_cached_contexts: 4,
+            conversation_clock: 0,
         }
     }
 }
@@ -77,23 +95,7 @@ pub async fn load_gguf_model(
     }
 
     // Initialize backend only once
-    let backend = {
-        let mut guard = state.lock().unwrap();
-        if !guard.backend_initialized {
-            info!("🔄 Initializing backend (first time)...");
-            let backend = Arc::new(LlamaBackend::init()
-                .map_err(|e| {
-                    error!("❌ Backend init failed: {:?}", e);
-                    format!("Backend init failed: {:?}", e)
-                })?);
-            
-            guard.backend = Some(backend.clone());
-            guard.backend_initialized = true;
-            backend
-        } else {
-            guard.backend.as_ref().unwrap().clone()
-        }
-    };
+    let backend = ensure_backend(&state)?;
 
     // GPU layers parametresini ayarla
     // CUDA veya Vulkan yoksa otomatik olarak 0'a düşür
@@ -186,289 +188,802 @@ pub async fn load_gguf_model(
     Ok(format!("✅ Model başarıyla yüklendi: {}", model_path))
 }
 
-#[tauri::command]
-pub async fn chat_with_gguf_model(
-    state: State<'_, Arc<Mutex<GgufState>>>,
-    model_path: String, // 🆕 Model path required
-    prompt: String,
-    max_tokens: u32,
-    temperature: f32,
-) -> Result<String, String> {
-    info!("🔵 Starting inference...");
-    info!("📝 Prompt length: {} chars", prompt.len());
-    info!("⚙️ Max tokens: {}, Temperature: {}", max_tokens, temperature);
+/// Initialize the shared llama backend on first use and return it
+pub fn ensure_backend(state: &Arc<Mutex<GgufState>>) -> Result<Arc<LlamaBackend>, String> {
+    let mut guard = match state.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if !guard.backend_initialized {
+        info!("🔄 Initializing backend (first time)...");
+        let backend = Arc::new(LlamaBackend::init()
+            .map_err(|e| {
+                error!("❌ Backend init failed: {:?}", e);
+                format!("Backend init failed: {:?}", e)
+            })?);
 
-    // 🆕 Get model and backend from pool with minimum lock time
-    let (loaded_model, backend) = {
+        guard.backend = Some(backend.clone());
+        guard.backend_initialized = true;
+        Ok(backend)
+    } else {
+        guard.backend.as_ref()
+            .cloned()
+            .ok_or_else(|| "Backend not initialized".to_string())
+    }
+}
+
+/// Compute one L2-normalized embedding per text with an embedding GGUF (e.g. a BGE quant)
+///
+/// Runs synchronously on the calling thread; callers in async code should wrap
+/// it in `spawn_blocking`. Inputs longer than `n_ctx` tokens are truncated.
+pub fn embed_texts(
+    model: &LlamaModel,
+    backend: &LlamaBackend,
+    n_ctx: u32,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let n_ctx = n_ctx.max(64);
+
+    // Encoder models need the whole input in a single ubatch
+    let ctx_params = LlamaContextParams::default()
+        .with_n_ctx(std::num::NonZeroU32::new(n_ctx))
+        .with_n_batch(n_ctx)
+        .with_n_ubatch(n_ctx)
+        .with_embeddings(true);
+
+    let mut context = model.new_context(backend, ctx_params)
+        .map_err(|e| format!("Embedding context creation failed: {:?}", e))?;
+
+    let mut batch = LlamaBatch::new(n_ctx as usize, 1);
+    let mut embeddings = Vec::with_capacity(texts.len());
+
+    for text in texts {
+        let mut tokens = model.str_to_token(text, AddBos::Always)
+            .map_err(|e| format!("Tokenization failed: {:?}", e))?;
+        tokens.truncate(n_ctx as usize);
+        if tokens.is_empty() {
+            embeddings.push(vec![0.0; model.n_embd().max(0) as usize]);
+            continue;
+        }
+
+        batch.clear();
+        batch.add_sequence(&tokens, 0, false)
+            .map_err(|e| format!("Batch add failed: {:?}", e))?;
+
+        context.clear_kv_cache();
+        context.decode(&mut batch)
+            .map_err(|e| format!("Embedding decode failed: {:?}", e))?;
+
+        let raw = context.embeddings_seq_ith(0)
+            .map_err(|e| format!("Embedding okunamadı: {:?}", e))?;
+
+        let norm = raw.iter().map(|v| v * v).sum::<f32>().sqrt();
+        let embedding = if norm > 0.0 {
+            raw.iter().map(|v| v / norm).collect()
+        } else {
+            raw.to_vec()
+        };
+        embeddings.push(embedding);
+    }
+
+    Ok(embeddings)
+}
+
+/// Embed `texts` with a pooled model, in a slot of that model's generation queue
+///
+/// Embedding contexts compete with generations for the same memory, so they
+/// wait their turn like any other request. The model is looked up only once a
+/// slot is free and is not kept afterwards.
+pub async fn embed_with_pooled_model(
+    state: &Arc<Mutex<GgufState>>,
+    model_path: &str,
+    texts: Vec<String>,
+) -> Result<Vec<Vec<f32>>, String> {
+    let request_id = format!("embedding-{}", uuid::Uuid::new_v4());
+    let admission = {
+        let mut guard = match state.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let limit = guard.default_concurrency;
+        guard.queues.entry(model_path.to_string())
+            .or_insert_with(|| GenerationQueue::new(limit))
+            .admit(&request_id)
+    };
+    if let Admission::Wait { ready, .. } = admission {
+        ready.await.map_err(|_| "Embedding isteği kuyruktan çıkarıldı".to_string())?;
+    }
+
+    let pooled = {
         let guard = match state.lock() {
             Ok(g) => g,
             Err(poisoned) => poisoned.into_inner(),
         };
-        
-        let model = guard.models.get(&model_path)
-            .cloned() // Arc cloing is cheap
-            .ok_or_else(|| format!("Model havuzda bulunamadı: {}", model_path))?;
-            
-        let backend = guard.backend.as_ref()
-            .cloned()
-            .ok_or_else(|| "Backend not initialized".to_string())?;
-            
-        (model, backend)
+        guard.models.get(model_path).cloned().zip(guard.backend.clone())
+    };
+    let result = match pooled {
+        Some((loaded_model, backend)) => tokio::task::spawn_blocking(move || {
+            embed_texts(&loaded_model.model, &backend, loaded_model.n_ctx, &texts)
+        })
+        .await
+        .map_err(|e| format!("Embedding görevi başarısız: {}", e))
+        .and_then(|r| r),
+        None => Err(format!("Embedding modeli yüklü değil: {}", model_path)),
     };
 
-    let model = &loaded_model.model;
-    let n_ctx = loaded_model.n_ctx;
+    if let Ok(mut guard) = state.lock() {
+        if let Some(queue) = guard.queues.get_mut(model_path) {
+            queue.finish();
+        }
+    }
+    result
+}
 
-    info!("📦 Using model from pool: {}", model_path);
+/// Why a generation stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    Eos,
+    Length,
+    Cancelled,
+}
 
-    // Create context with proper KV cache size (FIX-31)
-    let kv_cache_size = (n_ctx + max_tokens).max(4096);
-    
-    // 🔥 FIXED: n_batch context'in tek seferde işleyebileceği max token sayısıdır.
-    // LlamaBatch boyutu n_batch'den büyük olamaz.
-    let n_batch = 8192; // Max batch size increase
-    
-    info!("📊 Context Params: n_ctx={}, n_batch={}", kv_cache_size, n_batch);
-    
-    let ctx_params = LlamaContextParams::default()
-        .with_n_ctx(std::num::NonZeroU32::new(kv_cache_size as u32))
-        .with_n_batch(n_batch as u32);
+pub struct GenerationOutput {
+    pub text: String,
+    pub tokens: usize,
+    pub prompt_tokens: usize,
+    /// Prompt tokens served from a cached context instead of being decoded
+    pub reused_tokens: usize,
+    pub stop_reason: StopReason,
+}
 
-    let mut context = model.new_context(&backend, ctx_params)
-        .map_err(|e| {
-            error!("❌ Context creation failed: {:?}", e);
-            format!("Context creation failed: {:?}", e)
-        })?;
+/// A queued or running generation, so `cancel_generation` can reach it
+pub struct ActiveGeneration {
+    pub model_path: String,
+    pub cancel: Arc<AtomicBool>,
+}
+
+// 🔥 FIXED: n_batch context'in tek seferde işleyebileceği max token sayısıdır.
+// LlamaBatch boyutu n_batch'den büyük olamaz.
+const MAX_BATCH_SIZE: usize = 8192;
+
+/// Number of leading tokens of `prompt` whose KV entries can be kept.
+///
+/// At least the last prompt token is always decoded again, because sampling
+/// needs fresh logits for it.
+fn reusable_prefix<T: PartialEq>(cached: &[T], prompt: &[T]) -> usize {
+    let common = cached.iter().zip(prompt).take_while(|(a, b)| a == b).count();
+    common.min(prompt.len().saturating_sub(1))
+}
 
-    info!("✅ Context created with KV cache size: {}", kv_cache_size);
+/// Session file for one conversation on one model
+fn session_file_name(model_path: &str, conversation_id: &str) -> String {
+    use sha2::{Digest, Sha256};
 
+    let mut hasher = Sha256::new();
+    hasher.update(model_path.as_bytes());
+    hasher.update([0]);
+    hasher.update(conversation_id.as_bytes());
+    format!("{}.session", &hex::encode(hasher.finalize())[..32])
+}
+
+fn tokenize_prompt(loaded: &LoadedModel, prompt: &str) -> Result<Vec<LlamaToken>, String> {
     // Tokenize prompt with BOS token
-    info!("🔤 Tokenizing prompt with BOS token...");
-    let tokens = model.str_to_token(&prompt, AddBos::Always)
+    let tokens = loaded.model.str_to_token(prompt, AddBos::Always)
         .map_err(|e| {
             error!("❌ Tokenization failed: {:?}", e);
             format!("Tokenization failed: {:?}", e)
         })?;
 
     info!("✅ Tokenized: {} tokens", tokens.len());
-    
-    // Log first few tokens for debugging
-    if tokens.len() > 0 {
-        info!("🔍 First 10 tokens: {:?}", &tokens[..tokens.len().min(10)]);
+
+    if tokens.is_empty() {
+        return Err("Prompt boş".to_string());
     }
-    
-    // Check if prompt is too long
-    if tokens.len() > n_ctx as usize {
-        error!("❌ Prompt too long: {} tokens (max: {})", tokens.len(), n_ctx);
-        return Err(format!("Prompt too long: {} tokens (max: {})", tokens.len(), n_ctx));
+    if tokens.len() > loaded.n_ctx as usize {
+        error!("❌ Prompt too long: {} tokens (max: {})", tokens.len(), loaded.n_ctx);
+        return Err(format!("Prompt too long: {} tokens (max: {})", tokens.len(), loaded.n_ctx));
     }
+    Ok(tokens)
+}
 
-    // Create batch - MUST be at least as large as the number of prompt tokens
-    // But not more than n_batch of the context (FIX for abort crash)
-    let max_batch_size = 8192;
-    let batch_size = tokens.len().min(max_batch_size);
-    
-    info!("📦 Creating batch: prompt_tokens={}, batch_size={}, n_ctx={}", tokens.len(), batch_size, n_ctx);
-    
-    // Create batch outside if/else so it's available later
-    let mut batch = LlamaBatch::new(batch_size, 1);
-    
-    // Process prompt in chunks if necessary
-    if tokens.len() > max_batch_size {
-        info!("⚠️ Prompt too long for single batch, processing in chunks...");
-        
-        let mut processed = 0;
-        
-        while processed < tokens.len() {
+/// A context together with the tokens whose KV entries it currently holds
+struct KvSlot<'a> {
+    model: &'a LlamaModel,
+    context: LlamaContext<'a>,
+    tokens: Vec<LlamaToken>,
+    n_ctx: u32,
+}
+
+impl<'a> KvSlot<'a> {
+    fn new(loaded: &'a LoadedModel, backend: &LlamaBackend, max_tokens: u32) -> Result<Self, String> {
+        // Create context with proper KV cache size (FIX-31)
+        let n_ctx = (loaded.n_ctx + max_tokens).max(4096);
+
+        info!("📊 Context Params: n_ctx={}, n_batch={}", n_ctx, MAX_BATCH_SIZE);
+
+        let ctx_params = LlamaContextParams::default()
+            .with_n_ctx(std::num::NonZeroU32::new(n_ctx))
+            .with_n_batch(MAX_BATCH_SIZE as u32);
+
+        let context = loaded.model.new_context(backend, ctx_params)
+            .map_err(|e| {
+                error!("❌ Context creation failed: {:?}", e);
+                format!("Context creation failed: {:?}", e)
+            })?;
+
+        Ok(Self { model: &loaded.model, context, tokens: Vec::new(), n_ctx })
+    }
+
+    fn fits(&self, prompt_tokens: usize, max_tokens: u32) -> bool {
+        prompt_tokens + max_tokens as usize <= self.n_ctx as usize
+    }
+
+    fn restore(&mut self, path: &Path) {
+        match self.context.load_session_file(path, self.n_ctx as usize) {
+            Ok(tokens) => {
+                info!("♻️ Session restored: {} tokens from {}", tokens.len(), path.display());
+                self.tokens = tokens;
+            }
+            Err(e) => warn!("⚠️ Session dosyası yüklenemedi ({}): {:?}", path.display(), e),
+        }
+    }
+
+    /// Write the session next to `path` and rename it into place, so a worker respawned for
+    /// the same conversation never loads a half-written file
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Session klasörü oluşturulamadı: {}", e))?;
+        }
+        let temp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+        if let Err(e) = self.context.save_session_file(&temp, &self.tokens) {
+            let _ = std::fs::remove_file(&temp);
+            return Err(format!("Session kaydedilemedi: {:?}", e));
+        }
+        std::fs::rename(&temp, path).map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            format!("Session kaydedilemedi: {}", e)
+        })
+    }
+
+    /// Bring the cache in line with `prompt`, decoding only what follows the
+    /// shared prefix. Returns how many prompt tokens were reused, or `None`
+    /// when cancelled part way.
+    fn prefill(&mut self, prompt: &[LlamaToken], cancel: &AtomicBool) -> Result<Option<usize>, String> {
+        let mut reused = reusable_prefix(&self.tokens, prompt);
+        if reused < self.tokens.len() {
+            let trimmed = self.context.clear_kv_cache_seq(Some(0), Some(reused as u32), None)
+                .unwrap_or(false);
+            if !trimmed {
+                self.context.clear_kv_cache();
+                reused = 0;
+            }
+            self.tokens.truncate(reused);
+        }
+
+        let suffix = &prompt[reused..];
+        info!("♻️ Reusing {} cached tokens, decoding {}", reused, suffix.len());
+
+        // Batch must never exceed n_batch (FIX for abort crash); long prompts go in chunks
+        let mut batch = LlamaBatch::new(suffix.len().min(MAX_BATCH_SIZE), 1);
+        for (chunk_idx, chunk) in suffix.chunks(MAX_BATCH_SIZE).enumerate() {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
             batch.clear();
-            let chunk_size = (tokens.len() - processed).min(max_batch_size);
-            
-            for i in 0..chunk_size {
-                let token_idx = processed + i;
-                batch.add(
-                    tokens[token_idx], 
-                    token_idx as i32, 
-                    &[0], 
-                    token_idx == tokens.len() - 1
-                ).map_err(|e| format!("Batch add failed: {:?}", e))?;
+            for token in chunk {
+                let pos = self.tokens.len();
+                batch.add(*token, pos as i32, &[0], pos == prompt.len() - 1)
+                    .map_err(|e| format!("Batch add failed: {:?}", e))?;
+                self.tokens.push(*token);
             }
-            
-            context.decode(&mut batch)
+            self.context.decode(&mut batch)
                 .map_err(|e| {
-                    error!("❌ Decode failed at chunk {}: {:?}", processed / max_batch_size, e);
+                    error!("❌ Decode failed at chunk {}: {:?}", chunk_idx, e);
                     format!("Decode failed: {:?}", e)
                 })?;
-            
-            processed += chunk_size;
-            info!("📊 Processed {}/{} tokens", processed, tokens.len());
-        }
-        
-        info!("✅ All prompt chunks processed!");
-    } else {
-        // Single batch processing (normal case)
-        for (i, token) in tokens.iter().enumerate() {
-            batch.add(*token, i as i32, &[0], i == tokens.len() - 1)
-                .map_err(|e| format!("Batch add failed: {:?}", e))?;
         }
 
-        // Initial decode (process prompt)
-        context.decode(&mut batch)
-            .map_err(|e| {
-                error!("❌ Decode failed: {:?}", e);
-                format!("Decode failed: {:?}", e)
-            })?;
-        
         info!("✅ Prompt processed!");
+        Ok(Some(reused))
     }
 
-    // Token generation
-    let mut response_tokens = Vec::new();
-    
-    // 🔥 FIXED: n_cur her zaman tüm prompt tokenlarının sayısı olmalı (chunking olsa bile)
-    let mut n_cur = tokens.len() as i32;
-    
-    info!("🎲 Starting token generation from position {}", n_cur);
+    /// Prefill `prompt` and sample up to `max_tokens` tokens after it.
+    ///
+    /// Every decoded piece is handed to `on_piece` as soon as it is sampled,
+    /// and `cancel` is checked before each token.
+    fn generate(
+        &mut self,
+        prompt: &[LlamaToken],
+        max_tokens: u32,
+        sampler: &mut Sampler,
+        cancel: &AtomicBool,
+        mut on_piece: impl FnMut(&str),
+    ) -> Result<GenerationOutput, String> {
+        let mut output = GenerationOutput {
+            text: String::new(),
+            tokens: 0,
+            prompt_tokens: prompt.len(),
+            reused_tokens: 0,
+            stop_reason: StopReason::Length,
+        };
+        output.reused_tokens = match self.prefill(prompt, cancel)? {
+            Some(reused) => reused,
+            None => {
+                output.stop_reason = StopReason::Cancelled;
+                return Ok(output);
+            }
+        };
 
-    for i in 0..max_tokens {
-        // Get candidates
-        let candidates = context.candidates();
-        
-        // 🆕 Repetition Penalty ve Temperature Sampling
-        let candidates_vec: Vec<_> = candidates.into_iter().collect();
-        
-        // 🔄 Repetition Penalty Uygulama
-        let repeat_penalty = 1.15_f32;
-        let penalty_last_n = 64; 
-        
-        let mut recent_tokens = Vec::new();
-        let total_recent = tokens.len() + response_tokens.len();
-        let start_idx = if total_recent > penalty_last_n { total_recent - penalty_last_n } else { 0 };
-        
-        if tokens.len() > start_idx {
-            recent_tokens.extend_from_slice(&tokens[start_idx..]);
-            recent_tokens.extend_from_slice(&response_tokens);
-        } else {
-            let resp_start = start_idx - tokens.len();
-            recent_tokens.extend_from_slice(&response_tokens[resp_start..]);
-        }
-        
-        let adjusted_logits: Vec<(llama_cpp_2::token::LlamaToken, f32)> = candidates_vec.iter()
-            .map(|c| {
-                let id = c.id();
-                let mut logit = c.logit();
-                
-                if recent_tokens.contains(&id) {
-                    if logit <= 0.0 {
-                        logit *= repeat_penalty;
-                    } else {
-                        logit /= repeat_penalty;
-                    }
-                }
-                
-                (id, logit)
-            })
-            .collect();
-        
-        // Temperature-based sampling
-        let new_token_id = if temperature > 0.0 && temperature != 1.0 {
-            // Apply temperature scaling to logits
-            let scaled_logits: Vec<_> = adjusted_logits.iter()
-                .map(|(id, logit)| (*id, logit / temperature))
-                .collect();
-            
-            // Convert to probabilities using softmax
-            let max_logit = scaled_logits.iter()
-                .map(|(_, logit)| logit)
-                .fold(f32::NEG_INFINITY, |a, &b| a.max(b));
-            
-            let exp_sum: f32 = scaled_logits.iter()
-                .map(|(_, logit)| (logit - max_logit).exp())
-                .sum();
-            
-            let probs: Vec<_> = scaled_logits.iter()
-                .map(|(id, logit)| (*id, (logit - max_logit).exp() / exp_sum))
+        // Prompt + generated ids, for the repetition penalty
+        let mut history: Vec<i32> = prompt.iter().map(|t| t.0).collect();
+        let mut decoder = encoding_rs::UTF_8.new_decoder();
+        let mut batch = LlamaBatch::new(1, 1);
+
+        for i in 0..max_tokens {
+            if cancel.load(Ordering::Relaxed) {
+                info!("⏹️ Generation cancelled after {} tokens", output.tokens);
+                output.stop_reason = StopReason::Cancelled;
+                break;
+            }
+
+            let candidates: Vec<(i32, f32)> = self.context.candidates()
+                .map(|c| (c.id().0, c.logit()))
                 .collect();
-            
-            // Sample from distribution
-            let mut rng = rand::thread_rng();
-            let random_val: f32 = rng.gen();
-            let mut cumulative = 0.0;
-            
-            let mut selected_id = probs[0].0;
-            for (id, prob) in probs.iter() {
-                cumulative += prob;
-                if random_val <= cumulative {
-                    selected_id = *id;
-                    break;
+            let token = match sampler.sample(candidates, &history) {
+                Some(id) => LlamaToken(id),
+                None => break,
+            };
+
+            if self.model.is_eog_token(token) {
+                info!("✅ EOS token found at position {}, stopping", i);
+                output.stop_reason = StopReason::Eos;
+                break;
+            }
+
+            history.push(token.0);
+            output.tokens += 1;
+
+            match self.model.token_to_piece(token, &mut decoder, false, None) {
+                Ok(piece) if !piece.is_empty() => {
+                    output.text.push_str(&piece);
+                    on_piece(&piece);
                 }
+                Ok(_) => {}
+                Err(e) => warn!("⏭️ Token {}: decode failed: {:?}", i, e),
             }
-            selected_id
-        } else {
-            // No temperature, just pick highest probability
-            adjusted_logits.into_iter()
-                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
-                .map(|(id, _)| id)
-                .unwrap_or(candidates_vec[0].id())
-        };
 
-        // Check for EOS (End of Sequence)
-        if model.is_eog_token(new_token_id) {
-            info!("✅ EOS token found at position {}, stopping", i);
-            break;
+            batch.clear();
+            batch.add(token, self.tokens.len() as i32, &[0], true)
+                .map_err(|e| format!("Batch add failed: {:?}", e))?;
+            self.context.decode(&mut batch)
+                .map_err(|e| format!("Decode failed at token {}: {:?}", i, e))?;
+            self.tokens.push(token);
         }
 
-        response_tokens.push(new_token_id);
-        
-        // Log first few tokens to debug
-        if i < 5 {
-            info!("🔤 Token {}: id={:?}", i, new_token_id);
+        info!("✅ Token generation completed: {} tokens ({:?})", output.tokens, output.stop_reason);
+        Ok(output)
+    }
+}
+
+/// One-off generation in a fresh context. Blocking; call from `spawn_blocking`.
+pub fn generate(
+    loaded: &LoadedModel,
+    backend: &LlamaBackend,
+    prompt: &str,
+    max_tokens: u32,
+    sampler: &mut Sampler,
+    cancel: &AtomicBool,
+    on_piece: impl FnMut(&str),
+) -> Result<GenerationOutput, String> {
+    let tokens = tokenize_prompt(loaded, prompt)?;
+    KvSlot::new(loaded, backend, max_tokens)?
+        .generate(&tokens, max_tokens, sampler, cancel, on_piece)
+}
+
+struct ConversationJob {
+    prompt: String,
+    max_tokens: u32,
+    params: SamplingParams,
+    cancel: Arc<AtomicBool>,
+    on_piece: Box<dyn FnMut(&str) + Send>,
+    reply: tokio::sync::oneshot::Sender<Result<GenerationOutput, String>>,
+}
+
+enum WorkerMessage {
+    Generate(ConversationJob),
+    /// Stop the worker, optionally writing its KV state to the session file first
+    Evict { save: bool },
+}
+
+/// Thread that owns the cached context of one conversation
+pub struct ConversationWorker {
+    sender: std::sync::mpsc::Sender<WorkerMessage>,
+    session_path: PathBuf,
+    last_used: u64,
+}
+
+impl ConversationWorker {
+    fn spawn(loaded: Arc<LoadedModel>, backend: Arc<LlamaBackend>, session_path: PathBuf) -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let path = session_path.clone();
+        std::thread::spawn(move || conversation_worker(loaded, backend, path, receiver));
+        Self { sender, session_path, last_used: 0 }
+    }
+
+    fn evict(&self, save: bool) {
+        // A closed channel means the worker already exited
+        let _ = self.sender.send(WorkerMessage::Evict { save });
+    }
+}
+
+fn conversation_worker(
+    loaded: Arc<LoadedModel>,
+    backend: Arc<LlamaBackend>,
+    session_path: PathBuf,
+    receiver: std::sync::mpsc::Receiver<WorkerMessage>,
+) {
+    let mut slot: Option<KvSlot> = None;
+    let mut restore = session_path.exists();
+
+    while let Ok(message) = receiver.recv() {
+        match message {
+            WorkerMessage::Generate(mut job) => {
+                let session = restore.then_some(session_path.as_path());
+                restore = false;
+                let result = conversation_turn(&loaded, &backend, &mut slot, session, &mut job);
+                if result.is_err() {
+                    // The KV cache may no longer match the tracked tokens
+                    slot = None;
+                }
+                let _ = job.reply.send(result);
+            }
+            WorkerMessage::Evict { save } => {
+                if let (true, Some(slot)) = (save, slot.as_ref()) {
+                    match slot.save(&session_path) {
+                        Ok(()) => info!("💾 Session saved: {} tokens -> {}", slot.tokens.len(), session_path.display()),
+                        Err(e) => warn!("⚠️ {}", e),
+                    }
+                }
+                break;
+            }
         }
+    }
+}
 
-        // Log every 50 tokens
-        if i % 50 == 0 && i > 0 {
-            info!("📊 Generated {}/{} tokens", i, max_tokens);
+fn conversation_turn<'a>(
+    loaded: &'a LoadedModel,
+    backend: &LlamaBackend,
+    slot: &mut Option<KvSlot<'a>>,
+    session: Option<&Path>,
+    job: &mut ConversationJob,
+) -> Result<GenerationOutput, String> {
+    let tokens = tokenize_prompt(loaded, &job.prompt)?;
+
+    if slot.as_ref().is_some_and(|s| !s.fits(tokens.len(), job.max_tokens)) {
+        info!("🔄 Cached context too small, recreating");
+        *slot = None;
+    }
+    if slot.is_none() {
+        let mut fresh = KvSlot::new(loaded, backend, job.max_tokens)?;
+        if let Some(path) = session {
+            fresh.restore(path);
         }
+        *slot = Some(fresh);
+    }
+    let slot = slot.as_mut().ok_or_else(|| "Context oluşturulamadı".to_string())?;
 
-        // Create new batch
-        batch.clear();
-        batch.add(new_token_id, n_cur, &[0], true)
-            .map_err(|e| format!("Batch add failed: {:?}", e))?;
+    let mut sampler = Sampler::new(job.params.clone());
+    slot.generate(&tokens, job.max_tokens, &mut sampler, &job.cancel, &mut job.on_piece)
+}
 
-        // Decode
-        context.decode(&mut batch)
-            .map_err(|e| format!("Decode failed at token {}: {:?}", i, e))?;
+/// One generation as submitted by a command
+pub struct GenerationRequest {
+    /// Caller-chosen id for `cancel_generation`; generated when missing
+    pub request_id: Option<String>,
+    /// Falls back to the first pooled model
+    pub model_path: Option<String>,
+    pub prompt: String,
+    pub max_tokens: u32,
+    pub params: SamplingParams,
+    /// Keep this conversation's context alive so the next turn reuses its KV cache
+    pub conversation_id: Option<String>,
+}
 
-        n_cur += 1;
+/// Lifecycle of a request, reported through `generation-status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenerationStatus {
+    Queued,
+    Running,
+    Cancelled,
+    Done,
+    Failed,
+}
+
+fn emit_status(app: &AppHandle, request_id: &str, model_path: &str, status: GenerationStatus, extra: serde_json::Value) {
+    let mut payload = json!({
+        "request_id": request_id,
+        "model_path": model_path,
+        "status": status,
+    });
+    if let (Some(payload), serde_json::Value::Object(extra)) = (payload.as_object_mut(), extra) {
+        payload.extend(extra);
+    }
+    if let Err(e) = app.emit("generation-status", payload) {
+        error!("❌ Event emit hatası: {}", e);
     }
+}
 
-    let total_tokens = response_tokens.len();
-    info!("✅ Token generation completed: {} tokens", total_tokens);
-    
-    // 🆕 Token'ları tek tek decode et ve birleştir
-    info!("🔤 Decoding tokens...");
-    let mut response = String::new();
-    let mut decode_errors = 0;
-    
-    let mut decoder = encoding_rs::UTF_8.new_decoder();
-    for (idx, token_id) in response_tokens.iter().enumerate() {
-        // Use modern token_to_piece with 4 arguments as required by llama-cpp-2 v0.1.133
-        match model.token_to_piece(*token_id, &mut decoder, false, None) {
-            Ok(token_str) => {
-                response.push_str(&token_str);
-            },
-            Err(e) => {
-                decode_errors += 1;
-                if decode_errors <= 10 {
-                    info!("⏭️ Token {}: decode failed: {:?}", idx, e);
+/// Hand a turn to the conversation's context worker, spawning it (and
+/// evicting the least recently used one) when needed
+fn dispatch_conversation(
+    app: &AppHandle,
+    state: &Arc<Mutex<GgufState>>,
+    loaded: Arc<LoadedModel>,
+    backend: Arc<LlamaBackend>,
+    conversation_id: &str,
+    job: ConversationJob,
+) -> Result<(), String> {
+    let session_dir = session_dir(app)?;
+    let mut guard = state.lock().map_err(|e| e.to_string())?;
+    let key = (loaded.model_path.clone(), conversation_id.to_string());
+
+    if !guard.conversations.contains_key(&key) {
+        let limit = guard.max_cached_contexts;
+        evict_contexts(&mut guard, limit.saturating_sub(1));
+    }
+    guard.conversation_clock += 1;
+    let clock = guard.conversation_clock;
+
+    let mut message = WorkerMessage::Generate(job);
+    // A second attempt covers a worker that died since its last turn
+    for _ in 0..2 {
+        let worker = guard.conversations.entry(key.clone()).or_insert_with(|| {
+            info!("🧠 New context for conversation {}", key.1);
+            let path = session_dir.join(session_file_name(&key.0, &key.1));
+            ConversationWorker::spawn(loaded.clone(), backend.clone(), path)
+        });
+        worker.last_used = clock;
+        match worker.sender.send(message) {
+            Ok(()) => return Ok(()),
+            Err(std::sync::mpsc::SendError(returned)) => {
+                warn!("⚠️ Conversation worker kapanmış, yeniden başlatılıyor: {}", key.1);
+                guard.conversations.remove(&key);
+                message = returned;
+            }
+        }
+    }
+    Err(format!("Konuşma context'i başlatılamadı: {}", conversation_id))
+}
+
+/// Evict least recently used contexts until at most `keep` remain, saving each to disk
+fn evict_contexts(state: &mut GgufState, keep: usize) {
+    while state.conversations.len() > keep {
+        let Some(oldest) = state.conversations.iter()
+            .min_by_key(|(_, worker)| worker.last_used)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+        if let Some(worker) = state.conversations.remove(&oldest) {
+            info!("💾 Evicting context of conversation {} -> {}", oldest.1, worker.session_path.display());
+            worker.evict(true);
+        }
+    }
+}
+
+fn session_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_dir.join("kv_sessions"))
+}
+
+/// Queue a generation on its model and stream it once a slot is free.
+///
+/// Emits `generation-status` for every state change, `stream-start`, one
+/// `stream-token` per piece and `stream-complete`. A request cancelled while
+/// still queued returns an empty `Cancelled` output without decoding anything.
+pub async fn stream_generation(
+    app: &AppHandle,
+    state: &Arc<Mutex<GgufState>>,
+    request: GenerationRequest,
+) -> Result<GenerationOutput, String> {
+    let request_id = request.request_id
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    // 🆕 Get model and backend from pool with minimum lock time
+    let (loaded_model, backend, admission) = {
+        let mut guard = match state.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if guard.generations.contains_key(&request_id) {
+            return Err(format!("Bu istek zaten çalışıyor: {}", request_id));
+        }
+        let model_path = match request.model_path {
+            Some(path) => path,
+            None => guard.models.keys().next().cloned()
+                .ok_or_else(|| "Yüklü model yok".to_string())?,
+        };
+        let model = guard.models.get(&model_path)
+            .cloned() // Arc cloing is cheap
+            .ok_or_else(|| format!("Model havuzda bulunamadı: {}", model_path))?;
+        let backend = guard.backend.as_ref()
+            .cloned()
+            .ok_or_else(|| "Backend not initialized".to_string())?;
+
+        let limit = guard.default_concurrency;
+        let admission = guard.queues.entry(model_path.clone())
+            .or_insert_with(|| GenerationQueue::new(limit))
+            .admit(&request_id);
+        guard.generations.insert(request_id.clone(), ActiveGeneration {
+            model_path,
+            cancel: cancel.clone(),
+        });
+        (model, backend, admission)
+    };
+    let model_path = loaded_model.model_path.clone();
+
+    if let Admission::Wait { position, ready } = admission {
+        info!("⏳ Generation {} queued at position {}", request_id, position);
+        emit_status(app, &request_id, &model_path, GenerationStatus::Queued, json!({ "position": position }));
+
+        // An error means cancel_generation removed it from the queue before it got a slot
+        let admitted = ready.await.is_ok();
+        if !admitted || cancel.load(Ordering::Relaxed) {
+            if let Ok(mut guard) = state.lock() {
+                guard.generations.remove(&request_id);
+                if admitted {
+                    if let Some(queue) = guard.queues.get_mut(&model_path) {
+                        queue.finish();
+                    }
                 }
             }
+            emit_status(app, &request_id, &model_path, GenerationStatus::Cancelled, json!({}));
+            return Ok(GenerationOutput {
+                text: String::new(),
+                tokens: 0,
+                prompt_tokens: 0,
+                reused_tokens: 0,
+                stop_reason: StopReason::Cancelled,
+            });
         }
     }
-    
-    info!("✅ Decoded: {} characters from {} tokens ({} decode errors)", response.len(), total_tokens, decode_errors);
-    
+
+    info!("📦 Using model from pool: {}", model_path);
+    info!("⚙️ Max tokens: {}, Sampling: {:?}", request.max_tokens, request.params);
+    emit_status(app, &request_id, &model_path, GenerationStatus::Running, json!({}));
+
+    if let Err(e) = app.emit("stream-start", json!({ "request_id": request_id })) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+
+    let emitter = app.clone();
+    let token_request_id = request_id.clone();
+    let on_piece = move |piece: &str| {
+        let token = StreamToken {
+            token: piece.to_string(),
+            is_complete: false,
+            stop_reason: None,
+            request_id: Some(token_request_id.clone()),
+        };
+        if let Err(e) = emitter.emit("stream-token", token) {
+            error!("❌ Event emit hatası: {}", e);
+        }
+    };
+
+    let result = match request.conversation_id {
+        Some(conversation_id) => {
+            let (reply, receiver) = tokio::sync::oneshot::channel();
+            let job = ConversationJob {
+                prompt: request.prompt,
+                max_tokens: request.max_tokens,
+                params: request.params,
+                cancel: cancel.clone(),
+                on_piece: Box::new(on_piece),
+                reply,
+            };
+            match dispatch_conversation(app, state, loaded_model, backend, &conversation_id, job) {
+                Ok(()) => receiver.await
+                    .map_err(|_| "Konuşma context'i beklenmedik şekilde kapandı".to_string())
+                    .and_then(|r| r),
+                Err(e) => Err(e),
+            }
+        }
+        None => {
+            let worker_cancel = cancel.clone();
+            let (prompt, max_tokens, params) = (request.prompt, request.max_tokens, request.params);
+            tokio::task::spawn_blocking(move || {
+                let mut sampler = Sampler::new(params);
+                generate(&loaded_model, &backend, &prompt, max_tokens, &mut sampler, &worker_cancel, on_piece)
+            })
+            .await
+            .map_err(|e| format!("Üretim görevi başarısız: {}", e))
+            .and_then(|r| r)
+        }
+    };
+
+    // Free the slot before reporting so the next request starts right away
+    if let Ok(mut guard) = state.lock() {
+        guard.generations.remove(&request_id);
+        if let Some(queue) = guard.queues.get_mut(&model_path) {
+            queue.finish();
+        }
+    }
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e) => {
+            emit_status(app, &request_id, &model_path, GenerationStatus::Failed, json!({ "error": e }));
+            return Err(e);
+        }
+    };
+    info!(
+        "✅ Generation {} finished: {} tokens, {:?} ({}/{} prompt tokens reused)",
+        request_id, output.tokens, output.stop_reason, output.reused_tokens, output.prompt_tokens
+    );
+
+    let final_token = StreamToken {
+        token: String::new(),
+        is_complete: true,
+        stop_reason: Some(output.stop_reason),
+        request_id: Some(request_id.clone()),
+    };
+    if let Err(e) = app.emit("stream-token", final_token) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+    if let Err(e) = app.emit("stream-complete", json!({ "request_id": request_id, "text": output.text })) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+
+    let status = if output.stop_reason == StopReason::Cancelled {
+        GenerationStatus::Cancelled
+    } else {
+        GenerationStatus::Done
+    };
+    emit_status(app, &request_id, &model_path, status, json!({
+        "stop_reason": output.stop_reason,
+        "tokens": output.tokens,
+        "prompt_tokens": output.prompt_tokens,
+        "reused_tokens": output.reused_tokens,
+    }));
+
+    Ok(output)
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn chat_with_gguf_model(
+    app: AppHandle,
+    state: State<'_, Arc<Mutex<GgufState>>>,
+    model_path: String, // 🆕 Model path required
+    prompt: String,
+    max_tokens: u32,
+    temperature: f32,
+    sampling: Option<SamplingParams>,
+    request_id: Option<String>,
+    conversation_id: Option<String>,
+) -> Result<String, String> {
+    info!("🔵 Starting inference...");
+    info!("📝 Prompt length: {} chars", prompt.len());
+
+    let request = GenerationRequest {
+        request_id,
+        model_path: Some(model_path),
+        prompt,
+        max_tokens,
+        params: SamplingParams {
+            temperature,
+            ..sampling.unwrap_or_default()
+        },
+        conversation_id,
+    };
+    let output = stream_generation(&app, state.inner(), request).await?;
+
     // Clean up response (remove special tokens if any)
-    let cleaned_response = response
+    let cleaned_response = output.text
         .replace("<|im_start|>", "")
         .replace("<|im_end|>", "")
         .replace("<|endoftext|>", "")
@@ -477,23 +992,124 @@ pub async fn chat_with_gguf_model(
         .replace("<|assistant|>", "")
         .trim()
         .to_string();
-    
+
     info!("📤 Final response length: {} characters", cleaned_response.len());
-    if cleaned_response.len() > 0 {
-        let preview_len = cleaned_response.len().min(200);
-        info!("📤 Response preview: {}", &cleaned_response[..preview_len]);
-    }
 
     Ok(cleaned_response)
 }
 
+/// Stop a generation at its next token, or drop it from the queue if it has not started
+#[tauri::command]
+pub fn cancel_generation(
+    state: State<'_, Arc<Mutex<GgufState>>>,
+    request_id: String,
+) -> Result<bool, String> {
+    let mut guard = state.lock().map_err(|e| e.to_string())?;
+    let Some(model_path) = guard.generations.get(&request_id).map(|g| {
+        g.cancel.store(true, Ordering::Relaxed);
+        g.model_path.clone()
+    }) else {
+        return Ok(false);
+    };
+    if let Some(queue) = guard.queues.get_mut(&model_path) {
+        queue.remove_waiting(&request_id);
+    }
+    info!("⏹️ Cancel requested for generation {}", request_id);
+    Ok(true)
+}
+
+/// Set how many generations may run at once on a model (default for new models when no path is given)
+#[tauri::command]
+pub fn set_generation_concurrency(
+    state: State<'_, Arc<Mutex<GgufState>>>,
+    model_path: Option<String>,
+    limit: usize,
+) -> Result<(), String> {
+    if limit == 0 {
+        return Err("Eşzamanlılık en az 1 olmalı".to_string());
+    }
+    let mut guard = state.lock().map_err(|e| e.to_string())?;
+    match model_path {
+        Some(path) => guard.queues.entry(path)
+            .or_insert_with(|| GenerationQueue::new(limit))
+            .set_limit(limit),
+        None => guard.default_concurrency = limit,
+    }
+    Ok(())
+}
+
+/// Drop a conversation's cached context and its session file
+#[tauri::command]
+pub fn forget_conversation(
+    app: AppHandle,
+    state: State<'_, Arc<Mutex<GgufState>>>,
+    model_path: String,
+    conversation_id: String,
+) -> Result<(), String> {
+    let key = (model_path, conversation_id);
+    if let Some(worker) = state.lock().map_err(|e| e.to_string())?.conversations.remove(&key) {
+        worker.evict(false);
+    }
+    let path = session_dir(&app)?.join(session_file_name(&key.0, &key.1));
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Session dosyası silinemedi: {}", e))?;
+    }
+    Ok(())
+}
+
+/// How many conversation contexts stay in memory before the oldest is saved to disk
+#[tauri::command]
+pub fn set_context_cache_limit(
+    state: State<'_, Arc<Mutex<GgufState>>>,
+    limit: usize,
+) -> Result<(), String> {
+    let mut guard = state.lock().map_err(|e| e.to_string())?;
+    guard.max_cached_contexts = limit;
+    evict_contexts(&mut guard, limit);
+    Ok(())
+}
+
+/// Running and waiting generations per model
+#[tauri::command]
+pub fn get_generation_queue(
+    state: State<'_, Arc<Mutex<GgufState>>>,
+) -> Result<serde_json::Value, String> {
+    let guard = state.lock().map_err(|e| e.to_string())?;
+    let queues: serde_json::Map<String, serde_json::Value> = guard.queues.iter()
+        .map(|(path, queue)| (path.clone(), json!({
+            "limit": queue.limit(),
+            "running": queue.running(),
+            "waiting": queue.waiting(),
+        })))
+        .collect();
+    Ok(json!({
+        "default_concurrency": guard.default_concurrency,
+        "queues": queues,
+    }))
+}
+
 #[tauri::command]
 pub async fn unload_gguf_model(
     state: State<'_, Arc<Mutex<GgufState>>>,
+    app: AppHandle,
 ) -> Result<String, String> {
     info!("🔵 Unloading GGUF model - Starting cleanup...");
+
+    // A local embedder would fail on every call once its model is gone
+    if let Some(db) = app.try_state::<crate::vector_db::VectorDB>() {
+        if matches!(db.embedding_source(), crate::vector_db::EmbeddingSource::Local { .. }) {
+            db.set_embedding_source(crate::vector_db::EmbeddingSource::Hashed);
+        }
+    }
     
     let mut state_guard = state.lock().unwrap();
+
+    // Running generations keep their model alive; stop them at the next token
+    for generation in state_guard.generations.values() {
+        generation.cancel.store(true, Ordering::Relaxed);
+    }
+    // Cached conversation contexts go to disk so a reload can pick them up again
+    evict_contexts(&mut state_guard, 0);
     
     state_guard.models.clear();
     state_guard.backend = None;
@@ -773,13 +1389,16 @@ fn read_gguf_value(file: &mut std::fs::File, vtype: u32) -> Result<serde_json::V
 
 // 🆕 Vision AI Support - Chat with images
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn chat_with_gguf_vision(
+    app: AppHandle,
     state: State<'_, Arc<Mutex<GgufState>>>,
     model_path: String, // 🆕 Model path required
     prompt: String,
     images: Vec<String>, // Base64 encoded images
     max_tokens: u32,
     temperature: f32,
+    request_id: Option<String>,
 ) -> Result<String, String> {
     info!("📷 Starting vision inference...");
     
@@ -841,7 +1460,7 @@ pub async fn chat_with_gguf_vision(
     );
     
     // Use the existing text chat function
-    chat_with_gguf_model(state, model_path, vision_prompt, max_tokens, temperature).await
+    chat_with_gguf_model(app, state, model_path, vision_prompt, max_tokens, temperature, None, request_id, None).await
 }
 
 // Check if CUDA is available
@@ -880,7 +1499,7 @@ pub fn check_cuda_support() -> Result<serde_json::Value, String> {
 /// Split GGUF dosyalarini tespit edip ilk parcaya yonlendirir.
 /// Ornek: "model-00003-of-00004.gguf" -> "model-00001-of-00004.gguf"
 /// Tek parca dosyalarda ayni yolu dondurur.
-fn resolve_split_gguf_path(path: &str) -> String {
+pub(crate) fn resolve_split_gguf_path(path: &str) -> String {
     let re = regex::Regex::new(r"(-\d{5})-of-(\d{5})\.gguf$").ok();
     if let Some(re) = re {
         if let Some(caps) = re.captures(path) {
@@ -910,6 +1529,25 @@ fn resolve_split_gguf_path(path: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_reusable_prefix_keeps_the_last_prompt_token_for_logits() {
+        assert_eq!(reusable_prefix(&[1, 2, 3, 4], &[1, 2, 3, 9, 10]), 3);
+        assert_eq!(reusable_prefix(&[1, 2, 3], &[1, 2, 3, 4]), 3);
+        // Identical prompt: the final token is decoded again
+        assert_eq!(reusable_prefix(&[1, 2, 3], &[1, 2, 3]), 2);
+        assert_eq!(reusable_prefix(&[5, 6], &[1, 2]), 0);
+        assert_eq!(reusable_prefix::<i32>(&[], &[]), 0);
+    }
+
+    #[test]
+    fn test_session_files_are_per_model_and_conversation() {
+        let a = session_file_name("/models/a.gguf", "chat-1");
+        assert_eq!(a, session_file_name("/models/a.gguf", "chat-1"));
+        assert_ne!(a, session_file_name("/models/b.gguf", "chat-1"));
+        assert_ne!(a, session_file_name("/models/a.gguf", "chat-2"));
+        assert!(a.ends_with(".session"));
+    }
+
     #[test]
     fn test_resolve_split_gguf_path() {
         assert_eq!(resolve_split_gguf_path("test.gguf"), "test.gguf");
@@ -0,0 +1,438 @@
+// src-tauri/src/launch_config.rs
+// Debug configurations from `.corex/launch.json`: variable substitution and preLaunch tasks
+
+use crate::debug::{start_debug_session, DebugAdapterConfig, DebugConfig, DebugState};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter, Runtime, State};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+/// One entry of `configurations` in `.corex/launch.json`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LaunchConfiguration {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub request: String,
+    /// Label of a task in `.corex/tasks.json` to run first
+    #[serde(rename = "preLaunchTask", default, skip_serializing_if = "Option::is_none")]
+    pub pre_launch_task: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adapter: Option<DebugAdapterConfig>,
+    /// Everything else (`program`, `args`, `cwd`, ...) goes to the adapter untouched
+    #[serde(flatten)]
+    pub properties: Map<String, Value>,
+}
+
+impl LaunchConfiguration {
+    pub fn into_debug_config(self) -> DebugConfig {
+        DebugConfig {
+            name: self.name,
+            request: self.request,
+            type_: self.type_,
+            additional_props: Some(Value::Object(self.properties)),
+            adapter: self.adapter,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct LaunchFile {
+    #[serde(default)]
+    configurations: Vec<LaunchConfiguration>,
+}
+
+/// A build step from `.corex/tasks.json`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LaunchTask {
+    pub label: String,
+    /// Program to run; with no `args` it is split on whitespace ("cargo build")
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct TasksFile {
+    #[serde(default)]
+    tasks: Vec<LaunchTask>,
+}
+
+/// Values for `${...}` variables
+pub struct SubstitutionContext {
+    pub workspace: PathBuf,
+    pub file: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+}
+
+impl SubstitutionContext {
+    pub fn new(workspace: &Path, file: Option<&str>) -> Self {
+        Self {
+            workspace: workspace.to_path_buf(),
+            file: file.map(PathBuf::from),
+            env: std::env::vars().collect(),
+        }
+    }
+
+    fn resolve(&self, variable: &str) -> Result<String, String> {
+        if let Some(name) = variable.strip_prefix("env:") {
+            // Like VS Code, an unset variable becomes empty
+            return Ok(self.env.get(name).cloned().unwrap_or_default());
+        }
+
+        let lossy = |path: &Path| path.to_string_lossy().to_string();
+        match variable {
+            "workspaceFolder" | "workspaceRoot" | "cwd" => return Ok(lossy(&self.workspace)),
+            "workspaceFolderBasename" => {
+                return Ok(self.workspace.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default())
+            }
+            "pathSeparator" | "/" => return Ok(std::path::MAIN_SEPARATOR.to_string()),
+            _ => {}
+        }
+
+        let file = self.file.as_deref()
+            .ok_or_else(|| format!("${{{}}} için açık bir dosya gerekli", variable))?;
+        let value = match variable {
+            "file" => lossy(file),
+            "fileBasename" => file.file_name().map(lossy_os).unwrap_or_default(),
+            "fileBasenameNoExtension" => file.file_stem().map(lossy_os).unwrap_or_default(),
+            "fileExtname" => file.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default(),
+            "fileDirname" => file.parent().map(lossy).unwrap_or_default(),
+            "relativeFile" => lossy(file.strip_prefix(&self.workspace).unwrap_or(file)),
+            "relativeFileDirname" => {
+                let relative = file.strip_prefix(&self.workspace).unwrap_or(file);
+                relative.parent().map(lossy).unwrap_or_default()
+            }
+            _ => return Err(format!("Bilinmeyen değişken: ${{{}}}", variable)),
+        };
+        Ok(value)
+    }
+
+    /// Replace every `${...}` in a string
+    pub fn substitute(&self, text: &str) -> Result<String, String> {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after.find('}')
+                .ok_or_else(|| format!("Kapanmamış değişken: {}", &rest[start..]))?;
+            result.push_str(&self.resolve(&after[..end])?);
+            rest = &after[end + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    /// Substitute every string inside a JSON value
+    pub fn substitute_value(&self, value: &Value) -> Result<Value, String> {
+        Ok(match value {
+            Value::String(s) => Value::String(self.substitute(s)?),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.substitute_value(v)).collect::<Result<_, _>>()?),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| Ok((k.clone(), self.substitute_value(v)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+            other => other.clone(),
+        })
+    }
+}
+
+fn lossy_os(name: &std::ffi::OsStr) -> String {
+    name.to_string_lossy().to_string()
+}
+
+/// Drop `//` and `/* */` comments and trailing commas, which VS Code-style JSON files allow
+pub fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        out.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    // Keep line numbers stable for serde's error messages
+                    if next == '\n' {
+                        out.push('\n');
+                    }
+                    previous = next;
+                }
+            }
+            (',', _) => {
+                let trailing = chars.clone()
+                    .find(|next| !next.is_whitespace())
+                    .map(|next| next == '}' || next == ']')
+                    .unwrap_or(false);
+                if !trailing {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn corex_file(workspace: &Path, name: &str) -> PathBuf {
+    workspace.join(".corex").join(name)
+}
+
+/// Configurations from `.corex/launch.json`, as written (variables unexpanded)
+pub fn load_launch_configurations(workspace: &Path) -> Result<Vec<LaunchConfiguration>, String> {
+    let file = corex_file(workspace, "launch.json");
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| format!("launch.json okunamadı: {}", e))?;
+    let launch: LaunchFile = serde_json::from_str(&strip_jsonc(&content))
+        .map_err(|e| format!("launch.json geçersiz: {}", e))?;
+    Ok(launch.configurations)
+}
+
+fn load_task(workspace: &Path, label: &str) -> Result<LaunchTask, String> {
+    let file = corex_file(workspace, "tasks.json");
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| format!("tasks.json okunamadı ({}): {}", file.display(), e))?;
+    let tasks: TasksFile = serde_json::from_str(&strip_jsonc(&content))
+        .map_err(|e| format!("tasks.json geçersiz: {}", e))?;
+    tasks.tasks.into_iter()
+        .find(|task| task.label == label)
+        .ok_or_else(|| format!("preLaunchTask bulunamadı: {}", label))
+}
+
+/// Expand variables in a configuration and everything it passes to the adapter
+pub fn resolve_configuration(config: &LaunchConfiguration, context: &SubstitutionContext) -> Result<LaunchConfiguration, String> {
+    let mut resolved = config.clone();
+    resolved.properties = match context.substitute_value(&Value::Object(config.properties.clone()))? {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    if let Some(adapter) = &mut resolved.adapter {
+        adapter.command = context.substitute(&adapter.command)?;
+        adapter.args = adapter.args.iter().map(|a| context.substitute(a)).collect::<Result<_, _>>()?;
+    }
+    Ok(resolved)
+}
+
+fn resolve_task(task: &LaunchTask, context: &SubstitutionContext) -> Result<LaunchTask, String> {
+    Ok(LaunchTask {
+        label: task.label.clone(),
+        command: context.substitute(&task.command)?,
+        args: task.args.iter().map(|a| context.substitute(a)).collect::<Result<_, _>>()?,
+        cwd: task.cwd.as_deref().map(|cwd| context.substitute(cwd)).transpose()?,
+        env: task.env.iter()
+            .map(|(k, v)| Ok((k.clone(), context.substitute(v)?)))
+            .collect::<Result<_, String>>()?,
+    })
+}
+
+/// Program and arguments of a task; without explicit `args` the command line is split on whitespace
+fn task_command_line(task: &LaunchTask) -> Result<(String, Vec<String>), String> {
+    if !task.args.is_empty() {
+        return Ok((task.command.clone(), task.args.clone()));
+    }
+    let mut parts = task.command.split_whitespace().map(str::to_string);
+    let program = parts.next().ok_or_else(|| format!("Task komutu boş: {}", task.label))?;
+    Ok((program, parts.collect()))
+}
+
+/// Run a preLaunch task to completion, streaming its output as `debug-task-output` events
+async fn run_task<R: Runtime>(app: &AppHandle<R>, task: &LaunchTask, workspace: &Path) -> Result<(), String> {
+    let (program, args) = task_command_line(task)?;
+    info!("🔨 preLaunchTask çalıştırılıyor: {} ({} {})", task.label, program, args.join(" "));
+
+    let mut child = Command::new(&program)
+        .args(&args)
+        .current_dir(task.cwd.as_deref().map(PathBuf::from).unwrap_or_else(|| workspace.to_path_buf()))
+        .envs(&task.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("preLaunchTask başlatılamadı ({}): {}", program, e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to open stderr")?;
+    let forward = |stream: &'static str, reader: Box<dyn tokio::io::AsyncRead + Unpin + Send>| {
+        let app = app.clone();
+        let label = task.label.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Err(e) = app.emit("debug-task-output", json!({
+                    "task": label,
+                    "stream": stream,
+                    "output": line
+                })) {
+                    error!("❌ Event emit hatası: {}", e);
+                }
+            }
+        })
+    };
+    let stdout_task = forward("stdout", Box::new(stdout));
+    let stderr_task = forward("stderr", Box::new(stderr));
+
+    let status = child.wait().await.map_err(|e| format!("preLaunchTask beklenemedi: {}", e))?;
+    let _ = stdout_task.await;
+    let _ = stderr_task.await;
+
+    if !status.success() {
+        return Err(format!("preLaunchTask '{}' başarısız oldu (çıkış kodu {:?})", task.label, status.code()));
+    }
+    info!("✅ preLaunchTask tamamlandı: {}", task.label);
+    Ok(())
+}
+
+/// Debug configurations declared in the workspace's `.corex/launch.json`
+#[tauri::command]
+pub async fn list_debug_configurations(workspace: String) -> Result<Vec<LaunchConfiguration>, String> {
+    load_launch_configurations(Path::new(&workspace))
+}
+
+/// Start the named configuration: expand variables, run its preLaunchTask, then launch the adapter
+///
+/// `current_file` backs `${file}` and friends.
+#[tauri::command]
+pub async fn start_debug_configuration<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DebugState>,
+    workspace: String,
+    name: String,
+    current_file: Option<String>,
+) -> Result<String, String> {
+    let workspace_path = PathBuf::from(&workspace);
+    let config = load_launch_configurations(&workspace_path)?
+        .into_iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| format!("Debug yapılandırması bulunamadı: {}", name))?;
+
+    let context = SubstitutionContext::new(&workspace_path, current_file.as_deref());
+    let resolved = resolve_configuration(&config, &context)?;
+
+    if let Some(label) = &resolved.pre_launch_task {
+        let task = resolve_task(&load_task(&workspace_path, label)?, &context)?;
+        run_task(&app, &task, &workspace_path).await?;
+    }
+
+    start_debug_session(app, state, workspace, resolved.into_debug_config()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> SubstitutionContext {
+        SubstitutionContext {
+            workspace: PathBuf::from("/home/dev/app"),
+            file: Some(PathBuf::from("/home/dev/app/src/main.py")),
+            env: HashMap::from([("PORT".to_string(), "8080".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_substitutes_variables() {
+        let context = context();
+        assert_eq!(context.substitute("${workspaceFolder}/out").unwrap(), "/home/dev/app/out");
+        assert_eq!(
+            context.substitute("${relativeFile}:${fileBasenameNoExtension}${fileExtname}").unwrap(),
+            "src/main.py:main.py"
+        );
+        assert_eq!(context.substitute("--port=${env:PORT}${env:MISSING}").unwrap(), "--port=8080");
+        assert!(context.substitute("${unknown}").is_err());
+        assert!(context.substitute("${file").is_err());
+
+        let no_file = SubstitutionContext { file: None, ..context };
+        assert!(no_file.substitute("${file}").unwrap_err().contains("açık bir dosya"));
+        assert_eq!(no_file.substitute("${workspaceFolderBasename}").unwrap(), "app");
+    }
+
+    #[test]
+    fn test_parses_jsonc_launch_file() {
+        let content = r#"{
+            // Python
+            "version": "0.2.0",
+            "configurations": [
+                {
+                    "name": "Run current file", /* inline */
+                    "type": "python",
+                    "request": "launch",
+                    "program": "${file}",
+                    "args": ["--url", "http://localhost"],
+                    "preLaunchTask": "build",
+                },
+            ]
+        }"#;
+        let launch: LaunchFile = serde_json::from_str(&strip_jsonc(content)).unwrap();
+        let config = &launch.configurations[0];
+        assert_eq!(config.type_, "python");
+        assert_eq!(config.pre_launch_task.as_deref(), Some("build"));
+        // `//` inside a string is not a comment
+        assert_eq!(config.properties["args"], json!(["--url", "http://localhost"]));
+
+        let resolved = resolve_configuration(config, &context()).unwrap();
+        assert_eq!(resolved.properties["program"], "/home/dev/app/src/main.py");
+
+        let debug_config = resolved.into_debug_config();
+        assert_eq!(debug_config.additional_props.unwrap()["program"], "/home/dev/app/src/main.py");
+    }
+
+    #[test]
+    fn test_task_command_line() {
+        let task = LaunchTask {
+            label: "build".to_string(),
+            command: "cargo build --bin ${workspaceFolderBasename}".to_string(),
+            args: Vec::new(),
+            cwd: Some("${workspaceFolder}/crates".to_string()),
+            env: HashMap::new(),
+        };
+        let resolved = resolve_task(&task, &context()).unwrap();
+        assert_eq!(resolved.cwd.as_deref(), Some("/home/dev/app/crates"));
+        assert_eq!(
+            task_command_line(&resolved).unwrap(),
+            ("cargo".to_string(), vec!["build".to_string(), "--bin".to_string(), "app".to_string()])
+        );
+    }
+}
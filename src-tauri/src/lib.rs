@@ -3,12 +3,19 @@
 // This is the library entry point for Tauri 2.x
 // The main.rs file will call run() from here
 
+pub mod code_chunker;
 pub mod collab;
 pub mod commands;
+pub mod compose;
 pub mod debug;
+pub mod dependency_graph;
+pub mod devcontainer;
 pub mod docker;
+pub mod generation_queue;
 pub mod gguf;
 pub mod git_commands;
+pub mod launch_config;
+pub mod lsp;
 pub mod mcp;
 pub mod oauth;
 pub mod oauth_backend;
@@ -16,11 +23,14 @@ pub mod p2p;
 pub mod process_monitor;
 pub mod rag_pipeline;
 pub mod remote;
+pub mod sampling;
 pub mod streaming;
+pub mod symbol_index;
 pub mod testing;
 pub mod tree_sitter_parser;
 pub mod vector_db;
 pub mod window_manager;
+pub mod workspace_indexer;
 
 pub mod main_module {
     pub use crate::commands::*;
@@ -0,0 +1,807 @@
+// src-tauri/src/lsp.rs
+// Language Server Protocol client: stdio servers per workspace/language, results forwarded as events
+
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+/// Crashes allowed before a server is given up on
+const MAX_RESTARTS: u32 = 5;
+
+/// Delay before the first restart; later restarts wait proportionally longer
+const RESTART_BACKOFF: Duration = Duration::from_secs(1);
+
+/// A server that stayed up this long gets its restart budget back
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LspServerConfig {
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: Option<HashMap<String, String>>,
+}
+
+/// Known servers for the languages we support out of the box
+pub fn default_server_config(language: &str) -> Option<LspServerConfig> {
+    let (command, args): (&str, &[&str]) = match language {
+        "rust" => ("rust-analyzer", &[]),
+        "python" => ("pyright-langserver", &["--stdio"]),
+        "go" => ("gopls", &[]),
+        "typescript" | "typescriptreact" | "javascript" | "javascriptreact" => {
+            ("typescript-language-server", &["--stdio"])
+        }
+        _ => return None,
+    };
+    Some(LspServerConfig {
+        command: command.to_string(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+        env: None,
+    })
+}
+
+/// LSP `languageId` for a file, from its extension
+pub fn language_id_for_path(file_path: &str) -> &'static str {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match extension.as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "go" => "go",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        _ => "plaintext",
+    }
+}
+
+struct PendingRequest {
+    method: String,
+    file_path: Option<String>,
+}
+
+struct OpenDocument {
+    language_id: String,
+    version: i32,
+    text: String,
+}
+
+pub struct LspServerInstance {
+    pub config: LspServerConfig,
+    pub workspace: String,
+    pub language: String,
+    child: Option<Child>,
+    /// The child's stdin, behind its own lock so a long write does not hold the instance
+    stdin: Option<Arc<Mutex<ChildStdin>>>,
+    /// Bumped on every spawn so a stale reader thread can tell it was superseded
+    generation: u64,
+    next_id: i64,
+    pending: HashMap<i64, PendingRequest>,
+    initialized: bool,
+    /// Messages sent before the `initialize` handshake finished
+    queued: Vec<Value>,
+    /// Open documents by URI, re-sent to a restarted server
+    documents: HashMap<String, OpenDocument>,
+    restarts: u32,
+    started_at: Instant,
+    stopping: bool,
+}
+
+impl LspServerInstance {
+    fn new(workspace: String, language: String, config: LspServerConfig) -> Self {
+        Self {
+            config,
+            workspace,
+            language,
+            child: None,
+            stdin: None,
+            generation: 0,
+            next_id: 1,
+            pending: HashMap::new(),
+            initialized: false,
+            queued: Vec::new(),
+            documents: HashMap::new(),
+            restarts: 0,
+            started_at: Instant::now(),
+            stopping: false,
+        }
+    }
+
+    fn write(&mut self, message: &Value) -> Result<(), String> {
+        let stdin = self.stdin.as_ref().ok_or("LSP sunucusu çalışmıyor")?;
+        let mut stdin = stdin.lock().map_err(|e| e.to_string())?;
+        write_message(&mut stdin, message)
+    }
+
+    /// Write now, or hold until the server has answered `initialize`
+    fn send(&mut self, message: Value) -> Result<(), String> {
+        if self.initialized {
+            self.write(&message)
+        } else {
+            self.queued.push(message);
+            Ok(())
+        }
+    }
+
+    fn request(&mut self, method: &str, params: Value, file_path: Option<String>) -> Result<i64, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.insert(id, PendingRequest { method: method.to_string(), file_path });
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+        Ok(id)
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), String> {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+}
+
+/// Running servers keyed by `server_key(workspace, language)`
+#[derive(Default)]
+pub struct LspState {
+    pub instances: Mutex<HashMap<String, Arc<Mutex<LspServerInstance>>>>,
+}
+
+impl LspState {
+    fn get(&self, workspace: &str, language: &str) -> Result<Arc<Mutex<LspServerInstance>>, String> {
+        let instances = self.instances.lock().map_err(|e| e.to_string())?;
+        instances.get(&server_key(workspace, language))
+            .cloned()
+            .ok_or_else(|| format!("LSP sunucusu bulunamadı: {} ({})", language, workspace))
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LspServerStatus {
+    pub workspace: String,
+    pub language: String,
+    pub command: String,
+    pub running: bool,
+    pub initialized: bool,
+    pub restarts: u32,
+}
+
+fn server_key(workspace: &str, language: &str) -> String {
+    format!("{}::{}", workspace, language)
+}
+
+/// Frame a JSON-RPC message with its `Content-Length` header
+pub fn encode_message(message: &Value) -> Vec<u8> {
+    let body = message.to_string();
+    let mut framed = format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes();
+    framed.extend_from_slice(body.as_bytes());
+    framed
+}
+
+fn write_message(stdin: &mut ChildStdin, message: &Value) -> Result<(), String> {
+    stdin.write_all(&encode_message(message))
+        .and_then(|_| stdin.flush())
+        .map_err(|e| format!("LSP sunucusuna yazılamadı: {}", e))
+}
+
+/// Read one framed message; `None` at end of stream
+pub fn read_message(reader: &mut impl BufRead) -> std::io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or(0)];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn path_to_uri(path: &str) -> Result<String, String> {
+    url::Url::from_file_path(path)
+        .map(|uri| uri.to_string())
+        .map_err(|_| format!("Geçersiz dosya yolu: {}", path))
+}
+
+fn uri_to_path(uri: &str) -> Option<String> {
+    url::Url::parse(uri).ok()?
+        .to_file_path().ok()
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+fn initialize_params(workspace: &str) -> Result<Value, String> {
+    let root_uri = path_to_uri(workspace)?;
+    let name = Path::new(workspace)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| workspace.to_string());
+
+    Ok(json!({
+        "processId": std::process::id(),
+        "clientInfo": { "name": "CorexAI" },
+        "rootUri": root_uri,
+        "workspaceFolders": [{ "uri": root_uri, "name": name }],
+        "capabilities": {
+            "textDocument": {
+                "synchronization": { "dynamicRegistration": false, "didSave": false },
+                "publishDiagnostics": { "relatedInformation": true },
+                "hover": { "contentFormat": ["markdown", "plaintext"] },
+                "completion": {
+                    "completionItem": {
+                        "snippetSupport": false,
+                        "documentationFormat": ["markdown", "plaintext"]
+                    }
+                },
+                "rename": { "prepareSupport": false }
+            },
+            "workspace": {
+                "workspaceFolders": true,
+                "configuration": true,
+                "workspaceEdit": { "documentChanges": false }
+            }
+        }
+    }))
+}
+
+fn emit_status<R: Runtime>(app: &AppHandle<R>, workspace: &str, language: &str, status: &str) {
+    if let Err(e) = app.emit("lsp-server-status", json!({
+        "workspace": workspace,
+        "language": language,
+        "status": status
+    })) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+}
+
+/// Event name for the result of a request we sent
+fn result_event(method: &str) -> Option<&'static str> {
+    match method {
+        "textDocument/hover" => Some("lsp-hover"),
+        "textDocument/completion" => Some("lsp-completion"),
+        "textDocument/rename" => Some("lsp-rename"),
+        _ => None,
+    }
+}
+
+/// Start (or restart) the server process and send `initialize`
+fn spawn_server<R: Runtime>(app: &AppHandle<R>, instance: &Arc<Mutex<LspServerInstance>>) -> Result<(), String> {
+    let mut guard = instance.lock().map_err(|e| e.to_string())?;
+    // Before spawning, so a bad workspace path cannot leave a server running
+    let params = initialize_params(&guard.workspace)?;
+
+    let mut cmd = Command::new(&guard.config.command);
+    cmd.args(&guard.config.args);
+    cmd.current_dir(&guard.workspace);
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    if let Some(env) = &guard.config.env {
+        cmd.envs(env);
+    }
+
+    let mut child = cmd.spawn()
+        .map_err(|e| format!("LSP sunucusu başlatılamadı ({}): {}", guard.config.command, e))?;
+    let (Some(stdin), Some(stdout), Some(stderr)) = (child.stdin.take(), child.stdout.take(), child.stderr.take()) else {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err("LSP sunucusunun stdio akışları açılamadı".to_string());
+    };
+
+    guard.generation += 1;
+    guard.child = Some(child);
+    guard.stdin = Some(Arc::new(Mutex::new(stdin)));
+    guard.initialized = false;
+    guard.stopping = false;
+    guard.started_at = Instant::now();
+
+    // Document state supersedes queued sync notifications; queued requests were failed in handle_exit
+    let reopen: Vec<Value> = guard.documents.iter()
+        .map(|(uri, doc)| json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": doc.language_id,
+                    "version": doc.version,
+                    "text": doc.text
+                }
+            }
+        }))
+        .collect();
+    guard.queued = reopen;
+
+    let id = guard.next_id;
+    guard.next_id += 1;
+    guard.pending.insert(id, PendingRequest { method: "initialize".to_string(), file_path: None });
+    if let Err(e) = guard.write(&json!({ "jsonrpc": "2.0", "id": id, "method": "initialize", "params": params })) {
+        guard.stdin = None;
+        if let Some(mut child) = guard.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        return Err(e);
+    }
+
+    let generation = guard.generation;
+    let label = format!("{} ({})", guard.language, guard.workspace);
+    drop(guard);
+
+    let app_clone = app.clone();
+    let instance_clone = instance.clone();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        loop {
+            match read_message(&mut reader) {
+                Ok(Some(message)) => handle_message(&app_clone, &instance_clone, &message),
+                Ok(None) => break,
+                Err(e) => {
+                    error!("❌ [LSP {}] okuma hatası: {}", label, e);
+                    break;
+                }
+            }
+        }
+        handle_exit(&app_clone, &instance_clone, generation);
+    });
+
+    let instance_clone = instance.clone();
+    std::thread::spawn(move || {
+        let reader = BufReader::new(stderr);
+        for line in reader.lines() {
+            match line {
+                Ok(content) => {
+                    let language = instance_clone.lock().map(|i| i.language.clone()).unwrap_or_default();
+                    info!("[LSP {}] stderr: {}", language, content);
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn handle_message<R: Runtime>(app: &AppHandle<R>, instance: &Arc<Mutex<LspServerInstance>>, message: &Value) {
+    let Ok(mut guard) = instance.lock() else {
+        return;
+    };
+    let workspace = guard.workspace.clone();
+    let language = guard.language.clone();
+
+    match (message.get("id"), message.get("method").and_then(|m| m.as_str())) {
+        // Server -> client request; answer so the server does not block on us
+        (Some(id), Some(method)) => {
+            let result = match method {
+                "workspace/configuration" => {
+                    let items = message["params"]["items"].as_array().map(|i| i.len()).unwrap_or(0);
+                    Value::Array(vec![Value::Null; items])
+                }
+                _ => Value::Null,
+            };
+            if let Err(e) = guard.write(&json!({ "jsonrpc": "2.0", "id": id, "result": result })) {
+                warn!("⚠️ [LSP {}] yanıt gönderilemedi: {}", language, e);
+            }
+        }
+        // Response to one of our requests
+        (Some(id), None) => {
+            let Some(pending) = id.as_i64().and_then(|id| guard.pending.remove(&id)) else {
+                return;
+            };
+
+            if pending.method == "initialize" {
+                let Some(stdin) = guard.stdin.clone() else {
+                    return;
+                };
+                // Hold stdin, not the instance, while replaying: later sends wait for the
+                // replay to finish, so they stay in order without blocking everything else
+                let Ok(mut pipe) = stdin.lock() else {
+                    return;
+                };
+                guard.initialized = true;
+                let mut messages = vec![json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} })];
+                messages.append(&mut guard.queued);
+                drop(guard);
+                for queued in messages {
+                    if let Err(e) = write_message(&mut pipe, &queued) {
+                        warn!("⚠️ [LSP {}] kuyruk gönderilemedi: {}", language, e);
+                    }
+                }
+                drop(pipe);
+                info!("✅ LSP sunucusu hazır: {} ({})", language, workspace);
+                emit_status(app, &workspace, &language, "running");
+                return;
+            }
+
+            if let Some(event) = result_event(&pending.method) {
+                drop(guard);
+                if let Err(e) = app.emit(event, json!({
+                    "workspace": workspace,
+                    "language": language,
+                    "request_id": id,
+                    "file_path": pending.file_path,
+                    "result": message.get("result").cloned().unwrap_or(Value::Null),
+                    "error": message.get("error").cloned()
+                })) {
+                    error!("❌ Event emit hatası: {}", e);
+                }
+            }
+        }
+        // Notification
+        (None, Some(method)) => {
+            drop(guard);
+            let params = &message["params"];
+            match method {
+                "textDocument/publishDiagnostics" => {
+                    let uri = params["uri"].as_str().unwrap_or("");
+                    if let Err(e) = app.emit("lsp-diagnostics", json!({
+                        "workspace": workspace,
+                        "language": language,
+                        "uri": uri,
+                        "file_path": uri_to_path(uri),
+                        "diagnostics": params.get("diagnostics").cloned().unwrap_or(json!([]))
+                    })) {
+                        error!("❌ Event emit hatası: {}", e);
+                    }
+                }
+                "window/logMessage" | "window/showMessage" => {
+                    info!("[LSP {}] {}", language, params["message"].as_str().unwrap_or(""));
+                }
+                _ => {}
+            }
+        }
+        (None, None) => {}
+    }
+}
+
+/// Reap the process and restart it unless it was stopped on purpose
+fn handle_exit<R: Runtime>(app: &AppHandle<R>, instance: &Arc<Mutex<LspServerInstance>>, generation: u64) {
+    let (workspace, language, attempt, failed) = {
+        let Ok(mut guard) = instance.lock() else {
+            return;
+        };
+        if guard.generation != generation {
+            return;
+        }
+
+        guard.stdin = None;
+        let exit_status = guard.child.take().and_then(|mut child| child.wait().ok());
+        guard.initialized = false;
+
+        // Requests the dead process will never answer; queued ones are not re-sent after a restart
+        guard.queued.clear();
+        let workspace = guard.workspace.clone();
+        let language = guard.language.clone();
+        for (id, pending) in guard.pending.drain() {
+            if let Some(event) = result_event(&pending.method) {
+                let _ = app.emit(event, json!({
+                    "workspace": workspace,
+                    "language": language,
+                    "request_id": id,
+                    "file_path": pending.file_path,
+                    "result": Value::Null,
+                    "error": { "message": "LSP sunucusu kapandı" }
+                }));
+            }
+        }
+
+        if guard.stopping {
+            info!("🛑 LSP sunucusu durdu: {} ({})", language, workspace);
+            drop(guard);
+            emit_status(app, &workspace, &language, "stopped");
+            return;
+        }
+
+        error!("❌ LSP sunucusu çöktü: {} ({}) - {:?}", language, workspace, exit_status);
+        if guard.started_at.elapsed() >= STABLE_UPTIME {
+            guard.restarts = 0;
+        }
+        let failed = guard.restarts >= MAX_RESTARTS;
+        if !failed {
+            guard.restarts += 1;
+        }
+        (workspace, language, guard.restarts, failed)
+    };
+
+    if failed {
+        error!("❌ LSP sunucusu {} denemeden sonra bırakıldı: {} ({})", MAX_RESTARTS, language, workspace);
+        emit_status(app, &workspace, &language, "failed");
+        return;
+    }
+
+    emit_status(app, &workspace, &language, "restarting");
+    std::thread::sleep(RESTART_BACKOFF * attempt);
+
+    // stop_lsp_server may have run while we were waiting
+    let superseded = instance.lock()
+        .map(|guard| guard.stopping || guard.generation != generation)
+        .unwrap_or(true);
+    if superseded {
+        return;
+    }
+
+    info!("🔄 LSP sunucusu yeniden başlatılıyor ({}/{}): {} ({})", attempt, MAX_RESTARTS, language, workspace);
+    if let Err(e) = spawn_server(app, instance) {
+        error!("❌ {}", e);
+        emit_status(app, &workspace, &language, "failed");
+    }
+}
+
+/// Start a language server for a workspace; `config` defaults to the known server for `language`
+#[tauri::command]
+pub async fn start_lsp_server<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+    config: Option<LspServerConfig>,
+) -> Result<String, String> {
+    let config = config
+        .or_else(|| default_server_config(&language))
+        .ok_or_else(|| format!("{} için bilinen bir LSP sunucusu yok", language))?;
+    if !Path::new(&workspace).is_dir() {
+        return Err(format!("Klasör bulunamadı: {}", workspace));
+    }
+
+    let key = server_key(&workspace, &language);
+    let instance = {
+        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+        if let Some(existing) = instances.get(&key) {
+            let running = existing.lock().map_err(|e| e.to_string())?.child.is_some();
+            if running {
+                info!("ℹ️ LSP sunucusu zaten çalışıyor: {}", key);
+                return Ok(key);
+            }
+        }
+        let instance = Arc::new(Mutex::new(LspServerInstance::new(workspace.clone(), language.clone(), config)));
+        instances.insert(key.clone(), instance.clone());
+        instance
+    };
+
+    info!("🚀 LSP sunucusu başlatılıyor: {}", key);
+    emit_status(&app, &workspace, &language, "starting");
+    if let Err(e) = spawn_server(&app, &instance) {
+        state.instances.lock().map_err(|e| e.to_string())?.remove(&key);
+        return Err(e);
+    }
+    Ok(key)
+}
+
+#[tauri::command]
+pub async fn stop_lsp_server(
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+) -> Result<String, String> {
+    let key = server_key(&workspace, &language);
+    let instance = state.instances.lock().map_err(|e| e.to_string())?
+        .remove(&key)
+        .ok_or_else(|| format!("LSP sunucusu bulunamadı: {}", key))?;
+
+    let mut guard = instance.lock().map_err(|e| e.to_string())?;
+    guard.stopping = true;
+    if guard.initialized {
+        // Best effort; the server is killed below either way
+        let id = guard.next_id;
+        let _ = guard.write(&json!({ "jsonrpc": "2.0", "id": id, "method": "shutdown" }));
+        let _ = guard.write(&json!({ "jsonrpc": "2.0", "method": "exit" }));
+    }
+    if let Some(child) = guard.child.as_mut() {
+        let _ = child.kill();
+    }
+    Ok(format!("LSP sunucusu durduruldu: {}", key))
+}
+
+#[tauri::command]
+pub async fn list_lsp_servers(state: State<'_, LspState>) -> Result<Vec<LspServerStatus>, String> {
+    let instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let mut statuses = Vec::new();
+    for instance in instances.values() {
+        let guard = instance.lock().map_err(|e| e.to_string())?;
+        statuses.push(LspServerStatus {
+            workspace: guard.workspace.clone(),
+            language: guard.language.clone(),
+            command: guard.config.command.clone(),
+            running: guard.child.is_some(),
+            initialized: guard.initialized,
+            restarts: guard.restarts,
+        });
+    }
+    Ok(statuses)
+}
+
+#[tauri::command]
+pub async fn lsp_did_open(
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+    file_path: String,
+    text: String,
+) -> Result<(), String> {
+    let instance = state.get(&workspace, &language)?;
+    let mut guard = instance.lock().map_err(|e| e.to_string())?;
+    let uri = path_to_uri(&file_path)?;
+    let language_id = language_id_for_path(&file_path).to_string();
+
+    guard.notify("textDocument/didOpen", json!({
+        "textDocument": { "uri": uri, "languageId": language_id, "version": 1, "text": text }
+    }))?;
+    guard.documents.insert(uri, OpenDocument { language_id, version: 1, text });
+    Ok(())
+}
+
+/// Send the full new text of an open document
+#[tauri::command]
+pub async fn lsp_did_change(
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+    file_path: String,
+    text: String,
+) -> Result<(), String> {
+    let instance = state.get(&workspace, &language)?;
+    let mut guard = instance.lock().map_err(|e| e.to_string())?;
+    let uri = path_to_uri(&file_path)?;
+
+    let document = guard.documents.get_mut(&uri)
+        .ok_or_else(|| format!("Belge açık değil: {}", file_path))?;
+    document.version += 1;
+    document.text = text.clone();
+    let version = document.version;
+
+    guard.notify("textDocument/didChange", json!({
+        "textDocument": { "uri": uri, "version": version },
+        "contentChanges": [{ "text": text }]
+    }))
+}
+
+#[tauri::command]
+pub async fn lsp_did_close(
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+    file_path: String,
+) -> Result<(), String> {
+    let instance = state.get(&workspace, &language)?;
+    let mut guard = instance.lock().map_err(|e| e.to_string())?;
+    let uri = path_to_uri(&file_path)?;
+
+    if guard.documents.remove(&uri).is_some() {
+        guard.notify("textDocument/didClose", json!({ "textDocument": { "uri": uri } }))?;
+    }
+    Ok(())
+}
+
+fn position_params(file_path: &str, line: u32, character: u32) -> Result<Value, String> {
+    Ok(json!({
+        "textDocument": { "uri": path_to_uri(file_path)? },
+        "position": { "line": line, "character": character }
+    }))
+}
+
+fn send_request(
+    state: &LspState,
+    workspace: &str,
+    language: &str,
+    method: &str,
+    file_path: &str,
+    params: Value,
+) -> Result<i64, String> {
+    let instance = state.get(workspace, language)?;
+    let mut guard = instance.lock().map_err(|e| e.to_string())?;
+    guard.request(method, params, Some(file_path.to_string()))
+}
+
+/// Request hover info; the result arrives as an `lsp-hover` event with the returned request id
+///
+/// Positions are LSP positions: 0-based line and UTF-16 character offset.
+#[tauri::command]
+pub async fn lsp_hover(
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+    file_path: String,
+    line: u32,
+    character: u32,
+) -> Result<i64, String> {
+    let params = position_params(&file_path, line, character)?;
+    send_request(&state, &workspace, &language, "textDocument/hover", &file_path, params)
+}
+
+/// Request completions; the result arrives as an `lsp-completion` event
+#[tauri::command]
+pub async fn lsp_completion(
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+    file_path: String,
+    line: u32,
+    character: u32,
+) -> Result<i64, String> {
+    let params = position_params(&file_path, line, character)?;
+    send_request(&state, &workspace, &language, "textDocument/completion", &file_path, params)
+}
+
+/// Request a rename; the workspace edit arrives as an `lsp-rename` event
+#[tauri::command]
+pub async fn lsp_rename(
+    state: State<'_, LspState>,
+    workspace: String,
+    language: String,
+    file_path: String,
+    line: u32,
+    character: u32,
+    new_name: String,
+) -> Result<i64, String> {
+    let mut params = position_params(&file_path, line, character)?;
+    params["newName"] = json!(new_name);
+    send_request(&state, &workspace, &language, "textDocument/rename", &file_path, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_message_framing_round_trip() {
+        let first = json!({ "jsonrpc": "2.0", "id": 1, "result": { "contents": "ğüş" } });
+        let second = json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} });
+
+        let mut stream = encode_message(&first);
+        // Extra headers are allowed and ignored
+        stream.extend_from_slice(b"Content-Type: application/vscode-jsonrpc; charset=utf-8\r\n");
+        stream.extend_from_slice(&encode_message(&second));
+
+        let mut reader = Cursor::new(stream);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(first));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(second));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_truncated_body_is_an_error() {
+        let mut reader = Cursor::new(b"Content-Length: 40\r\n\r\n{\"jsonrpc\":".to_vec());
+        assert!(read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_default_servers_and_language_ids() {
+        assert_eq!(default_server_config("rust").unwrap().command, "rust-analyzer");
+        assert_eq!(default_server_config("python").unwrap().args, vec!["--stdio"]);
+        assert_eq!(default_server_config("typescriptreact").unwrap().command, "typescript-language-server");
+        assert!(default_server_config("cobol").is_none());
+
+        assert_eq!(language_id_for_path("src/App.TSX"), "typescriptreact");
+        assert_eq!(language_id_for_path("main.go"), "go");
+        assert_eq!(language_id_for_path("README"), "plaintext");
+    }
+
+    #[test]
+    fn test_uri_round_trip() {
+        let uri = path_to_uri("/home/dev/my project/main.rs").unwrap();
+        assert_eq!(uri, "file:///home/dev/my%20project/main.rs");
+        assert_eq!(uri_to_path(&uri).as_deref(), Some("/home/dev/my project/main.rs"));
+        assert!(path_to_uri("relative/main.rs").is_err());
+    }
+}
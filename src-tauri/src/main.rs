@@ -3,17 +3,15 @@
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod collab; // 🆕 WebSocket collaboration
-mod commands;
-mod debug;
-mod gguf;
 mod local_history;
-mod oauth;
-mod oauth_backend;
-mod rag_pipeline;
-mod streaming;
-mod tree_sitter_parser;
-mod vector_db;
+
+// Every module shared with the lib crate is used from it, so managed state, the
+// tree-sitter parse cache and the container session exist once per process and
+// the lib commands that read them (e.g. `testing::run_affected_tests`) agree
+use corex_lib::{
+    commands, dependency_graph, devcontainer, gguf, oauth, oauth_backend, streaming,
+    symbol_index, workspace_indexer,
+};
 
 use commands::{
     // RAG Pipeline commands
@@ -39,9 +37,11 @@ use commands::{
     open_terminal,
     // Tree-sitter Parser commands
     parse_file_ast,
+    get_complexity_hotspots,
     read_file,
     read_file_content,
     scan_project,
+    set_vector_embedding_source,
     test_project,
     vector_search,
     write_file,
@@ -51,21 +51,42 @@ use local_history::{get_local_history, restore_local_history, save_local_history
 
 use corex_lib::debug::{
     debug_continue, debug_step_into, debug_step_out, debug_step_over, evaluate_expression,
-    get_variables, remove_breakpoint, set_breakpoint, start_debug_session,
+    get_exception_filters, get_scopes, get_stack_trace, get_variables, list_breakpoints,
+    remove_breakpoint, set_breakpoint, set_exception_breakpoints, set_function_breakpoint,
+    start_debug_session, stop_debug_session, DebugState,
 };
 
 use gguf::{
+    cancel_generation,
     chat_with_gguf_model,
     chat_with_gguf_vision, // 🆕 Vision AI
     check_cuda_support,
+    forget_conversation,
+    get_generation_queue,
     get_gguf_model_status,
     get_gpu_memory_info,
     load_gguf_model,
     read_gguf_metadata,
+    set_context_cache_limit,
+    set_generation_concurrency,
     unload_gguf_model,
     GgufState,
 };
 
+use dependency_graph::{
+    build_dependency_graph, find_dependency_cycles, get_transitive_dependents,
+    DependencyGraphState,
+};
+
+use devcontainer::{devcontainer_close, devcontainer_open, devcontainer_status};
+
+use symbol_index::{find_references, go_to_definition, SymbolIndexState};
+
+use workspace_indexer::{
+    list_workspace_indexers, start_workspace_indexer, stop_workspace_indexer,
+    WorkspaceIndexerState,
+};
+
 use oauth::oauth_authenticate;
 use oauth_backend::{exchange_oauth_token, refresh_oauth_token};
 use streaming::{chat_with_http_streaming, chat_with_streaming};
@@ -92,6 +113,12 @@ pub fn run() {
 
     tauri::Builder::default()
         .manage(gguf_state.clone())
+        .manage(WorkspaceIndexerState::default())
+        .manage(DependencyGraphState::default())
+        .manage(SymbolIndexState::default())
+        .manage(corex_lib::lsp::LspState::default())
+        .manage(DebugState::default())
+        .manage(corex_lib::docker::DockerState::default())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
@@ -128,6 +155,11 @@ pub fn run() {
             load_gguf_model,
             chat_with_gguf_model,
             chat_with_gguf_vision,
+            cancel_generation,
+            set_generation_concurrency,
+            get_generation_queue,
+            forget_conversation,
+            set_context_cache_limit,
             unload_gguf_model,
             get_gguf_model_status,
             get_gpu_memory_info,
@@ -146,27 +178,83 @@ pub fn run() {
             vector_search,
             index_file_vector,
             delete_file_index,
+            set_vector_embedding_source,
+            // Workspace indexer
+            start_workspace_indexer,
+            stop_workspace_indexer,
+            list_workspace_indexers,
+            // Dependency graph
+            build_dependency_graph,
+            get_transitive_dependents,
+            find_dependency_cycles,
+            // Symbol navigation
+            go_to_definition,
+            find_references,
             // RAG Pipeline commands
             analyze_query_intent,
             build_rag_context,
             // Tree-sitter Parser commands
             parse_file_ast,
+            get_complexity_hotspots,
             clear_ast_cache,
             invalidate_file_cache,
             // Debug Adapter Protocol commands
             start_debug_session,
+            stop_debug_session,
             set_breakpoint,
+            set_function_breakpoint,
             remove_breakpoint,
+            list_breakpoints,
+            set_exception_breakpoints,
+            get_exception_filters,
+            corex_lib::launch_config::list_debug_configurations,
+            corex_lib::launch_config::start_debug_configuration,
             debug_continue,
             debug_step_over,
             debug_step_into,
             debug_step_out,
+            get_stack_trace,
+            get_scopes,
             get_variables,
             evaluate_expression,
+            // Language servers
+            corex_lib::lsp::start_lsp_server,
+            corex_lib::lsp::stop_lsp_server,
+            corex_lib::lsp::list_lsp_servers,
+            corex_lib::lsp::lsp_did_open,
+            corex_lib::lsp::lsp_did_change,
+            corex_lib::lsp::lsp_did_close,
+            corex_lib::lsp::lsp_hover,
+            corex_lib::lsp::lsp_completion,
+            corex_lib::lsp::lsp_rename,
             // Testing Framework commands
             corex_lib::testing::scan_workspace_tests,
             corex_lib::testing::run_test_suite,
+            corex_lib::testing::run_test,
+            corex_lib::testing::rerun_failed_tests,
+            corex_lib::testing::run_affected_tests,
+            corex_lib::testing::get_cached_test_results,
+            corex_lib::testing::get_test_insights,
             corex_lib::testing::get_code_coverage,
+            // Docker
+            corex_lib::docker::docker_list_containers,
+            corex_lib::docker::docker_list_images,
+            corex_lib::docker::docker_container_action,
+            corex_lib::docker::docker_remove_image,
+            corex_lib::docker::docker_follow_logs,
+            corex_lib::docker::docker_exec_start,
+            corex_lib::docker::docker_exec_input,
+            corex_lib::docker::docker_exec_resize,
+            corex_lib::docker::docker_stream_stats,
+            corex_lib::docker::docker_stop_stream,
+            corex_lib::compose::docker_compose_find,
+            corex_lib::compose::docker_compose_project,
+            corex_lib::compose::docker_compose_action,
+            corex_lib::compose::docker_compose_logs,
+            // Dev containers
+            devcontainer_open,
+            devcontainer_close,
+            devcontainer_status,
             // Local history
             save_local_history,
             get_local_history,
@@ -182,6 +270,7 @@ pub fn run() {
                         log::info!("🧹 Cleaning up GGUF backend...");
                         state.backend = None;
                         state.models.clear();
+                        state.conversations.clear();
                         state.backend_initialized = false;
                         log::info!("✅ GGUF model cleaned up");
                     }
@@ -1,9 +1,20 @@
 // src-tauri/src/rag_pipeline.rs
 // RAG Pipeline Integration using Rig framework
 
+use crate::dependency_graph::DependencyGraph;
+use crate::tree_sitter_parser::{FileAnalysis, HOTSPOT_COMPLEXITY};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 
+/// Hotspots listed when the refactor target itself has no metrics
+const MAX_REPORTED_HOTSPOTS: usize = 5;
+
+/// Vector hits whose import neighbourhood is added to the context
+const MAX_GRAPH_FILES: usize = 3;
+
+/// Imports / importers listed per file
+const MAX_GRAPH_NEIGHBOURS: usize = 10;
+
 /// Query intent types for context building
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -18,7 +29,7 @@ pub enum QueryIntent {
 /// Context source attribution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextSource {
-    pub source_type: String,  // "vector_db", "symbol_resolver", "git", "dependency_graph"
+    pub source_type: String,  // "vector_db", "symbol_resolver", "git", "dependency_graph", "code_metrics"
     pub file_path: String,
     pub relevance_score: f32,
     pub reason: String,
@@ -92,7 +103,17 @@ impl RAGPipeline {
                 word.ends_with(".jsx") ||
                 word.ends_with(".rs") ||
                 word.ends_with(".py") ||
-                word.ends_with(".go")
+                word.ends_with(".go") ||
+                word.ends_with(".java") ||
+                word.ends_with(".c") ||
+                word.ends_with(".cpp") ||
+                word.ends_with(".h") ||
+                word.ends_with(".hpp") ||
+                word.ends_with(".cs") ||
+                word.ends_with(".json") ||
+                word.ends_with(".toml") ||
+                word.ends_with(".yaml") ||
+                word.ends_with(".yml")
             ) {
                 return word.to_string();
             }
@@ -118,13 +139,14 @@ impl RAGPipeline {
             _ => 3,
         };
 
-        // Generate embedding using the VectorDB's internal fastembed model
+        // Generate embedding with the VectorDB's built-in embedder
         let query_embedding = vector_db.generate_embedding(query).await?;
-        let chunks = vector_db.query(query_embedding, top_k, None).await.unwrap_or_default();
+        let hits = vector_db.query_scored(query_embedding, top_k, None).await.unwrap_or_default();
 
-        if !chunks.is_empty() {
+        if !hits.is_empty() {
             context.push_str("=== İLGİLİ KOD PARÇALARI (Vector DB) ===\n\n");
-            for chunk in &chunks {
+            for hit in &hits {
+                let chunk = &hit.chunk;
                 context.push_str(&format!(
                     "--- {} ({}) ---\n{}\n\n",
                     chunk.file_path,
@@ -135,13 +157,32 @@ impl RAGPipeline {
                 sources.push(ContextSource {
                     source_type: "vector_db".to_string(),
                     file_path: chunk.file_path.clone(),
-                    relevance_score: 0.9, 
+                    relevance_score: hit.score,
                     reason: format!("Vector benzerliği: {}", chunk.chunk_type),
                 });
             }
         }
 
         // 2. Intent'e göre ek bağlam
+        if let QueryIntent::Refactor { symbol } = &intent {
+            let mut analyses = Vec::new();
+            for hit in &hits {
+                let path = &hit.chunk.file_path;
+                if analyses.iter().any(|a: &FileAnalysis| &a.file_path == path) {
+                    continue;
+                }
+                // Languages without a grammar simply contribute no metrics
+                if let Ok(analysis) = crate::commands::analyze_file(path).await {
+                    analyses.push(analysis);
+                }
+            }
+
+            if let Some((report, metric_sources)) = Self::complexity_report(symbol, &analyses) {
+                context.push_str(&report);
+                sources.extend(metric_sources);
+            }
+        }
+
         match &intent {
             QueryIntent::Debug { file } if !file.is_empty() => {
                 context.push_str(&format!("\n=== HEDEF ANALİZ DOSYASI: {} ===\n", file));
@@ -163,6 +204,117 @@ impl RAGPipeline {
         Ok((context, sources))
     }
     
+    /// Complexity metrics for a refactor target
+    ///
+    /// Lists the functions named `symbol` (or defined inside it); if none of
+    /// the analyzed files contain it, falls back to their worst hotspots.
+    pub fn complexity_report(symbol: &str, analyses: &[FileAnalysis]) -> Option<(String, Vec<ContextSource>)> {
+        let is_target = |name: &str, parent: Option<&str>| {
+            !symbol.is_empty() && (name == symbol || parent == Some(symbol))
+        };
+
+        let mut entries: Vec<(&FileAnalysis, &crate::tree_sitter_parser::Symbol)> = analyses.iter()
+            .flat_map(|a| a.symbols.iter().map(move |s| (a, s)))
+            .filter(|(_, s)| s.metrics.is_some() && is_target(&s.name, s.parent.as_deref()))
+            .collect();
+
+        let targeted = !entries.is_empty();
+        if !targeted {
+            entries = analyses.iter()
+                .flat_map(|a| a.hotspots(HOTSPOT_COMPLEXITY).into_iter().map(move |s| (a, s)))
+                .collect();
+            entries.sort_by_key(|(_, s)| std::cmp::Reverse(s.metrics.as_ref().map(|m| m.cyclomatic_complexity)));
+            entries.truncate(MAX_REPORTED_HOTSPOTS);
+        }
+
+        if entries.is_empty() {
+            return None;
+        }
+
+        let mut report = String::from("\n=== KARMAŞIKLIK METRİKLERİ ===\n");
+        let mut sources = Vec::new();
+        for (analysis, s) in entries {
+            let Some(metrics) = s.metrics.as_ref() else { continue };
+            let hotspot = if metrics.cyclomatic_complexity >= HOTSPOT_COMPLEXITY { " [HOTSPOT]" } else { "" };
+            report.push_str(&format!(
+                "- {} ({}:{}-{}): cyclomatic {}, {} satır, nesting {}, {} parametre{}\n",
+                s.name, analysis.file_path, s.line, s.end_line,
+                metrics.cyclomatic_complexity, metrics.line_count, metrics.nesting_depth, metrics.parameter_count,
+                hotspot
+            ));
+
+            if !sources.iter().any(|src: &ContextSource| src.file_path == analysis.file_path) {
+                sources.push(ContextSource {
+                    source_type: "code_metrics".to_string(),
+                    file_path: analysis.file_path.clone(),
+                    relevance_score: if targeted { 1.0 } else { 0.5 },
+                    reason: if targeted {
+                        format!("Refactor hedefinin metrikleri: {}", symbol)
+                    } else {
+                        "Karmaşıklık hotspot'ları".to_string()
+                    },
+                });
+            }
+        }
+
+        Some((report, sources))
+    }
+
+    /// Direct imports and importers of the given files, from the dependency graph
+    pub fn dependency_context(&self, graph: &DependencyGraph, files: &[String]) -> Option<(String, Vec<ContextSource>)> {
+        let mut seen_files: Vec<&String> = Vec::new();
+        for file in files {
+            if !seen_files.contains(&file) {
+                seen_files.push(file);
+            }
+        }
+        seen_files.truncate(MAX_GRAPH_FILES);
+
+        let mut report = String::new();
+        let mut sources: Vec<ContextSource> = Vec::new();
+        for file in seen_files {
+            let dependencies = graph.dependencies_of(file);
+            let dependents = graph.dependents_of(file);
+            if dependencies.is_empty() && dependents.is_empty() {
+                continue;
+            }
+
+            report.push_str(&format!("\n--- {} ---\n", file));
+            let groups = [
+                ("Import ettiği", &dependencies, 0.4, format!("{} tarafından import ediliyor", file)),
+                ("Import edenler", &dependents, 0.5, format!("{} dosyasını import ediyor", file)),
+            ];
+            for (label, related, score, reason) in groups {
+                if related.is_empty() {
+                    continue;
+                }
+                let shown: Vec<&String> = related.iter().take(MAX_GRAPH_NEIGHBOURS).collect();
+                report.push_str(&format!(
+                    "{}: {}\n",
+                    label,
+                    shown.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+                ));
+
+                for related_file in shown {
+                    if files.contains(related_file) || sources.iter().any(|s| &s.file_path == related_file) {
+                        continue;
+                    }
+                    sources.push(ContextSource {
+                        source_type: "dependency_graph".to_string(),
+                        file_path: related_file.clone(),
+                        relevance_score: score,
+                        reason: reason.clone(),
+                    });
+                }
+            }
+        }
+
+        if report.is_empty() {
+            return None;
+        }
+        Some((format!("\n=== BAĞIMLILIK GRAFİĞİ ===\n{}", report), sources))
+    }
+
     /// Estimate token count (rough approximation)
     pub fn estimate_tokens(text: &str) -> usize {
         // Rough estimation: 1 token ≈ 4 characters
@@ -213,6 +365,49 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_complexity_report_prefers_refactor_target() {
+        let mut parser = crate::tree_sitter_parser::TreeSitterParser::new();
+        let content = "def calculateTotal(items, tax):\n    return sum(i for i in items if i) * tax if tax else 0\n\ndef other():\n    pass\n";
+        let analysis = parser.parse_file("cart.py", content).unwrap();
+
+        let (report, sources) = RAGPipeline::complexity_report("calculateTotal", std::slice::from_ref(&analysis)).unwrap();
+        assert!(report.contains("calculateTotal (cart.py:1-2): cyclomatic 4"));
+        assert!(!report.contains("other"));
+        assert_eq!(sources[0].source_type, "code_metrics");
+
+        // Unknown target and no hotspots: nothing worth adding
+        assert!(RAGPipeline::complexity_report("Missing", &[analysis]).is_none());
+    }
+
+    #[test]
+    fn test_dependency_context_lists_neighbours() {
+        let analysis = |path: &str, imports: &[&str]| FileAnalysis {
+            file_path: path.to_string(),
+            symbols: Vec::new(),
+            imports: imports.iter().map(|i| i.to_string()).collect(),
+            exports: Vec::new(),
+            complexity: 0,
+            metrics: Default::default(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        };
+        let graph = DependencyGraph::from_analyses(
+            std::path::Path::new("/ws"),
+            None,
+            &[analysis("/ws/a.ts", &["./b"]), analysis("/ws/b.ts", &[]), analysis("/ws/c.ts", &["./a"])],
+        );
+
+        let pipeline = RAGPipeline::new(170_000);
+        let (report, sources) = pipeline.dependency_context(&graph, &["/ws/a.ts".to_string()]).unwrap();
+        assert!(report.contains("Import ettiği: /ws/b.ts"));
+        assert!(report.contains("Import edenler: /ws/c.ts"));
+        assert_eq!(sources.len(), 2);
+        assert!(sources.iter().all(|s| s.source_type == "dependency_graph"));
+
+        assert!(pipeline.dependency_context(&graph, &["/ws/other.ts".to_string()]).is_none());
+    }
+
     #[test]
     fn test_token_estimation() {
         let text = "This is a test string with approximately 10 words in it.";
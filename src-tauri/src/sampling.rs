@@ -0,0 +1,224 @@
+// src-tauri/src/sampling.rs
+// Token sampler shared by every local generation path
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// Sampling knobs for a single generation (llama.cpp defaults, except the app-wide 0.7 temperature)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingParams {
+    pub temperature: f32,
+    /// 0 disables top-k
+    pub top_k: usize,
+    /// 1.0 disables top-p
+    pub top_p: f32,
+    /// 0.0 disables min-p
+    pub min_p: f32,
+    /// 1.0 disables the repetition penalty
+    pub repeat_penalty: f32,
+    /// How many trailing tokens the repetition penalty looks at
+    pub repeat_last_n: usize,
+    /// Fixed seed for reproducible output
+    pub seed: Option<u64>,
+}
+
+impl Default for SamplingParams {
+    fn default() -> Self {
+        Self {
+            temperature: 0.7,
+            top_k: 40,
+            top_p: 0.95,
+            min_p: 0.05,
+            repeat_penalty: 1.0,
+            repeat_last_n: 64,
+            seed: None,
+        }
+    }
+}
+
+pub struct Sampler {
+    params: SamplingParams,
+    rng: StdRng,
+}
+
+impl Sampler {
+    pub fn new(params: SamplingParams) -> Self {
+        let rng = match params.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self { params, rng }
+    }
+
+    /// Pick the next token from raw `(id, logit)` candidates.
+    ///
+    /// `history` is the prompt plus everything generated so far; only its
+    /// last `repeat_last_n` tokens are penalized. Samplers run in llama.cpp's
+    /// order: penalty, top-k, top-p, min-p, then temperature.
+    pub fn sample(&mut self, mut candidates: Vec<(i32, f32)>, history: &[i32]) -> Option<i32> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let p = &self.params;
+
+        // 1. Repetition penalty (llama.cpp style: shrink positive logits, grow negative ones)
+        if p.repeat_penalty != 1.0 && p.repeat_last_n > 0 {
+            let start = history.len().saturating_sub(p.repeat_last_n);
+            let recent: HashSet<i32> = history[start..].iter().copied().collect();
+            for (id, logit) in candidates.iter_mut() {
+                if recent.contains(id) {
+                    if *logit <= 0.0 {
+                        *logit *= p.repeat_penalty;
+                    } else {
+                        *logit /= p.repeat_penalty;
+                    }
+                }
+            }
+        }
+
+        // Highest logit first
+        let by_logit = |a: &(i32, f32), b: &(i32, f32)| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal);
+
+        // Greedy
+        if p.temperature <= 0.0 {
+            return candidates.iter().min_by(|a, b| by_logit(a, b)).map(|(id, _)| *id);
+        }
+
+        // 2. Top-k: partition instead of sorting the whole vocabulary
+        if p.top_k > 0 && candidates.len() > p.top_k {
+            candidates.select_nth_unstable_by(p.top_k - 1, by_logit);
+            candidates.truncate(p.top_k);
+        }
+
+        // Top-p and min-p see the distribution before temperature
+        let max_logit = candidates.iter().map(|(_, logit)| *logit).fold(f32::NEG_INFINITY, f32::max);
+
+        // 3. Top-p: smallest prefix whose mass reaches top_p
+        if p.top_p < 1.0 {
+            candidates.sort_by(by_logit);
+            let total: f32 = candidates.iter().map(|(_, logit)| (logit - max_logit).exp()).sum();
+            let mut cumulative = 0.0;
+            let mut keep = candidates.len();
+            for (i, (_, logit)) in candidates.iter().enumerate() {
+                cumulative += (logit - max_logit).exp() / total;
+                if cumulative >= p.top_p {
+                    keep = i + 1;
+                    break;
+                }
+            }
+            candidates.truncate(keep.max(1));
+        }
+
+        // 4. Min-p: drop tokens far less likely than the best one (p >= min_p * p_max, in logits)
+        if p.min_p > 0.0 {
+            let threshold = max_logit + p.min_p.ln();
+            candidates.retain(|(_, logit)| *logit >= threshold);
+        }
+
+        // 5. Temperature + softmax
+        let mut probs: Vec<(i32, f32)> = candidates
+            .iter()
+            .map(|(id, logit)| (*id, ((logit - max_logit) / p.temperature).exp()))
+            .collect();
+        normalize(&mut probs);
+
+        let roll: f32 = self.rng.gen();
+        let mut cumulative = 0.0;
+        for (id, prob) in &probs {
+            cumulative += prob;
+            if roll < cumulative {
+                return Some(*id);
+            }
+        }
+        probs.last().map(|(id, _)| *id)
+    }
+}
+
+fn normalize(probs: &mut [(i32, f32)]) {
+    let sum: f32 = probs.iter().map(|(_, p)| p).sum();
+    if sum > 0.0 {
+        for (_, p) in probs.iter_mut() {
+            *p /= sum;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(temperature: f32) -> SamplingParams {
+        SamplingParams {
+            temperature,
+            seed: Some(7),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_zero_temperature_is_greedy_after_penalty() {
+        let candidates = vec![(1, 2.0), (2, 1.9), (3, 0.5)];
+        let mut sampler = Sampler::new(SamplingParams {
+            repeat_penalty: 1.15,
+            ..params(0.0)
+        });
+        assert_eq!(sampler.sample(candidates.clone(), &[]), Some(1));
+        // Token 1 was just generated, so the penalty lets token 2 win
+        assert_eq!(sampler.sample(candidates, &[1]), Some(2));
+    }
+
+    #[test]
+    fn test_top_k_and_min_p_cut_the_tail() {
+        let candidates = vec![(1, 5.0), (2, 4.9), (3, -10.0), (4, -12.0)];
+        let mut sampler = Sampler::new(SamplingParams {
+            top_k: 3,
+            ..params(1.0)
+        });
+        for _ in 0..200 {
+            let id = sampler.sample(candidates.clone(), &[]).unwrap();
+            assert!(id == 1 || id == 2, "sampled tail token {}", id);
+        }
+    }
+
+    #[test]
+    fn test_min_p_runs_before_temperature() {
+        // At temperature 1 token 3 is e^-5 as likely as token 1, under min-p; a hot
+        // temperature must not flatten it back above the threshold
+        let candidates = vec![(3, 0.0), (1, 5.0), (2, 4.0)];
+        let mut sampler = Sampler::new(SamplingParams {
+            top_k: 0,
+            top_p: 1.0,
+            ..params(5.0)
+        });
+        for _ in 0..300 {
+            assert_ne!(sampler.sample(candidates.clone(), &[]), Some(3));
+        }
+    }
+
+    #[test]
+    fn test_top_p_keeps_the_dominant_token() {
+        let candidates = vec![(1, 10.0), (2, 1.0), (3, 0.5)];
+        let mut sampler = Sampler::new(SamplingParams {
+            top_p: 0.5,
+            min_p: 0.0,
+            ..params(1.0)
+        });
+        for _ in 0..50 {
+            assert_eq!(sampler.sample(candidates.clone(), &[]), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_tokens() {
+        let candidates: Vec<(i32, f32)> = (0..20).map(|i| (i, (i % 5) as f32 * 0.3)).collect();
+        let mut a = Sampler::new(params(1.2));
+        let mut b = Sampler::new(params(1.2));
+        let run_a: Vec<_> = (0..30).map(|_| a.sample(candidates.clone(), &[])).collect();
+        let run_b: Vec<_> = (0..30).map(|_| b.sample(candidates.clone(), &[])).collect();
+        assert_eq!(run_a, run_b);
+    }
+}
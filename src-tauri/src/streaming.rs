@@ -5,10 +5,19 @@ use tauri::{AppHandle, Emitter, Manager};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
+use crate::gguf::{stream_generation, GenerationRequest, StopReason};
+use crate::sampling::SamplingParams;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamToken {
     pub token: String,
     pub is_complete: bool,
+    /// Set on the final token of a local generation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<StopReason>,
+    /// Local generation this token belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +26,14 @@ pub struct StreamingRequest {
     pub prompt: String,
     pub max_tokens: Option<i32>,
     pub temperature: Option<f32>,
+    #[serde(default)]
+    pub sampling: Option<SamplingParams>,
+    /// Id for `cancel_generation`; generated when missing
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Reuse this conversation's cached context across turns
+    #[serde(default)]
+    pub conversation_id: Option<String>,
 }
 
 /// Stream AI response with real-time token emission
@@ -26,98 +43,26 @@ pub async fn chat_with_streaming(
     request: StreamingRequest,
 ) -> Result<String, String> {
     log::info!("🌊 Starting real GGUF streaming chat...");
-    
-    // 1. Get GGUF state and model
-    let gguf_state = app.state::<Arc<Mutex<crate::gguf::GgufState>>>();
-    
-    let (loaded_model, backend) = {
-        let guard = gguf_state.lock().map_err(|e| e.to_string())?;
-        
-        let model_path = match &request.model_path {
-            Some(path) => path.clone(),
-            None => guard.models.keys().next().cloned().ok_or("No models loaded")?,
-        };
-
-        let model = guard.models.get(&model_path).cloned().ok_or("Model not found")?;
-        let backend = guard.backend.as_ref().cloned().ok_or("Backend not init")?;
-        (model, backend)
-    };
-
-    let model = &loaded_model.model;
-    let n_ctx = loaded_model.n_ctx;
-    let max_tokens = request.max_tokens.unwrap_or(2000) as u32;
-    let temperature = request.temperature.unwrap_or(0.7);
-
-    // 2. Setup Context (FIX-31)
-    let kv_cache_size = (n_ctx + max_tokens).max(4096);
-    let n_batch = 2048;
-    let ctx_params = llama_cpp_2::context::params::LlamaContextParams::default()
-        .with_n_ctx(std::num::NonZeroU32::new(kv_cache_size as u32))
-        .with_n_batch(n_batch as u32);
-
-    let mut context = model.new_context(&backend, ctx_params).map_err(|e| format!("{:?}", e))?;
-
-    // 3. Tokenize Prompt
-    let tokens = model.str_to_token(&request.prompt, llama_cpp_2::model::AddBos::Always)
-        .map_err(|e| format!("{:?}", e))?;
-
-    app.emit("stream-start", ()).map_err(|e| e.to_string())?;
-
-    // 4. Initial Batch Processing
-    let mut batch = llama_cpp_2::llama_batch::LlamaBatch::new(tokens.len().min(8192), 1);
-    for (i, token) in tokens.iter().enumerate() {
-        batch.add(*token, i as i32, &[0], i == tokens.len() - 1).map_err(|e| format!("{:?}", e))?;
-    }
-    context.decode(&mut batch).map_err(|e| format!("{:?}", e))?;
 
-    // 5. Token Generation Loop
-    let mut full_response = String::new();
-    let mut n_cur = batch.n_tokens();
-    let mut decoder = encoding_rs::UTF_8.new_decoder();
-    let mut response_tokens = Vec::new();
-
-    for _i in 0..max_tokens {
-        let candidates = context.candidates();
-        
-        // Simple sampling logic (reused from gguf.rs basics)
-        let candidates_vec: Vec<_> = candidates.into_iter().collect();
-        let new_token_id = if temperature > 0.0 {
-            // Very basic sampling for brevity here, similar to gguf.rs
-            candidates_vec[0].id() // Fallback to greedy if sampling complex
-        } else {
-            candidates_vec[0].id()
-        };
-
-        if model.is_eog_token(new_token_id) { break; }
-
-        // Decode token to string
-        match model.token_to_piece(new_token_id, &mut decoder, false, None) {
-            Ok(token_str) => {
-                full_response.push_str(&token_str);
-                
-                // 🔥 Emit token immediately!
-                app.emit("stream-token", StreamToken {
-                    token: token_str,
-                    is_complete: false,
-                }).map_err(|e| e.to_string())?;
-            },
-            _ => {}
-        }
+    let gguf_state = app.state::<Arc<Mutex<crate::gguf::GgufState>>>();
 
-        response_tokens.push(new_token_id);
-        
-        // Prepare for next token
-        batch.clear();
-        batch.add(new_token_id, n_cur, &[0], true).map_err(|e| format!("{:?}", e))?;
-        context.decode(&mut batch).map_err(|e| format!("{:?}", e))?;
-        n_cur += 1;
+    let mut params = request.sampling.unwrap_or_default();
+    if let Some(temperature) = request.temperature {
+        params.temperature = temperature;
     }
-
-    // 6. Complete
-    app.emit("stream-token", StreamToken { token: String::new(), is_complete: true }).map_err(|e| e.to_string())?;
-    app.emit("stream-complete", full_response.clone()).map_err(|e| e.to_string())?;
-    
-    Ok(full_response)
+    let max_tokens = request.max_tokens.unwrap_or(2000).max(1) as u32;
+
+    let output = stream_generation(&app, gguf_state.inner(), GenerationRequest {
+        request_id: request.request_id,
+        model_path: request.model_path,
+        prompt: request.prompt,
+        max_tokens,
+        params,
+        conversation_id: request.conversation_id,
+    })
+    .await?;
+
+    Ok(output.text)
 }
 
 /// Stream with HTTP API (LM Studio, Ollama)
@@ -135,7 +80,9 @@ pub async fn chat_with_http_streaming(
     let client = Client::new();
     
     // Emit start event
-    app.emit("stream-start", ()).map_err(|e| e.to_string())?;
+    let request_id = request.request_id.clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    app.emit("stream-start", serde_json::json!({ "request_id": request_id })).map_err(|e| e.to_string())?;
     
     let body = serde_json::json!({
         "model": "default",
@@ -184,6 +131,8 @@ pub async fn chat_with_http_streaming(
                         let stream_token = StreamToken {
                             token: token.to_string(),
                             is_complete: false,
+                            stop_reason: None,
+                            request_id: Some(request_id.clone()),
                         };
                         
                         app.emit("stream-token", stream_token).map_err(|e| e.to_string())?;
@@ -197,14 +146,14 @@ pub async fn chat_with_http_streaming(
     let final_token = StreamToken {
         token: String::new(),
         is_complete: true,
+        stop_reason: None,
+        request_id: Some(request_id.clone()),
     };
     app.emit("stream-token", final_token).map_err(|e| e.to_string())?;
-    app.emit("stream-complete", full_response.clone()).map_err(|e| e.to_string())?;
+    app.emit("stream-complete", serde_json::json!({ "request_id": request_id, "text": full_response }))
+        .map_err(|e| e.to_string())?;
     
     log::info!("✅ HTTP streaming complete");
     
     Ok(full_response)
 }
-
-// Note: We don't need chat_with_gguf_model_internal anymore
-// We use the existing chat_with_gguf_model directly
@@ -0,0 +1,353 @@
+// src-tauri/src/symbol_index.rs
+// Workspace symbol index: go-to-definition and find-references on top of the parser and import graph
+
+use crate::dependency_graph::{DependencyGraph, DependencyGraphState};
+use crate::tree_sitter_parser::{FileAnalysis, TreeSitterParser};
+use log::info;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use tauri::State;
+
+/// Where a symbol is defined
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolLocation {
+    pub file_path: String,
+    pub name: String,
+    pub kind: String,
+    pub parent: Option<String>,
+    /// 1-based lines and 0-based byte columns, like `Symbol`
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// One use (or the definition) of an identifier
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReferenceLocation {
+    pub file_path: String,
+    pub line: usize,
+    pub column: usize,
+    pub end_column: usize,
+    pub is_definition: bool,
+}
+
+/// Definitions by name across every parsed workspace file
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    by_name: HashMap<String, Vec<SymbolLocation>>,
+    files: BTreeSet<String>,
+}
+
+impl SymbolIndex {
+    pub fn from_analyses(analyses: &[FileAnalysis]) -> Self {
+        let mut index = SymbolIndex::default();
+        for analysis in analyses {
+            index.files.insert(analysis.file_path.clone());
+            for symbol in &analysis.symbols {
+                index.by_name.entry(symbol.name.clone()).or_default().push(SymbolLocation {
+                    file_path: analysis.file_path.clone(),
+                    name: symbol.name.clone(),
+                    kind: symbol.kind.clone(),
+                    parent: symbol.parent.clone(),
+                    line: symbol.line,
+                    column: symbol.column,
+                    end_line: symbol.end_line,
+                    end_column: symbol.end_column,
+                });
+            }
+        }
+        index
+    }
+
+    /// Number of indexed definitions
+    pub fn len(&self) -> usize {
+        self.by_name.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &String> {
+        self.files.iter()
+    }
+
+    /// Definitions of `name`, most relevant to `from_file` first
+    ///
+    /// The file itself wins, then files it imports directly, then files it reaches
+    /// through the import graph; everything else keeps file/line order.
+    pub fn definitions(&self, name: &str, from_file: &str, graph: Option<&DependencyGraph>) -> Vec<SymbolLocation> {
+        let Some(locations) = self.by_name.get(name) else {
+            return Vec::new();
+        };
+
+        let direct: BTreeSet<String> = graph
+            .map(|g| g.dependencies_of(from_file).into_iter().collect())
+            .unwrap_or_default();
+        let transitive: BTreeSet<String> = graph
+            .map(|g| g.transitive_dependencies(from_file).into_iter().collect())
+            .unwrap_or_default();
+        let rank = |location: &SymbolLocation| {
+            if location.file_path == from_file {
+                3
+            } else if direct.contains(&location.file_path) {
+                2
+            } else if transitive.contains(&location.file_path) {
+                1
+            } else {
+                0
+            }
+        };
+
+        let mut ranked = locations.clone();
+        ranked.sort_by(|a, b| {
+            rank(b).cmp(&rank(a))
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then(a.line.cmp(&b.line))
+        });
+        ranked
+    }
+
+    /// True if `name` is defined on `line` of `file_path`
+    fn defines_at(&self, name: &str, file_path: &str, line: usize) -> bool {
+        self.by_name
+            .get(name)
+            .map(|locations| locations.iter().any(|l| l.file_path == file_path && l.line == line))
+            .unwrap_or(false)
+    }
+}
+
+/// The index built alongside the most recent dependency graph
+#[derive(Default)]
+pub struct SymbolIndexState {
+    index: Mutex<Option<SymbolIndex>>,
+}
+
+impl SymbolIndexState {
+    pub fn replace(&self, index: SymbolIndex) -> Result<(), String> {
+        *self.index.lock().map_err(|e| e.to_string())? = Some(index);
+        Ok(())
+    }
+
+    /// Run a query against the current index
+    pub fn with_index<T>(&self, f: impl FnOnce(&SymbolIndex) -> T) -> Result<T, String> {
+        let index = self.index.lock().map_err(|e| e.to_string())?;
+        let index = index.as_ref()
+            .ok_or("Sembol indeksi oluşturulmamış, önce build_dependency_graph çağırın")?;
+        Ok(f(index))
+    }
+}
+
+/// Identifier under the cursor (1-based line, 0-based column)
+fn identifier_at(file_path: &str, line: usize, column: usize) -> Result<Option<String>, String> {
+    let content = std::fs::read_to_string(file_path)
+        .map_err(|e| format!("Dosya okunamadı: {}", e))?;
+    TreeSitterParser::new()
+        .identifier_at(file_path, &content, line, column)
+        .map_err(|e| format!("Parse hatası: {}", e))
+}
+
+/// Files that can reference a definition in `definition_file`: itself and everything importing it
+fn reference_candidates(definition_file: &str, graph: &DependencyGraph) -> Vec<String> {
+    let mut files = vec![definition_file.to_string()];
+    files.extend(graph.transitive_dependents(definition_file));
+    files
+}
+
+/// Definitions of the identifier at the cursor, best match first
+#[tauri::command]
+pub async fn go_to_definition(
+    file_path: String,
+    line: usize,
+    column: usize,
+    symbol_state: State<'_, SymbolIndexState>,
+    graph_state: State<'_, DependencyGraphState>,
+) -> Result<Vec<SymbolLocation>, String> {
+    let Some(name) = identifier_at(&file_path, line, column)? else {
+        return Ok(Vec::new());
+    };
+    info!("🎯 Tanım aranıyor: {} ({}:{})", name, file_path, line);
+
+    // The graph is optional: without it only same-file definitions get a boost
+    let definitions = graph_state
+        .with_graph(|graph| symbol_state.with_index(|index| index.definitions(&name, &file_path, Some(graph))))
+        .unwrap_or_else(|_| symbol_state.with_index(|index| index.definitions(&name, &file_path, None)))?;
+    Ok(definitions)
+}
+
+/// Every occurrence of the identifier at the cursor in files that can see its definition
+///
+/// Occurrences are matched by name, so a shadowing local with the same name is reported too.
+#[tauri::command]
+pub async fn find_references(
+    file_path: String,
+    line: usize,
+    column: usize,
+    include_declaration: Option<bool>,
+    symbol_state: State<'_, SymbolIndexState>,
+    graph_state: State<'_, DependencyGraphState>,
+) -> Result<Vec<ReferenceLocation>, String> {
+    let Some(name) = identifier_at(&file_path, line, column)? else {
+        return Ok(Vec::new());
+    };
+    let include_declaration = include_declaration.unwrap_or(true);
+    info!("🔎 Referanslar aranıyor: {} ({}:{})", name, file_path, line);
+
+    let graph_files = graph_state.with_graph(|graph| {
+        let definitions = symbol_state.with_index(|index| index.definitions(&name, &file_path, Some(graph)))?;
+        if definitions.is_empty() {
+            return Ok(None);
+        }
+        let mut files: BTreeSet<String> = definitions
+            .iter()
+            .flat_map(|d| reference_candidates(&d.file_path, graph))
+            .collect();
+        files.insert(file_path.clone());
+        Ok::<_, String>(Some(files))
+    });
+
+    // No graph or no known definition: fall back to every indexed file
+    let files = match graph_files {
+        Ok(result) => result?,
+        Err(_) => None,
+    };
+    let files = match files {
+        Some(files) => files,
+        None => symbol_state.with_index(|index| {
+            let mut files: BTreeSet<String> = index.files().cloned().collect();
+            files.insert(file_path.clone());
+            files
+        })?,
+    };
+
+    let parser = TreeSitterParser::new();
+    let mut references = Vec::new();
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let occurrences = match parser.find_identifiers(&file, &content, &name) {
+            Ok(occurrences) => occurrences,
+            Err(e) => {
+                info!("ℹ️ Referans taraması atlandı [{}]: {}", file, e);
+                continue;
+            }
+        };
+
+        for occurrence in occurrences {
+            let is_definition = symbol_state.with_index(|index| index.defines_at(&name, &file, occurrence.line))?;
+            if is_definition && !include_declaration {
+                continue;
+            }
+            references.push(ReferenceLocation {
+                file_path: file.clone(),
+                line: occurrence.line,
+                column: occurrence.column,
+                end_column: occurrence.end_column,
+                is_definition,
+            });
+        }
+    }
+
+    info!("✅ {} referans bulundu: {}", references.len(), name);
+    Ok(references)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree_sitter_parser::Symbol;
+    use std::path::Path;
+
+    fn symbol(name: &str, line: usize) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            kind: "function".to_string(),
+            line,
+            column: 0,
+            end_line: line + 2,
+            end_column: 1,
+            signature: None,
+            documentation: None,
+            is_exported: true,
+            parent: None,
+            metrics: None,
+        }
+    }
+
+    fn analysis(path: &str, imports: &[&str], symbols: Vec<Symbol>) -> FileAnalysis {
+        FileAnalysis {
+            file_path: path.to_string(),
+            symbols,
+            imports: imports.iter().map(|i| i.to_string()).collect(),
+            exports: Vec::new(),
+            complexity: 0,
+            metrics: Default::default(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_index_covers_every_parsed_file() {
+        let analyses = vec![
+            analysis("/ws/a.ts", &[], vec![symbol("load", 1), symbol("save", 5)]),
+            analysis("/ws/Store.java", &[], vec![symbol("Store", 2)]),
+        ];
+        let index = SymbolIndex::from_analyses(&analyses);
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.files().collect::<Vec<_>>(), vec!["/ws/Store.java", "/ws/a.ts"]);
+        assert_eq!(index.definitions("Store", "/ws/a.ts", None).len(), 1);
+        assert!(index.defines_at("save", "/ws/a.ts", 5));
+        assert!(!index.defines_at("save", "/ws/a.ts", 6));
+    }
+
+    #[test]
+    fn test_definitions_ranked_by_import_distance() {
+        let analyses = vec![
+            analysis("/ws/app.ts", &["./mid"], vec![symbol("helper", 20)]),
+            analysis("/ws/mid.ts", &["./deep"], vec![symbol("helper", 3)]),
+            analysis("/ws/deep.ts", &[], vec![symbol("helper", 7)]),
+            analysis("/ws/other.ts", &[], vec![symbol("helper", 1)]),
+        ];
+        let index = SymbolIndex::from_analyses(&analyses);
+        let graph = DependencyGraph::from_analyses(Path::new("/ws"), None, &analyses);
+
+        let files = |from: &str, graph: Option<&DependencyGraph>| -> Vec<String> {
+            index.definitions("helper", from, graph).into_iter().map(|d| d.file_path).collect()
+        };
+
+        assert_eq!(
+            files("/ws/app.ts", Some(&graph)),
+            vec!["/ws/app.ts", "/ws/mid.ts", "/ws/deep.ts", "/ws/other.ts"]
+        );
+        assert_eq!(
+            files("/ws/other.ts", Some(&graph)),
+            vec!["/ws/other.ts", "/ws/app.ts", "/ws/deep.ts", "/ws/mid.ts"]
+        );
+        // Without a graph only the current file is preferred
+        assert_eq!(
+            files("/ws/mid.ts", None),
+            vec!["/ws/mid.ts", "/ws/app.ts", "/ws/deep.ts", "/ws/other.ts"]
+        );
+    }
+
+    #[test]
+    fn test_reference_candidates_follow_dependents() {
+        let analyses = vec![
+            analysis("/ws/app.ts", &["./mid"], Vec::new()),
+            analysis("/ws/mid.ts", &["./deep"], Vec::new()),
+            analysis("/ws/deep.ts", &[], Vec::new()),
+            analysis("/ws/other.ts", &[], Vec::new()),
+        ];
+        let graph = DependencyGraph::from_analyses(Path::new("/ws"), None, &analyses);
+
+        assert_eq!(
+            reference_candidates("/ws/deep.ts", &graph),
+            vec!["/ws/deep.ts", "/ws/app.ts", "/ws/mid.ts"]
+        );
+    }
+}
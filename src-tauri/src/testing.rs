@@ -1,4 +1,22 @@
+use crate::dependency_graph::{graph_for_workspace, DependencyGraph, DependencyGraphState};
+use crate::devcontainer::DevContainerSession;
+use crate::docker::DockerClient;
+use crate::tree_sitter_parser::TreeSitterParser;
+use crate::workspace_indexer::collect_workspace_files;
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
+use chrono::TimeZone;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter, Runtime, State};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tree_sitter::Node;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TestCase {
@@ -8,6 +26,11 @@ pub struct TestCase {
     pub line: u32,
     pub status: String, // "passed", "failed", "skipped", "pending"
     pub message: Option<String>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Set when the run history shows this test flipping on unchanged code
+    #[serde(default)]
+    pub flaky: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -15,6 +38,12 @@ pub struct TestSuite {
     pub id: String,
     pub name: String,
     pub tests: Vec<TestCase>,
+    /// Absolute path of the file the tests live in
+    #[serde(default)]
+    pub file: String,
+    /// "cargo", "jest", "vitest", "pytest" or "go"
+    #[serde(default)]
+    pub framework: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -27,105 +56,2669 @@ pub struct TestRunResult {
     pub test_cases: Vec<TestCase>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LineCoverage {
+    pub line: u32,
+    pub hits: u64,
+}
+
+/// Branches that start on a line, and how many of them were taken
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BranchCoverage {
+    pub line: u32,
+    pub covered: u32,
+    pub total: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CoverageTotals {
+    pub lines_found: u32,
+    pub lines_hit: u32,
+    pub branches_found: u32,
+    pub branches_hit: u32,
+    pub line_percent: f64,
+    pub branch_percent: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FileCoverage {
+    /// Path relative to the workspace, with forward slashes
+    pub file: String,
+    pub lines: Vec<LineCoverage>,
+    pub branches: Vec<BranchCoverage>,
+    pub totals: CoverageTotals,
+}
+
+/// Percentage point changes since the previous coverage run
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CoverageDelta {
+    pub line_percent: f64,
+    pub branch_percent: f64,
+    /// Line coverage change of files that were in both runs and moved
+    pub files: BTreeMap<String, f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CoverageReport {
+    /// Report file that was read, or the tool that produced it
+    pub source: String,
+    pub files: Vec<FileCoverage>,
+    pub totals: CoverageTotals,
+    /// `None` on the first run in a workspace
+    pub delta: Option<CoverageDelta>,
+}
+
+/// Runner for a file, if it looks like it holds tests
+fn test_framework(path: &Path, content: &str, workspace: &Path) -> Option<&'static str> {
+    let file_name = path.file_name()?.to_str()?;
+    let extension = path.extension()?.to_str()?;
+    match extension {
+        "rs" if content.contains("#[test") || content.contains("::test") => Some("cargo"),
+        "go" if file_name.ends_with("_test.go") => Some("go"),
+        "py" if file_name.starts_with("test_") || file_name.ends_with("_test.py") => Some("pytest"),
+        "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" | "mjs" | "cjs" => {
+            let is_test_file = file_name.contains(".test.")
+                || file_name.contains(".spec.")
+                || path.components().any(|c| c.as_os_str() == "__tests__");
+            is_test_file.then(|| js_runner(path, content, workspace))
+        }
+        _ => None,
+    }
+}
+
+/// vitest if the file or the nearest package.json mentions it, jest otherwise
+fn js_runner(path: &Path, content: &str, workspace: &Path) -> &'static str {
+    if content.contains("from 'vitest'") || content.contains("from \"vitest\"") {
+        return "vitest";
+    }
+    for dir in path.ancestors().skip(1) {
+        if let Ok(package) = std::fs::read_to_string(dir.join("package.json")) {
+            return if package.contains("\"vitest\"") { "vitest" } else { "jest" };
+        }
+        if dir == workspace {
+            break;
+        }
+    }
+    "jest"
+}
+
+fn node_text<'a>(node: Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or("")
+}
+
+fn line_of(node: Node) -> u32 {
+    node.start_position().row as u32 + 1
+}
+
+/// A test found in a file, before it gets an id
+#[derive(Debug, Clone, PartialEq)]
+struct FoundTest {
+    name: String,
+    line: u32,
+    skipped: bool,
+}
+
+/// `test`, `tokio::test`, `async_std::test`, `rstest`... from `#[...]`
+fn is_test_attribute(text: &str) -> bool {
+    let inner = text.trim_start_matches("#[").trim_end_matches(']');
+    let path = inner.split(['(', ' ']).next().unwrap_or("");
+    path == "test" || path == "rstest" || path.ends_with("::test")
+}
+
+fn rust_tests(node: Node, source: &[u8], module_path: &mut Vec<String>, found: &mut Vec<FoundTest>) {
+    let mut is_test = false;
+    let mut ignored = false;
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "attribute_item" => {
+                let text = node_text(child, source);
+                is_test |= is_test_attribute(text);
+                ignored |= text.starts_with("#[ignore");
+                continue;
+            }
+            "function_item" if is_test => {
+                if let Some(name) = child.child_by_field_name("name") {
+                    let mut path = module_path.clone();
+                    path.push(node_text(name, source).to_string());
+                    found.push(FoundTest { name: path.join("::"), line: line_of(child), skipped: ignored });
+                }
+            }
+            "mod_item" => {
+                if let (Some(name), Some(body)) = (child.child_by_field_name("name"), child.child_by_field_name("body")) {
+                    module_path.push(node_text(name, source).to_string());
+                    rust_tests(body, source, module_path, found);
+                    module_path.pop();
+                }
+            }
+            _ => {}
+        }
+        is_test = false;
+        ignored = false;
+    }
+}
+
+/// pytest: module-level `test*` functions and `test*` methods of `Test*` classes
+fn python_tests(node: Node, source: &[u8], class: Option<&str>, found: &mut Vec<FoundTest>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let (definition, skipped) = match child.kind() {
+            "decorated_definition" => {
+                let skipped = node_text(child, source).lines()
+                    .take_while(|l| l.trim_start().starts_with('@'))
+                    .any(|l| l.contains("mark.skip"));
+                match child.child_by_field_name("definition") {
+                    Some(definition) => (definition, skipped),
+                    None => continue,
+                }
+            }
+            _ => (child, false),
+        };
+        let Some(name) = definition.child_by_field_name("name").map(|n| node_text(n, source)) else {
+            continue;
+        };
+
+        match definition.kind() {
+            "function_definition" if name.starts_with("test") => {
+                let name = match class {
+                    Some(class) => format!("{}::{}", class, name),
+                    None => name.to_string(),
+                };
+                found.push(FoundTest { name, line: line_of(child), skipped });
+            }
+            "class_definition" if class.is_none() && name.starts_with("Test") => {
+                if let Some(body) = definition.child_by_field_name("body") {
+                    python_tests(body, source, Some(name), found);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `func TestXxx(t *testing.T)`; the rune after "Test" must not be lowercase
+fn go_tests(node: Node, source: &[u8], found: &mut Vec<FoundTest>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() != "function_declaration" {
+            continue;
+        }
+        let Some(name) = child.child_by_field_name("name").map(|n| node_text(n, source)) else {
+            continue;
+        };
+        let takes_t = child.child_by_field_name("parameters")
+            .map(|p| node_text(p, source).contains("testing.T"))
+            .unwrap_or(false);
+        let valid_name = name.strip_prefix("Test")
+            .map(|rest| !rest.starts_with(|c: char| c.is_lowercase()))
+            .unwrap_or(false);
+        if takes_t && valid_name {
+            found.push(FoundTest { name: name.to_string(), line: line_of(child), skipped: false });
+        }
+    }
+}
+
+/// `describe`/`it`/`test` and their `.skip`/`.only`/`.each` forms
+fn js_callee(node: Node, source: &[u8]) -> Option<(String, Option<String>)> {
+    match node.kind() {
+        "identifier" => Some((node_text(node, source).to_string(), None)),
+        "member_expression" => {
+            let (base, _) = js_callee(node.child_by_field_name("object")?, source)?;
+            let property = node.child_by_field_name("property").map(|p| node_text(p, source).to_string());
+            Some((base, property))
+        }
+        // describe.each(table)("name", fn)
+        "call_expression" => js_callee(node.child_by_field_name("function")?, source),
+        _ => None,
+    }
+}
+
+fn js_title(arguments: Node, source: &[u8]) -> Option<String> {
+    let mut cursor = arguments.walk();
+    let first = arguments.named_children(&mut cursor).next()?;
+    let text = node_text(first, source);
+    Some(match first.kind() {
+        "string" | "template_string" => text.get(1..text.len().saturating_sub(1)).unwrap_or("").to_string(),
+        _ => text.to_string(),
+    })
+}
+
+/// Jest/Vitest tests, named like Jest's `fullName` (describe titles and test title joined by spaces)
+fn js_tests(node: Node, source: &[u8], titles: &mut Vec<String>, skipped: bool, found: &mut Vec<FoundTest>) {
+    if node.kind() == "call_expression" {
+        let callee = node.child_by_field_name("function").and_then(|f| js_callee(f, source));
+        let arguments = node.child_by_field_name("arguments");
+        if let (Some((base, modifier)), Some(arguments)) = (callee, arguments) {
+            let is_skip = base.starts_with('x') || matches!(modifier.as_deref(), Some("skip") | Some("todo"));
+            let title = js_title(arguments, source);
+            match (base.as_str(), title) {
+                ("describe" | "xdescribe" | "fdescribe" | "suite", Some(title)) => {
+                    titles.push(title);
+                    js_tests(arguments, source, titles, skipped || is_skip, found);
+                    titles.pop();
+                    return;
+                }
+                ("it" | "test" | "xit" | "xtest" | "fit", Some(title)) => {
+                    let mut name = titles.clone();
+                    name.push(title);
+                    found.push(FoundTest { name: name.join(" "), line: line_of(node), skipped: skipped || is_skip });
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        js_tests(child, source, titles, skipped, found);
+    }
+}
+
+fn find_tests(parser: &TreeSitterParser, file_path: &str, content: &str) -> Result<Vec<FoundTest>, String> {
+    let (language, tree) = parser.syntax_tree(file_path, content)
+        .map_err(|e| format!("Parse hatası: {}", e))?;
+    let source = content.as_bytes();
+    let root = tree.root_node();
+
+    let mut found = Vec::new();
+    match language.as_str() {
+        "rust" => rust_tests(root, source, &mut Vec::new(), &mut found),
+        "python" => python_tests(root, source, None, &mut found),
+        "go" => go_tests(root, source, &mut found),
+        "typescript" | "tsx" | "javascript" => js_tests(root, source, &mut Vec::new(), false, &mut found),
+        _ => {}
+    }
+    Ok(found)
+}
+
+/// Suite id: the file path relative to the workspace, so it survives rescans
+fn suite_id(workspace: &Path, file: &Path) -> String {
+    file.strip_prefix(workspace)
+        .unwrap_or(file)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Turn found tests into `TestCase`s whose ids are `<suite id>::<name>`
+fn build_suite(id: String, file: &Path, framework: &str, found: Vec<FoundTest>) -> TestSuite {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let tests = found.into_iter()
+        .map(|test| {
+            // Same-named tests (e.g. `it.each` rows) get a counter to keep ids unique
+            let count = seen.entry(test.name.clone()).or_insert(0);
+            *count += 1;
+            let test_id = match *count {
+                1 => format!("{}::{}", id, test.name),
+                n => format!("{}::{}#{}", id, test.name, n),
+            };
+            TestCase {
+                id: test_id,
+                name: test.name,
+                file: file.to_string_lossy().to_string(),
+                line: test.line,
+                status: if test.skipped { "skipped" } else { "pending" }.to_string(),
+                message: None,
+                duration_ms: None,
+                flaky: false,
+            }
+        })
+        .collect();
+
+    TestSuite {
+        name: id.clone(),
+        id,
+        tests,
+        file: file.to_string_lossy().to_string(),
+        framework: framework.to_string(),
+    }
+}
+
+/// Run workspace walks, parses and file IO on the blocking pool, off the async runtime
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Result<T, String> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| format!("Test görevi başarısız: {}", e))
+}
+
+/// One suite per file of `workspace` that holds tests, sorted by id
+fn discover_suites(workspace: &Path) -> Vec<TestSuite> {
+    let parser = TreeSitterParser::new();
+    let mut suites = Vec::new();
+    for file in collect_workspace_files(workspace) {
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let Some(framework) = test_framework(&file, &content, workspace) else {
+            continue;
+        };
+        match find_tests(&parser, &file.to_string_lossy(), &content) {
+            Ok(found) if !found.is_empty() => {
+                suites.push(build_suite(suite_id(workspace, &file), &file, framework, found));
+            }
+            Ok(_) => {}
+            Err(e) => warn!("⚠️ Test taraması atlandı [{}]: {}", file.display(), e),
+        }
+    }
+    suites.sort_by(|a, b| a.id.cmp(&b.id));
+    suites
+}
+
+/// Find the tests of every supported framework under `path`, one suite per file
 #[tauri::command]
 pub async fn scan_workspace_tests(path: String) -> Result<Vec<TestSuite>, String> {
-    // Mock testleri döndürelim
-    let mock_suites = vec![
-        TestSuite {
-            id: "suite_1".to_string(),
-            name: "AppComponent.spec.ts".to_string(),
-            tests: vec![
-                TestCase {
-                    id: "test_1_1".to_string(),
-                    name: "should create the app".to_string(),
-                    file: path.clone() + "/AppComponent.spec.ts",
-                    line: 12,
-                    status: "pending".to_string(),
-                    message: None,
-                },
-                TestCase {
-                    id: "test_1_2".to_string(),
-                    name: "should render title".to_string(),
-                    file: path.clone() + "/AppComponent.spec.ts",
-                    line: 18,
-                    status: "pending".to_string(),
-                    message: None,
-                },
-            ],
-        },
-        TestSuite {
-            id: "suite_2".to_string(),
-            name: "Utils.test.ts".to_string(),
-            tests: vec![
-                TestCase {
-                    id: "test_2_1".to_string(),
-                    name: "formatDate() works correctly".to_string(),
-                    file: path.clone() + "/Utils.test.ts",
-                    line: 5,
-                    status: "pending".to_string(),
-                    message: None,
-                },
-            ],
-        },
-    ];
-    Ok(mock_suites)
+    let workspace = Path::new(&path);
+    if !workspace.is_dir() {
+        return Err(format!("Klasör bulunamadı: {}", path));
+    }
+    info!("🧪 Testler aranıyor: {}", path);
+
+    let root = workspace.to_path_buf();
+    let suites = blocking(move || discover_suites(&root)).await?;
+    info!("✅ {} test dosyası, {} test bulundu", suites.len(), suites.iter().map(|s| s.tests.len()).sum::<usize>());
+    Ok(suites)
+}
+
+/// One result or progress update from a runner, keyed by the runner's own test name
+#[derive(Debug, Clone, PartialEq)]
+struct RunnerEvent {
+    name: String,
+    status: String, // "running", "passed", "failed", "skipped"
+    duration_ms: Option<u64>,
+    message: Option<String>,
+}
+
+impl RunnerEvent {
+    fn new(name: impl Into<String>, status: &str) -> Self {
+        Self { name: name.into(), status: status.to_string(), duration_ms: None, message: None }
+    }
+}
+
+/// Machine-readable output a runner is asked for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReportFormat {
+    /// libtest's stable text output: a `test <name> ... ok` line per test, failure output after
+    Libtest,
+    /// `go test -json`, one event per stdout line
+    GoTestJson,
+    /// Jest `--json` report; Vitest's json reporter writes the same shape
+    JestJson,
+    /// pytest `--junit-xml`
+    JunitXml,
+}
+
+impl ReportFormat {
+    /// Results arrive on stdout while running rather than in a report file at the end
+    fn is_streamed(self) -> bool {
+        matches!(self, Self::Libtest | Self::GoTestJson)
+    }
+}
+
+/// How to run one suite
+#[derive(Debug)]
+struct RunPlan {
+    program: String,
+    args: Vec<String>,
+    cwd: PathBuf,
+    env: Vec<(String, String)>,
+    format: ReportFormat,
+    report_file: Option<PathBuf>,
+    /// Name the runner reports for each test, by test id
+    runner_names: HashMap<String, String>,
+}
+
+/// Lines of runner output kept for the error message when a run produces no results
+const OUTPUT_TAIL_LINES: usize = 40;
+
+fn seconds_to_ms(seconds: f64) -> u64 {
+    (seconds * 1000.0).round() as u64
+}
+
+/// `test_add[1]` -> `test_add`
+fn strip_parameters(name: &str) -> &str {
+    match name.find('[') {
+        Some(index) if name.ends_with(']') => &name[..index],
+        _ => name,
+    }
+}
+
+/// Nearest directory between `file` and `workspace` holding `marker`, the workspace otherwise
+fn project_root(file: &Path, workspace: &Path, marker: &str) -> PathBuf {
+    for dir in file.ancestors().skip(1) {
+        if dir.join(marker).is_file() {
+            return dir.to_path_buf();
+        }
+        if dir == workspace {
+            break;
+        }
+    }
+    workspace.to_path_buf()
+}
+
+/// `cargo test` target flags for a file of `package`, and the module prefix libtest puts on its tests
+fn cargo_target(package: &Path, file: &Path) -> (Vec<String>, String) {
+    let parts: Vec<String> = file.strip_prefix(package)
+        .unwrap_or(file)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let module_prefix = |parts: &[String]| -> String {
+        parts.iter()
+            .map(|part| part.strip_suffix(".rs").unwrap_or(part))
+            .filter(|part| !matches!(*part, "lib" | "main" | "mod"))
+            .map(|part| format!("{}::", part))
+            .collect()
+    };
+    let target_name = |part: &String| part.trim_end_matches(".rs").to_string();
+
+    match parts.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["tests", _, ..] => (vec!["--test".to_string(), target_name(&parts[1])], module_prefix(&parts[2..])),
+        ["src", "bin", _, ..] => (vec!["--bin".to_string(), target_name(&parts[2])], module_prefix(&parts[3..])),
+        ["src", rest @ ..] => {
+            let flag = if rest.first() == Some(&"main.rs") || !package.join("src").join("lib.rs").is_file() {
+                "--bins"
+            } else {
+                "--lib"
+            };
+            (vec![flag.to_string()], module_prefix(&parts[1..]))
+        }
+        _ => (Vec::new(), String::new()),
+    }
+}
+
+/// `whole_file` is false when `suite` only holds the tests picked for this run
+fn plan_run(workspace: &Path, suite: &TestSuite, whole_file: bool) -> Result<RunPlan, String> {
+    let file = Path::new(&suite.file);
+    let file_arg = suite.file.clone();
+    let name_pattern = format!("--testNamePattern={}", js_name_pattern(suite.tests.iter().map(|t| t.name.as_str())));
+    let report_path = |extension: &str| {
+        std::env::temp_dir().join(format!("corex_test_report_{}.{}", uuid::Uuid::new_v4(), extension))
+    };
+    let runner_names = |prefix: &str| -> HashMap<String, String> {
+        suite.tests.iter().map(|t| (t.id.clone(), format!("{}{}", prefix, t.name))).collect()
+    };
+    let unique_names = |names: &HashMap<String, String>| -> Vec<String> {
+        let mut names: Vec<String> = names.values().cloned().collect();
+        names.sort();
+        names.dedup();
+        names
+    };
+
+    let plan = match suite.framework.as_str() {
+        "cargo" => {
+            let package = project_root(file, workspace, "Cargo.toml");
+            let (target, prefix) = cargo_target(&package, file);
+            let runner_names = runner_names(&prefix);
+            let mut args = vec!["test".to_string()];
+            args.extend(target);
+            args.extend(["--", "--exact"].map(String::from));
+            args.extend(unique_names(&runner_names));
+            RunPlan {
+                program: "cargo".to_string(),
+                args,
+                cwd: package,
+                env: Vec::new(),
+                format: ReportFormat::Libtest,
+                report_file: None,
+                runner_names,
+            }
+        }
+        "jest" => {
+            let report = report_path("json");
+            let mut args = vec![
+                "jest".to_string(),
+                "--json".to_string(),
+                format!("--outputFile={}", report.display()),
+                "--verbose".to_string(),
+                "--runTestsByPath".to_string(),
+                file_arg,
+            ];
+            if !whole_file {
+                args.push(name_pattern);
+            }
+            RunPlan {
+                program: "npx".to_string(),
+                args,
+                cwd: project_root(file, workspace, "package.json"),
+                env: Vec::new(),
+                format: ReportFormat::JestJson,
+                report_file: Some(report),
+                runner_names: runner_names(""),
+            }
+        }
+        "vitest" => {
+            let report = report_path("json");
+            let mut args = vec![
+                "vitest".to_string(),
+                "run".to_string(),
+                file_arg,
+                "--reporter=verbose".to_string(),
+                "--reporter=json".to_string(),
+                format!("--outputFile={}", report.display()),
+            ];
+            if !whole_file {
+                args.push(name_pattern);
+            }
+            RunPlan {
+                program: "npx".to_string(),
+                args,
+                cwd: project_root(file, workspace, "package.json"),
+                env: Vec::new(),
+                format: ReportFormat::JestJson,
+                report_file: Some(report),
+                runner_names: runner_names(""),
+            }
+        }
+        "pytest" => {
+            let report = report_path("xml");
+            let mut args = vec!["-m".to_string(), "pytest".to_string()];
+            match whole_file {
+                true => args.push(file_arg),
+                // Node ids: file.py::TestCart::test_add
+                false => args.extend(unique_names(&runner_names("")).into_iter().map(|name| format!("{}::{}", file_arg, name))),
+            }
+            args.extend(["-v".to_string(), format!("--junit-xml={}", report.display())]);
+            RunPlan {
+                program: "python".to_string(),
+                args,
+                cwd: workspace.to_path_buf(),
+                env: Vec::new(),
+                format: ReportFormat::JunitXml,
+                report_file: Some(report),
+                runner_names: runner_names(""),
+            }
+        }
+        "go" => {
+            let runner_names = runner_names("");
+            RunPlan {
+                program: "go".to_string(),
+                args: vec![
+                    "test".to_string(),
+                    "-json".to_string(),
+                    "-count=1".to_string(),
+                    "-run".to_string(),
+                    format!("^({})$", unique_names(&runner_names).join("|")),
+                    ".".to_string(),
+                ],
+                cwd: file.parent().unwrap_or(workspace).to_path_buf(),
+                env: Vec::new(),
+                format: ReportFormat::GoTestJson,
+                report_file: None,
+                runner_names,
+            }
+        }
+        other => return Err(format!("Desteklenmeyen test çalıştırıcısı: {}", other)),
+    };
+    Ok(plan)
+}
+
+/// Prepares the plan to run in the workspace's dev container: the report file moves to
+/// `.corex/`, which the container sees through the workspace mount
+fn in_container(mut plan: RunPlan, workspace: &Path) -> Result<RunPlan, String> {
+    if let Some(report) = plan.report_file.take() {
+        let dir = workspace.join(".corex");
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Rapor dizini oluşturulamadı: {}", e))?;
+        let moved = dir.join(report.file_name().unwrap_or_default());
+        let (from, to) = (report.display().to_string(), moved.display().to_string());
+        for arg in &mut plan.args {
+            *arg = arg.replace(&from, &to);
+        }
+        plan.report_file = Some(moved);
+    }
+    Ok(plan)
+}
+
+/// Anchored regex matching the Jest/Vitest full names; `it.each` placeholders match anything
+fn js_name_pattern<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    let mut alternatives: Vec<String> = names
+        .map(|name| {
+            let mut escaped = String::with_capacity(name.len());
+            let mut chars = name.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '%' if chars.peek().is_some_and(|n| "sdifjop#".contains(*n)) => {
+                        chars.next();
+                        escaped.push_str(".*");
+                    }
+                    '\\' | '^' | '$' | '.' | '|' | '?' | '*' | '+' | '(' | ')' | '[' | ']' | '{' | '}' | '/' => {
+                        escaped.push('\\');
+                        escaped.push(c);
+                    }
+                    c => escaped.push(c),
+                }
+            }
+            escaped
+        })
+        .collect();
+    alternatives.sort();
+    alternatives.dedup();
+    format!("^(?:{})$", alternatives.join("|"))
+}
+
+/// One line of libtest output. A failed test's captured output comes after all results in a
+/// `---- <name> stdout ----` section; `failure` collects the section being read and it is
+/// reported as a second `failed` event, with the output as message, once the section ends.
+fn parse_libtest_line(line: &str, failure: &mut Option<(String, String)>) -> Option<RunnerEvent> {
+    let line = line.trim_end();
+    let section_end = line.starts_with("---- ") || line == "failures:" || line.starts_with("test result:");
+    if section_end {
+        let finished = failure.take().map(|(name, output)| {
+            let mut event = RunnerEvent::new(name, "failed");
+            event.message = Some(output.trim().to_string()).filter(|text| !text.is_empty());
+            event
+        });
+        *failure = line.strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+            .map(|name| (name.to_string(), String::new()));
+        return finished;
+    }
+    if let Some((_, output)) = failure {
+        output.push_str(line);
+        output.push('\n');
+        return None;
+    }
+
+    // test cart::tests::adds ... ok
+    let (name, result) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+    let status = match result {
+        "ok" => "passed",
+        "FAILED" => "failed",
+        result if result.starts_with("ignored") => "skipped",
+        _ => return None,
+    };
+    Some(RunnerEvent::new(name, status))
 }
 
+/// One line of `go test -json`; output is collected per test so a failure carries its log
+fn parse_go_line(line: &str, output: &mut HashMap<String, String>) -> Option<RunnerEvent> {
+    let value: Value = serde_json::from_str(line.trim()).ok()?;
+    let test = value["Test"].as_str()?;
+    // Subtests are reported through their parent
+    let parent = test.split('/').next().unwrap_or(test);
+    let action = value["Action"].as_str()?;
+    if action == "output" {
+        output.entry(parent.to_string()).or_default().push_str(value["Output"].as_str().unwrap_or(""));
+        return None;
+    }
+    if parent != test {
+        return None;
+    }
+    let status = match action {
+        "run" => "running",
+        "pass" => "passed",
+        "fail" => "failed",
+        "skip" => "skipped",
+        _ => return None,
+    };
+    let mut event = RunnerEvent::new(test, status);
+    event.duration_ms = value["Elapsed"].as_f64().map(seconds_to_ms);
+    if status != "running" {
+        let log = output.remove(test).unwrap_or_default();
+        if status == "failed" && !log.trim().is_empty() {
+            event.message = Some(log.trim_end().to_string());
+        }
+    }
+    Some(event)
+}
+
+/// Per-test lines of Jest/Vitest `verbose` and pytest `-v` output; only used for progress
+fn parse_progress_line(format: ReportFormat, line: &str) -> Option<RunnerEvent> {
+    let line = line.trim();
+    match format {
+        // tests/test_cart.py::TestCart::test_add PASSED   [ 50%]
+        ReportFormat::JunitXml => {
+            let (node_id, rest) = line.split_once(' ')?;
+            let (_, name) = node_id.split_once("::")?;
+            let status = match rest.split_whitespace().next()? {
+                "PASSED" | "XFAIL" => "passed",
+                "FAILED" | "ERROR" | "XPASS" => "failed",
+                "SKIPPED" => "skipped",
+                _ => return None,
+            };
+            Some(RunnerEvent::new(strip_parameters(name), status))
+        }
+        // Jest: "✓ adds items (3 ms)", Vitest: "✓ src/cart.test.ts > Cart > adds items 3ms"
+        ReportFormat::JestJson => {
+            let mut chars = line.chars();
+            let status = match chars.next()? {
+                '✓' | '√' => "passed",
+                '✕' | '×' => "failed",
+                '○' | '↓' => "skipped",
+                _ => return None,
+            };
+            let mut title = chars.as_str().trim();
+            if title.ends_with("ms)") {
+                title = title.rfind(" (").map(|index| &title[..index]).unwrap_or(title);
+            } else if let Some((rest, last)) = title.rsplit_once(' ') {
+                if last.strip_suffix("ms").is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())) {
+                    title = rest;
+                }
+            }
+            // Vitest's per-file summary line
+            if title.ends_with(" tests)") || title.ends_with(" test)") {
+                return None;
+            }
+            let name = match title.split_once(" > ") {
+                Some((_file, path)) => path.replace(" > ", " "),
+                None => title.to_string(),
+            };
+            Some(RunnerEvent::new(name, status))
+        }
+        ReportFormat::Libtest | ReportFormat::GoTestJson => None,
+    }
+}
+
+/// Jest `--json` / Vitest json reporter output
+fn parse_jest_report(report: &str) -> Result<Vec<RunnerEvent>, String> {
+    let value: Value = serde_json::from_str(report).map_err(|e| format!("Test raporu okunamadı: {}", e))?;
+    let mut events = Vec::new();
+    for file in value["testResults"].as_array().into_iter().flatten() {
+        for assertion in file["assertionResults"].as_array().into_iter().flatten() {
+            let Some(name) = assertion["fullName"].as_str() else {
+                continue;
+            };
+            let status = match assertion["status"].as_str() {
+                Some("passed") => "passed",
+                Some("failed") => "failed",
+                _ => "skipped",
+            };
+            let mut event = RunnerEvent::new(name.trim(), status);
+            event.duration_ms = assertion["duration"].as_f64().map(|ms| ms.round() as u64);
+            let failures: Vec<&str> = assertion["failureMessages"].as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            if !failures.is_empty() {
+                event.message = Some(failures.join("\n"));
+            }
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum XmlEvent {
+    Start { name: String, attributes: HashMap<String, String>, empty: bool },
+    End(String),
+    Text(String),
+}
+
+/// `&lt;`, `&amp;`, `&#10;`, `&#x1F;`... Unknown entities are left as they are
+fn xml_unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let c = match &rest[1..semi] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                entity => match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => entity.strip_prefix('#').and_then(|n| n.parse().ok()),
+                }
+                .and_then(char::from_u32),
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn xml_attributes(mut text: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    while let Some((name, rest)) = text.split_once('=') {
+        let rest = rest.trim_start();
+        let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(end) = rest[1..].find(quote) else {
+            break;
+        };
+        attributes.insert(name.trim().to_string(), xml_unescape(&rest[1..end + 1]));
+        text = &rest[end + 2..];
+    }
+    attributes
+}
+
+/// Just enough XML for report files: tags, attributes, text, CDATA and entities; no DTDs or namespaces
+fn xml_events(xml: &str) -> Vec<XmlEvent> {
+    let mut events = Vec::new();
+    let push_text = |events: &mut Vec<XmlEvent>, text: &str| {
+        if !text.trim().is_empty() {
+            events.push(XmlEvent::Text(xml_unescape(text)));
+        }
+    };
+
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        push_text(&mut events, &rest[..start]);
+        rest = &rest[start..];
+        if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let end = body.find("]]>").unwrap_or(body.len());
+            events.push(XmlEvent::Text(body[..end].to_string()));
+            rest = body.get(end + 3..).unwrap_or("");
+        } else if let Some(body) = rest.strip_prefix("<!--") {
+            rest = body.find("-->").map(|end| &body[end + 3..]).unwrap_or("");
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = rest.find('>').map(|end| &rest[end + 1..]).unwrap_or("");
+        } else {
+            // '>' may appear inside quoted attribute values
+            let mut quote = None;
+            let end = rest.char_indices().skip(1).find(|&(_, c)| {
+                match quote {
+                    Some(q) if c == q => quote = None,
+                    Some(_) => {}
+                    None if c == '"' || c == '\'' => quote = Some(c),
+                    None => return c == '>',
+                }
+                false
+            });
+            let Some((end, _)) = end else {
+                break;
+            };
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+            if let Some(name) = tag.strip_prefix('/') {
+                events.push(XmlEvent::End(name.trim().to_string()));
+            } else {
+                let empty = tag.ends_with('/');
+                let tag = tag.trim_end_matches('/');
+                let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                events.push(XmlEvent::Start {
+                    name: name.to_string(),
+                    attributes: xml_attributes(attributes),
+                    empty,
+                });
+            }
+        }
+    }
+    push_text(&mut events, rest);
+    events
+}
+
+/// pytest `--junit-xml`, with names turned back into discovery names (`TestCart::test_add`)
+fn parse_junit_report(xml: &str) -> Vec<RunnerEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<RunnerEvent> = None;
+    let mut in_failure = false;
+    for event in xml_events(xml) {
+        match event {
+            XmlEvent::Start { name, attributes, empty } => match name.as_str() {
+                "testcase" => {
+                    let class = attributes.get("classname")
+                        .and_then(|c| c.rsplit('.').next())
+                        .filter(|c| c.starts_with("Test"));
+                    let test = strip_parameters(attributes.get("name").map(String::as_str).unwrap_or(""));
+                    let name = match class {
+                        Some(class) => format!("{}::{}", class, test),
+                        None => test.to_string(),
+                    };
+                    let mut case = RunnerEvent::new(name, "passed");
+                    case.duration_ms = attributes.get("time").and_then(|t| t.parse().ok()).map(seconds_to_ms);
+                    if empty {
+                        events.push(case);
+                    } else {
+                        current = Some(case);
+                    }
+                }
+                "failure" | "error" => {
+                    if let Some(case) = current.as_mut() {
+                        case.status = "failed".to_string();
+                        case.message = attributes.get("message").cloned();
+                        in_failure = !empty;
+                    }
+                }
+                "skipped" => {
+                    if let Some(case) = current.as_mut() {
+                        case.status = "skipped".to_string();
+                        case.message = attributes.get("message").cloned();
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::Text(text) if in_failure => {
+                // The element text is the whole traceback, which beats the one-line message
+                if let Some(case) = current.as_mut() {
+                    case.message = Some(text.trim().to_string());
+                }
+            }
+            XmlEvent::End(name) => match name.as_str() {
+                "testcase" => events.extend(current.take()),
+                "failure" | "error" => in_failure = false,
+                _ => {}
+            },
+            XmlEvent::Text(_) => {}
+        }
+    }
+    events
+}
+
+/// Fold runner results into the suite's tests; on whole-file runs, results for tests
+/// discovery did not see are appended
+fn merge_results(
+    suite: &TestSuite,
+    runner_names: &HashMap<String, String>,
+    events: &[RunnerEvent],
+    run_error: Option<&str>,
+    whole_file: bool,
+) -> TestRunResult {
+    // Parametrized rows report under one name: durations add up and a failure wins
+    let mut results: HashMap<&str, RunnerEvent> = HashMap::new();
+    let mut order = Vec::new();
+    for event in events.iter().filter(|e| e.status != "running") {
+        match results.get_mut(event.name.as_str()) {
+            Some(existing) => {
+                if let Some(ms) = event.duration_ms {
+                    existing.duration_ms = Some(existing.duration_ms.unwrap_or(0) + ms);
+                }
+                if event.status == "failed" || existing.status == "skipped" {
+                    existing.status = event.status.clone();
+                    existing.message = event.message.clone();
+                }
+            }
+            None => {
+                order.push(event.name.as_str());
+                results.insert(event.name.as_str(), event.clone());
+            }
+        }
+    }
+
+    let mut matched = std::collections::HashSet::new();
+    let mut test_cases: Vec<TestCase> = suite.tests.iter()
+        .map(|test| {
+            let mut case = test.clone();
+            let runner_name = runner_names.get(&test.id).map(String::as_str).unwrap_or(&test.name);
+            match (results.get(runner_name), run_error) {
+                (Some(result), _) => {
+                    matched.insert(runner_name);
+                    case.status = result.status.clone();
+                    case.message = result.message.clone();
+                    case.duration_ms = result.duration_ms;
+                }
+                (None, Some(error)) if test.status != "skipped" => {
+                    case.status = "failed".to_string();
+                    case.message = Some(error.to_string());
+                }
+                (None, _) => {
+                    case.status = "skipped".to_string();
+                    if test.status != "skipped" {
+                        case.message = Some("Çalıştırıcı bu test için sonuç bildirmedi".to_string());
+                    }
+                }
+            }
+            case
+        })
+        .collect();
+
+    // A filtered run also reports the tests it left out, which say nothing new
+    let unmatched = order.into_iter().filter(|name| whole_file && !matched.contains(name));
+    for name in unmatched {
+        let result = &results[name];
+        test_cases.push(TestCase {
+            id: format!("{}::{}", suite.id, name),
+            name: name.to_string(),
+            file: suite.file.clone(),
+            line: 0,
+            status: result.status.clone(),
+            message: result.message.clone(),
+            duration_ms: result.duration_ms,
+            flaky: false,
+        });
+    }
+
+    let count = |status: &str| test_cases.iter().filter(|t| t.status == status).count() as u32;
+    let (passed, failed, skipped) = (count("passed"), count("failed"), count("skipped"));
+    TestRunResult {
+        success: failed == 0 && run_error.is_none(),
+        total: test_cases.len() as u32,
+        passed,
+        failed,
+        skipped,
+        test_cases,
+    }
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, suite: &TestSuite, plan: &RunPlan, event: &RunnerEvent) {
+    let test_id = plan.runner_names.iter()
+        .filter(|(_, name)| **name == event.name)
+        .map(|(id, _)| id)
+        .min();
+    if let Err(e) = app.emit("test-progress", json!({
+        "suite_id": suite.id,
+        "test_id": test_id,
+        "name": event.name,
+        "status": event.status,
+        "duration_ms": event.duration_ms,
+        "message": event.message
+    })) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+}
+
+/// Start the plan's runner, on the host or in the dev container, sending every output line
+/// to `lines`. Returns the exit code, `None` when the runner was killed by a signal.
+async fn run_runner(
+    plan: &RunPlan,
+    session: Option<&DevContainerSession>,
+    lines: mpsc::UnboundedSender<String>,
+) -> Result<Option<i64>, String> {
+    if let Some(session) = session {
+        let code = session
+            .exec(&DockerClient::from_env(), &plan.program, &plan.args, &plan.cwd, &plan.env, |_, line| {
+                let _ = lines.send(line.to_string());
+            })
+            .await
+            .map_err(|e| format!("Test çalıştırıcısı başlatılamadı ({}): {}", plan.program, e))?;
+        return Ok(Some(code));
+    }
+
+    let mut child = Command::new(&plan.program)
+        .args(&plan.args)
+        .current_dir(&plan.cwd)
+        .envs(plan.env.iter().cloned())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Test çalıştırıcısı başlatılamadı ({}): {}", plan.program, e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to open stderr")?;
+    for reader in [Box::new(stdout) as Box<dyn AsyncRead + Unpin + Send>, Box::new(stderr)] {
+        let sender = lines.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(lines);
+
+    let status = child.wait().await.map_err(|e| format!("Test çalıştırıcısı beklenemedi: {}", e))?;
+    Ok(status.code().map(i64::from))
+}
+
+/// Run the plan, emitting `test-progress` as lines come in. Returns the results and,
+/// when the runner produced none and exited with an error, the tail of its output.
+async fn execute_plan<R: Runtime>(
+    app: &AppHandle<R>,
+    suite: &TestSuite,
+    plan: &RunPlan,
+    session: Option<&DevContainerSession>,
+) -> Result<(Vec<RunnerEvent>, Option<String>), String> {
+    let (sender, mut lines) = mpsc::unbounded_channel::<String>();
+    let runner = run_runner(plan, session, sender);
+
+    let mut results = Vec::new();
+    let mut go_output = HashMap::new();
+    let mut libtest_failure = None;
+    let mut tail = VecDeque::with_capacity(OUTPUT_TAIL_LINES);
+    let collect = async {
+        while let Some(line) = lines.recv().await {
+            let event = match plan.format {
+                ReportFormat::Libtest => parse_libtest_line(&line, &mut libtest_failure),
+                ReportFormat::GoTestJson => parse_go_line(&line, &mut go_output),
+                format => parse_progress_line(format, &line),
+            };
+            match event {
+                Some(event) => {
+                    emit_progress(app, suite, plan, &event);
+                    if plan.format.is_streamed() {
+                        results.push(event);
+                    }
+                }
+                None => {
+                    // go test -json wraps build output in events too
+                    let text = serde_json::from_str::<Value>(&line).ok()
+                        .and_then(|v| v["Output"].as_str().map(|s| s.trim_end().to_string()))
+                        .unwrap_or(line);
+                    if tail.len() == OUTPUT_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(text);
+                }
+            }
+        }
+    };
+    let (exit_code, ()) = tokio::join!(runner, collect);
+    let exit_code = exit_code?;
+
+    if let Some(report_file) = &plan.report_file {
+        match std::fs::read_to_string(report_file) {
+            Ok(report) => {
+                results = match plan.format {
+                    ReportFormat::JunitXml => parse_junit_report(&report),
+                    _ => parse_jest_report(&report)?,
+                };
+            }
+            Err(e) => warn!("⚠️ Test raporu bulunamadı [{}]: {}", report_file.display(), e),
+        }
+        let _ = std::fs::remove_file(report_file);
+    }
+
+    let finished = results.iter().any(|e| e.status != "running");
+    let run_error = (!finished && exit_code != Some(0)).then(|| {
+        let output: Vec<String> = tail.into_iter().collect();
+        match output.is_empty() {
+            true => format!("{} çıkış kodu {:?} ile sonlandı", plan.program, exit_code),
+            false => output.join("\n"),
+        }
+    });
+    Ok((results, run_error))
+}
+
+/// Rediscover a suite from its id, so the run matches the file as it is now
+///
+/// The id comes from the frontend; only plain workspace-relative paths are accepted.
+fn load_suite(workspace: &Path, suite_id: &str) -> Result<TestSuite, String> {
+    let relative = Path::new(suite_id);
+    if relative.is_absolute() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("Geçersiz test dosyası: {}", suite_id));
+    }
+    let file = workspace.join(relative);
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| format!("Test dosyası okunamadı [{}]: {}", file.display(), e))?;
+    let framework = test_framework(&file, &content, workspace)
+        .ok_or_else(|| format!("Test dosyası değil: {}", suite_id))?;
+    let found = find_tests(&TreeSitterParser::new(), &file.to_string_lossy(), &content)?;
+    Ok(build_suite(suite_id.to_string(), &file, framework, found))
+}
+
+/// Last known result of every test in a workspace, persisted in `.corex/test-results.json`
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TestResultCache {
+    /// test id -> last result
+    #[serde(default)]
+    pub results: BTreeMap<String, TestCase>,
+}
+
+impl TestResultCache {
+    fn file(workspace: &Path) -> PathBuf {
+        workspace.join(".corex").join("test-results.json")
+    }
+
+    pub fn load(workspace: &Path) -> Result<Self, String> {
+        let file = Self::file(workspace);
+        if !file.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&file)
+            .map_err(|e| format!("Test sonuçları okunamadı: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Test sonuç dosyası bozuk ({}): {}", file.display(), e))
+    }
+
+    pub fn save(&self, workspace: &Path) -> Result<(), String> {
+        let file = Self::file(workspace);
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Klasör oluşturulamadı: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&file, content).map_err(|e| format!("Test sonuçları yazılamadı: {}", e))
+    }
+
+    /// Load, change and save the cache; failures are only logged
+    fn update(workspace: &Path, change: impl FnOnce(&mut Self)) {
+        let mut cache = Self::load(workspace).unwrap_or_else(|e| {
+            warn!("⚠️ {}", e);
+            Self::default()
+        });
+        change(&mut cache);
+        if let Err(e) = cache.save(workspace) {
+            warn!("⚠️ {}", e);
+        }
+    }
+
+    /// Store the results of a run over `selected` tests of a suite, or all of it when `None`
+    ///
+    /// Earlier results in that scope are dropped first, so tests that were removed or
+    /// renamed since do not linger as failed.
+    fn record(&mut self, suite_id: &str, selected: Option<&BTreeSet<String>>, result: &TestRunResult) {
+        match selected {
+            Some(selected) => self.results.retain(|id, _| !selected.contains(id)),
+            None => {
+                let prefix = format!("{}::", suite_id);
+                self.results.retain(|id, _| !id.starts_with(&prefix));
+            }
+        }
+        for case in &result.test_cases {
+            self.results.insert(case.id.clone(), case.clone());
+        }
+    }
+
+    /// Failed test ids grouped by suite id
+    fn failed(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut failed: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for case in self.results.values().filter(|c| c.status == "failed") {
+            if let Some((suite, _)) = case.id.split_once("::") {
+                failed.entry(suite.to_string()).or_default().insert(case.id.clone());
+            }
+        }
+        failed
+    }
+}
+
+/// Runs kept in a workspace's history; older ones are dropped
+const MAX_HISTORY_RUNS: usize = 1000;
+
+/// One test's outcome in a recorded run
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TestRunEntry {
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+}
+
+/// A runner invocation as kept in `.corex/test-history.jsonl`, one JSON object per line
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TestRunRecord {
+    /// Unix time in milliseconds
+    pub timestamp: i64,
+    pub suite_id: String,
+    /// HEAD when the run started; `None` outside a git repository
+    pub commit: Option<String>,
+    /// Hash of `git diff HEAD`, `None` for a clean tree. Runs only count as the same code
+    /// when both this and `commit` match.
+    #[serde(default)]
+    pub changes: Option<String>,
+    pub tests: Vec<TestRunEntry>,
+}
+
+impl TestRunRecord {
+    fn new(suite_id: &str, commit: Option<String>, changes: Option<String>, result: &TestRunResult) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            suite_id: suite_id.to_string(),
+            commit,
+            changes,
+            tests: result.test_cases.iter()
+                .filter(|t| t.status != "pending")
+                .map(|t| TestRunEntry { id: t.id.clone(), status: t.status.clone(), duration_ms: t.duration_ms })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SlowTest {
+    pub id: String,
+    pub runs: u32,
+    pub average_ms: u64,
+    pub max_ms: u64,
+    pub last_ms: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlakyTest {
+    pub id: String,
+    /// Times the result went from passed to failed or back on unchanged code
+    pub flips: u32,
+    pub runs: u32,
+    /// Commits the flips were seen on
+    pub commits: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FailingTest {
+    pub id: String,
+    pub failures: u32,
+    pub runs: u32,
+    /// Unix time in milliseconds
+    pub last_failure: i64,
+}
+
+/// Results of one local calendar day
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FailureTrend {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub runs: u32,
+    pub results: u32,
+    pub failed: u32,
+    pub failure_rate: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TestInsights {
+    pub runs: usize,
+    pub slowest: Vec<SlowTest>,
+    pub flakiest: Vec<FlakyTest>,
+    pub most_failing: Vec<FailingTest>,
+    pub trends: Vec<FailureTrend>,
+}
+
+/// Every recorded run of a workspace, oldest first
+#[derive(Debug, Default)]
+pub struct TestHistory {
+    pub runs: Vec<TestRunRecord>,
+}
+
+impl TestHistory {
+    fn file(workspace: &Path) -> PathBuf {
+        workspace.join(".corex").join("test-history.jsonl")
+    }
+
+    /// Load the history; a line that does not parse is skipped, not fatal
+    pub fn load(workspace: &Path) -> Result<Self, String> {
+        let file = Self::file(workspace);
+        if !file.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&file)
+            .map_err(|e| format!("Test geçmişi okunamadı: {}", e))?;
+        let runs = content.lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| warn!("⚠️ Test geçmişinde bozuk satır atlandı: {}", e))
+                    .ok()
+            })
+            .collect();
+        Ok(Self { runs })
+    }
+
+    /// Append a run, rewriting the file only when old runs have to go
+    fn append(&mut self, workspace: &Path, record: TestRunRecord) -> Result<(), String> {
+        let file = Self::file(workspace);
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Klasör oluşturulamadı: {}", e))?;
+        }
+        let line = serde_json::to_string(&record).map_err(|e| e.to_string())?;
+        self.runs.push(record);
+
+        if self.runs.len() > MAX_HISTORY_RUNS {
+            self.runs.drain(..self.runs.len() - MAX_HISTORY_RUNS);
+            let mut content = String::new();
+            for run in &self.runs {
+                content.push_str(&serde_json::to_string(run).map_err(|e| e.to_string())?);
+                content.push('\n');
+            }
+            return std::fs::write(&file, content).map_err(|e| format!("Test geçmişi yazılamadı: {}", e));
+        }
+
+        let mut handle = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&file)
+            .map_err(|e| format!("Test geçmişi açılamadı: {}", e))?;
+        writeln!(handle, "{}", line).map_err(|e| format!("Test geçmişi yazılamadı: {}", e))
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&TestRunRecord, &TestRunEntry)> {
+        self.runs.iter().flat_map(|run| run.tests.iter().map(move |test| (run, test)))
+    }
+
+    /// Tests whose result flipped between passed and failed while commit and edits stayed the same
+    pub fn flaky_tests(&self) -> Vec<FlakyTest> {
+        // (test, commit, changes) -> statuses in run order
+        let mut outcomes: HashMap<(&str, &str, Option<&str>), Vec<&str>> = HashMap::new();
+        for (run, test) in self.entries() {
+            let Some(commit) = run.commit.as_deref() else {
+                continue;
+            };
+            if test.status == "passed" || test.status == "failed" {
+                outcomes.entry((test.id.as_str(), commit, run.changes.as_deref()))
+                    .or_default()
+                    .push(test.status.as_str());
+            }
+        }
+
+        let mut flaky: BTreeMap<&str, FlakyTest> = BTreeMap::new();
+        for ((id, commit, _), statuses) in outcomes {
+            let flips = statuses.windows(2).filter(|pair| pair[0] != pair[1]).count() as u32;
+            let entry = flaky.entry(id).or_insert_with(|| FlakyTest {
+                id: id.to_string(),
+                flips: 0,
+                runs: 0,
+                commits: Vec::new(),
+            });
+            entry.runs += statuses.len() as u32;
+            if flips > 0 {
+                entry.flips += flips;
+                entry.commits.push(commit.to_string());
+            }
+        }
+
+        let mut flaky: Vec<FlakyTest> = flaky.into_values().filter(|t| t.flips > 0).collect();
+        flaky.iter_mut().for_each(|t| t.commits.sort());
+        flaky.sort_by(|a, b| b.flips.cmp(&a.flips).then_with(|| a.id.cmp(&b.id)));
+        flaky
+    }
+
+    pub fn slowest_tests(&self, limit: usize) -> Vec<SlowTest> {
+        let mut durations: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+        for (_, test) in self.entries().filter(|(_, t)| t.status != "skipped") {
+            if let Some(ms) = test.duration_ms {
+                durations.entry(test.id.as_str()).or_default().push(ms);
+            }
+        }
+        let mut slowest: Vec<SlowTest> = durations.into_iter()
+            .map(|(id, durations)| SlowTest {
+                id: id.to_string(),
+                runs: durations.len() as u32,
+                average_ms: durations.iter().sum::<u64>() / durations.len() as u64,
+                max_ms: durations.iter().copied().max().unwrap_or(0),
+                last_ms: durations.last().copied().unwrap_or(0),
+            })
+            .collect();
+        slowest.sort_by(|a, b| b.average_ms.cmp(&a.average_ms).then_with(|| a.id.cmp(&b.id)));
+        slowest.truncate(limit);
+        slowest
+    }
+
+    pub fn most_failing(&self, limit: usize) -> Vec<FailingTest> {
+        let mut tests: BTreeMap<&str, FailingTest> = BTreeMap::new();
+        for (run, test) in self.entries().filter(|(_, t)| t.status != "skipped") {
+            let entry = tests.entry(test.id.as_str()).or_insert_with(|| FailingTest {
+                id: test.id.clone(),
+                failures: 0,
+                runs: 0,
+                last_failure: 0,
+            });
+            entry.runs += 1;
+            if test.status == "failed" {
+                entry.failures += 1;
+                entry.last_failure = entry.last_failure.max(run.timestamp);
+            }
+        }
+        let mut failing: Vec<FailingTest> = tests.into_values().filter(|t| t.failures > 0).collect();
+        failing.sort_by(|a, b| b.failures.cmp(&a.failures).then_with(|| b.last_failure.cmp(&a.last_failure)));
+        failing.truncate(limit);
+        failing
+    }
+
+    /// Failure rate per day, oldest first
+    pub fn failure_trends(&self) -> Vec<FailureTrend> {
+        let mut days: BTreeMap<String, FailureTrend> = BTreeMap::new();
+        for run in &self.runs {
+            let Some(date) = chrono::Local.timestamp_millis_opt(run.timestamp).single() else {
+                continue;
+            };
+            let date = date.format("%Y-%m-%d").to_string();
+            let day = days.entry(date.clone()).or_insert_with(|| FailureTrend {
+                date,
+                runs: 0,
+                results: 0,
+                failed: 0,
+                failure_rate: 0.0,
+            });
+            day.runs += 1;
+            for test in run.tests.iter().filter(|t| t.status != "skipped") {
+                day.results += 1;
+                day.failed += (test.status == "failed") as u32;
+            }
+        }
+        days.into_values()
+            .map(|mut day| {
+                day.failure_rate = if day.results == 0 { 0.0 } else { percent(day.failed, day.results) };
+                day
+            })
+            .collect()
+    }
+}
+
+/// HEAD and a hash of the uncommitted diff; both `None` outside git
+async fn tree_state(workspace: &Path) -> (Option<String>, Option<String>) {
+    let git = |args: &'static [&'static str]| {
+        Command::new("git").args(args).current_dir(workspace).stdin(Stdio::null()).output()
+    };
+    let commit = match git(&["rev-parse", "HEAD"]).await {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        _ => return (None, None),
+    };
+    let changes = match git(&["diff", "HEAD"]).await {
+        Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+            Some(hex::encode(Sha256::digest(&output.stdout))[..16].to_string())
+        }
+        _ => None,
+    };
+    (Some(commit), changes)
+}
+
+/// Add a run to the workspace's history and flag the tests the history shows as flaky
+fn record_history(
+    workspace: &Path,
+    suite_id: &str,
+    (commit, changes): (Option<String>, Option<String>),
+    result: &mut TestRunResult,
+) {
+    let mut history = TestHistory::load(workspace).unwrap_or_else(|e| {
+        warn!("⚠️ {}", e);
+        TestHistory::default()
+    });
+    if let Err(e) = history.append(workspace, TestRunRecord::new(suite_id, commit, changes, result)) {
+        warn!("⚠️ {}", e);
+    }
+    let flaky: BTreeSet<String> = history.flaky_tests().into_iter().map(|t| t.id).collect();
+    for case in &mut result.test_cases {
+        case.flaky = flaky.contains(&case.id);
+    }
+}
+
+/// Sum the results of several runs into one
+fn combine_results(results: Vec<TestRunResult>) -> TestRunResult {
+    let mut combined = TestRunResult {
+        success: true,
+        total: 0,
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        test_cases: Vec::new(),
+    };
+    for result in results {
+        combined.success &= result.success;
+        combined.total += result.total;
+        combined.passed += result.passed;
+        combined.failed += result.failed;
+        combined.skipped += result.skipped;
+        combined.test_cases.extend(result.test_cases);
+    }
+    combined
+}
+
+/// Run a suite, or only the tests of it listed in `selected`, and remember the results
+async fn run_selection<R: Runtime>(
+    app: &AppHandle<R>,
+    workspace: &Path,
+    mut suite: TestSuite,
+    selected: Option<&BTreeSet<String>>,
+) -> Result<TestRunResult, String> {
+    if let Some(selected) = selected {
+        suite.tests.retain(|t| selected.contains(&t.id));
+        if suite.tests.is_empty() {
+            // Discovery no longer finds any of them, so their old results are forgotten
+            let (root, selected) = (workspace.to_path_buf(), selected.clone());
+            let result = combine_results(Vec::new());
+            return blocking(move || {
+                TestResultCache::update(&root, |cache| cache.record(&suite.id, Some(&selected), &result));
+                result
+            })
+            .await;
+        }
+    }
+    let whole_file = selected.is_none();
+    let mut plan = plan_run(workspace, &suite, whole_file)?;
+    let session = DevContainerSession::find(workspace).await;
+    if session.is_some() {
+        plan = in_container(plan, workspace)?;
+    }
+    info!("🧪 Testler çalıştırılıyor [{}]: {} {}", suite.id, plan.program, plan.args.join(" "));
+    let state = tree_state(workspace).await;
+
+    let (events, run_error) = execute_plan(app, &suite, &plan, session.as_ref()).await?;
+    if let Some(error) = &run_error {
+        warn!("⚠️ Test çalıştırıcısı sonuç üretmedi [{}]: {}", suite.id, error.lines().last().unwrap_or(""));
+    }
+    let mut result = merge_results(&suite, &plan.runner_names, &events, run_error.as_deref(), whole_file);
+    info!("✅ {}: {} geçti, {} başarısız, {} atlandı", suite.id, result.passed, result.failed, result.skipped);
+
+    let (root, selected) = (workspace.to_path_buf(), selected.cloned());
+    blocking(move || {
+        record_history(&root, &suite.id, state, &mut result);
+        TestResultCache::update(&root, |cache| cache.record(&suite.id, selected.as_ref(), &result));
+        result
+    })
+    .await
+}
+
+/// `load_suite` on the blocking pool
+async fn load_suite_blocking(workspace: &Path, suite_id: &str) -> Result<TestSuite, String> {
+    let (root, suite_id) = (workspace.to_path_buf(), suite_id.to_string());
+    blocking(move || load_suite(&root, &suite_id)).await?
+}
+
+/// Run a suite with its framework's runner, emitting `test-progress` for each test as it goes
 #[tauri::command]
-pub async fn run_test_suite(suite_id: String) -> Result<TestRunResult, String> {
-    // Simulate test run
-    tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
-    
-    if suite_id == "suite_1" {
-        Ok(TestRunResult {
-            success: false,
-            total: 2,
-            passed: 1,
-            failed: 1,
-            skipped: 0,
-            test_cases: vec![
-                TestCase {
-                    id: "test_1_1".to_string(),
-                    name: "should create the app".to_string(),
-                    file: "/AppComponent.spec.ts".to_string(),
-                    line: 12,
-                    status: "passed".to_string(),
-                    message: None,
-                },
-                TestCase {
-                    id: "test_1_2".to_string(),
-                    name: "should render title".to_string(),
-                    file: "/AppComponent.spec.ts".to_string(),
-                    line: 18,
-                    status: "failed".to_string(),
-                    message: Some("Expected 'CorexAI' to equal 'CoreX'".to_string()),
-                },
-            ]
+pub async fn run_test_suite<R: Runtime>(
+    app: AppHandle<R>,
+    workspace: String,
+    suite_id: String,
+) -> Result<TestRunResult, String> {
+    let workspace = Path::new(&workspace);
+    let suite = load_suite_blocking(workspace, &suite_id).await?;
+    run_selection(&app, workspace, suite, None).await
+}
+
+/// Run one test by its id (`<suite id>::<name>`)
+#[tauri::command]
+pub async fn run_test<R: Runtime>(
+    app: AppHandle<R>,
+    workspace: String,
+    test_id: String,
+) -> Result<TestRunResult, String> {
+    let workspace = Path::new(&workspace);
+    let (suite_id, _) = test_id.split_once("::").ok_or_else(|| format!("Geçersiz test id: {}", test_id))?;
+    let suite = load_suite_blocking(workspace, suite_id).await?;
+    if !suite.tests.iter().any(|t| t.id == test_id) {
+        return Err(format!("Test bulunamadı: {}", test_id));
+    }
+    run_selection(&app, workspace, suite, Some(&BTreeSet::from([test_id]))).await
+}
+
+/// Run again every test whose last recorded result is a failure
+#[tauri::command]
+pub async fn rerun_failed_tests<R: Runtime>(app: AppHandle<R>, workspace: String) -> Result<TestRunResult, String> {
+    let workspace = Path::new(&workspace);
+    let root = workspace.to_path_buf();
+    let failed = blocking(move || TestResultCache::load(&root)).await??.failed();
+    info!("🔁 {} başarısız test yeniden çalıştırılıyor", failed.values().map(BTreeSet::len).sum::<usize>());
+
+    let mut results = Vec::new();
+    let mut gone = Vec::new();
+    for (suite_id, test_ids) in failed {
+        match load_suite_blocking(workspace, &suite_id).await {
+            Ok(suite) => results.push(run_selection(&app, workspace, suite, Some(&test_ids)).await?),
+            // The file is gone or no longer has tests; its old failures go with it
+            Err(e) => {
+                warn!("⚠️ {}", e);
+                gone.push(suite_id);
+            }
+        }
+    }
+    if !gone.is_empty() {
+        let root = workspace.to_path_buf();
+        let empty = combine_results(Vec::new());
+        blocking(move || {
+            TestResultCache::update(&root, |cache| {
+                for suite_id in &gone {
+                    cache.record(suite_id, None, &empty);
+                }
+            })
         })
+        .await?;
+    }
+    Ok(combine_results(results))
+}
+
+/// Workspace-relative paths changed since HEAD: staged, unstaged and untracked
+async fn changed_files(workspace: &Path) -> Result<Vec<String>, String> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain=v1", "-z", "--untracked-files=all"])
+        .current_dir(workspace)
+        .output()
+        .await
+        .map_err(|e| format!("Git status failed: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(parse_git_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `git status --porcelain -z` entries; renames and copies are followed by their old path
+fn parse_git_status(output: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut entries = output.split('\0').filter(|e| e.len() > 3);
+    while let Some(entry) = entries.next() {
+        let (status, path) = entry.split_at(3);
+        files.push(path.to_string());
+        if status.starts_with(['R', 'C']) {
+            entries.next();
+        }
+    }
+    files
+}
+
+/// Suites whose file changed or imports a changed file, directly or not
+fn affected_suites(suites: Vec<TestSuite>, changed: &BTreeSet<String>, graph: &DependencyGraph) -> Vec<TestSuite> {
+    suites.into_iter()
+        .filter(|suite| {
+            changed.contains(&suite.file)
+                || graph.transitive_dependencies(&suite.file).iter().any(|d| changed.contains(d))
+        })
+        .collect()
+}
+
+/// Run every suite affected by the files changed since HEAD, going through the import graph
+///
+/// The graph from `build_dependency_graph` is used when it belongs to this workspace.
+#[tauri::command]
+pub async fn run_affected_tests<R: Runtime>(
+    app: AppHandle<R>,
+    workspace: String,
+    graph_state: State<'_, DependencyGraphState>,
+) -> Result<TestRunResult, String> {
+    let workspace_path = Path::new(&workspace);
+    let changed: BTreeSet<String> = changed_files(workspace_path).await?
+        .into_iter()
+        .map(|path| workspace_path.join(path).to_string_lossy().to_string())
+        .collect();
+    if changed.is_empty() {
+        info!("ℹ️ HEAD'den beri değişiklik yok, çalıştırılacak test yok");
+        return Ok(combine_results(Vec::new()));
+    }
+
+    let root = workspace_path.to_path_buf();
+    let suites = blocking(move || discover_suites(&root)).await?;
+    let cached = graph_state.with_graph(|graph| {
+        (graph.root() == workspace_path).then(|| affected_suites(suites.clone(), &changed, graph))
+    });
+    let affected = match cached {
+        Ok(Some(affected)) => affected,
+        _ => affected_suites(suites, &changed, &graph_for_workspace(workspace_path).await),
+    };
+    info!("🎯 {} değişiklik, {} etkilenen test dosyası", changed.len(), affected.len());
+
+    let mut results = Vec::new();
+    for suite in affected {
+        results.push(run_selection(&app, workspace_path, suite, None).await?);
+    }
+    Ok(combine_results(results))
+}
+
+/// Slowest, flakiest and most failing tests plus the daily failure rate, from the run history
+#[tauri::command]
+pub async fn get_test_insights(workspace: String, limit: Option<usize>) -> Result<TestInsights, String> {
+    let history = blocking(move || TestHistory::load(Path::new(&workspace))).await??;
+    let limit = limit.unwrap_or(10);
+    let mut flakiest = history.flaky_tests();
+    flakiest.truncate(limit);
+    Ok(TestInsights {
+        runs: history.runs.len(),
+        slowest: history.slowest_tests(limit),
+        flakiest,
+        most_failing: history.most_failing(limit),
+        trends: history.failure_trends(),
+    })
+}
+
+/// Last recorded result of every test in the workspace, including from earlier sessions
+#[tauri::command]
+pub async fn get_cached_test_results(workspace: String) -> Result<Vec<TestCase>, String> {
+    let cache = blocking(move || TestResultCache::load(Path::new(&workspace))).await??;
+    Ok(cache.results.into_values().collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CoverageFormat {
+    Lcov,
+    Cobertura,
+    /// `go test -coverprofile`
+    GoProfile,
+}
+
+/// Reports a project may already have lying around, checked before running anything
+const COVERAGE_REPORTS: &[(&str, CoverageFormat)] = &[
+    ("lcov.info", CoverageFormat::Lcov),
+    ("coverage/lcov.info", CoverageFormat::Lcov),
+    ("target/llvm-cov/lcov.info", CoverageFormat::Lcov),
+    ("coverage.xml", CoverageFormat::Cobertura),
+    ("cobertura.xml", CoverageFormat::Cobertura),
+    ("coverage/cobertura-coverage.xml", CoverageFormat::Cobertura),
+];
+
+/// Hits of one file while reports are being read; the same file may show up more than once
+#[derive(Debug, Default, Clone, PartialEq)]
+struct FileHits {
+    lines: BTreeMap<u32, u64>,
+    /// line -> (covered, total)
+    branches: BTreeMap<u32, (u32, u32)>,
+    /// Go cover profile blocks, `start,end` -> (statements, hits); line totals count statements
+    blocks: BTreeMap<String, (u32, u64)>,
+}
+
+impl FileHits {
+    fn add_line(&mut self, line: u32, hits: u64) {
+        *self.lines.entry(line).or_insert(0) += hits;
+    }
+
+    fn add_branches(&mut self, line: u32, covered: u32, total: u32) {
+        let entry = self.branches.entry(line).or_insert((0, 0));
+        entry.0 += covered;
+        entry.1 += total;
+    }
+
+    /// Profiles from several packages repeat a block; its statements are only counted once
+    fn add_block(&mut self, range: &str, statements: u32, hits: u64) {
+        let entry = self.blocks.entry(range.to_string()).or_insert((statements, 0));
+        entry.1 += hits;
+    }
+}
+
+fn percent(hit: u32, found: u32) -> f64 {
+    if found == 0 {
+        return 100.0;
+    }
+    (hit as f64 * 10000.0 / found as f64).round() / 100.0
+}
+
+/// LCOV tracefile: `SF`, `DA` and `BRDA` records
+fn parse_lcov(content: &str) -> BTreeMap<String, FileHits> {
+    let mut files: BTreeMap<String, FileHits> = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for line in content.lines().map(str::trim) {
+        let Some((key, value)) = line.split_once(':') else {
+            if line == "end_of_record" {
+                current = None;
+            }
+            continue;
+        };
+        if key == "SF" {
+            files.entry(value.to_string()).or_default();
+            current = Some(value.to_string());
+            continue;
+        }
+        let Some(hits) = current.as_ref().and_then(|file| files.get_mut(file)) else {
+            continue;
+        };
+        let fields: Vec<&str> = value.split(',').collect();
+        match (key, fields.as_slice()) {
+            ("DA", [line, count, ..]) => {
+                if let (Ok(line), Ok(count)) = (line.parse(), count.parse::<f64>()) {
+                    hits.add_line(line, count as u64);
+                }
+            }
+            // `-` means the block never ran, which still counts as an untaken branch
+            ("BRDA", [line, _block, _branch, taken]) => {
+                if let Ok(line) = line.parse() {
+                    let taken = taken.parse::<u64>().unwrap_or(0) > 0;
+                    hits.add_branches(line, taken as u32, 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    files
+}
+
+/// Cobertura XML (coverage.py, istanbul); relative file names are joined to the first `<source>`
+fn parse_cobertura(xml: &str) -> BTreeMap<String, FileHits> {
+    let mut files: BTreeMap<String, FileHits> = BTreeMap::new();
+    let mut source: Option<String> = None;
+    let mut in_source = false;
+    let mut current: Option<String> = None;
+    for event in xml_events(xml) {
+        match event {
+            XmlEvent::Start { name, attributes, .. } => match name.as_str() {
+                "source" => in_source = source.is_none(),
+                "class" => {
+                    current = attributes.get("filename").map(|filename| {
+                        let relative = !filename.starts_with('/') && !filename.contains(":\\");
+                        match (&source, relative) {
+                            (Some(source), true) => format!("{}/{}", source.trim_end_matches(['/', '\\']), filename),
+                            _ => filename.clone(),
+                        }
+                    });
+                }
+                "line" => {
+                    let (Some(file), Some(line)) = (&current, attributes.get("number").and_then(|n| n.parse().ok())) else {
+                        continue;
+                    };
+                    let hits = files.entry(file.clone()).or_default();
+                    hits.add_line(line, attributes.get("hits").and_then(|h| h.parse().ok()).unwrap_or(0));
+                    // condition-coverage="50% (1/2)"
+                    let conditions = attributes.get("condition-coverage")
+                        .and_then(|c| c.split_once('('))
+                        .and_then(|(_, rest)| rest.trim_end_matches(')').split_once('/'))
+                        .and_then(|(covered, total)| Some((covered.parse().ok()?, total.parse().ok()?)));
+                    if let Some((covered, total)) = conditions {
+                        hits.add_branches(line, covered, total);
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::Text(text) if in_source => source = Some(text.trim().to_string()),
+            XmlEvent::End(name) => match name.as_str() {
+                "source" => in_source = false,
+                "class" => current = None,
+                _ => {}
+            },
+            XmlEvent::Text(_) => {}
+        }
+    }
+    files
+}
+
+/// `go test -coverprofile` blocks: `file.go:10.2,12.3 2 1`; a line takes the best hit count of its blocks
+fn parse_go_profile(content: &str) -> BTreeMap<String, FileHits> {
+    let mut files: BTreeMap<String, FileHits> = BTreeMap::new();
+    for line in content.lines().filter(|l| !l.starts_with("mode:")) {
+        let Some((file, block)) = line.rsplit_once(':') else {
+            continue;
+        };
+        let mut parts = block.split_whitespace();
+        let (Some(range), Some(statements), Some(count)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let Some((start, end)) = range.split_once(',') else {
+            continue;
+        };
+        let line_of = |position: &str| position.split('.').next().and_then(|l| l.parse::<u32>().ok());
+        let (Some(start), Some(end), Ok(statements), Ok(count)) =
+            (line_of(start), line_of(end), statements.parse::<u32>(), count.parse::<u64>())
+        else {
+            continue;
+        };
+        let hits = files.entry(file.to_string()).or_default();
+        hits.add_block(range, statements, count);
+        // Every line of a block is highlighted, but only its statements count toward the totals
+        for line in start..=end {
+            let entry = hits.lines.entry(line).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+    }
+    files
+}
+
+/// Report path -> workspace-relative path with forward slashes; Go import paths lose the module prefix
+fn coverage_path(workspace: &Path, path: &str, go_module: Option<&str>) -> String {
+    let path = path.replace('\\', "/");
+    if let Some(rest) = go_module.and_then(|module| path.strip_prefix(&format!("{}/", module))) {
+        return rest.to_string();
+    }
+    let root = format!("{}/", workspace.to_string_lossy().replace('\\', "/").trim_end_matches('/'));
+    let path = path.strip_prefix(&root).unwrap_or(&path);
+    path.strip_prefix("./").unwrap_or(path).to_string()
+}
+
+fn totals_of<'a>(files: impl Iterator<Item = &'a CoverageTotals>) -> CoverageTotals {
+    let mut totals = CoverageTotals::default();
+    for file in files {
+        totals.lines_found += file.lines_found;
+        totals.lines_hit += file.lines_hit;
+        totals.branches_found += file.branches_found;
+        totals.branches_hit += file.branches_hit;
+    }
+    totals.line_percent = percent(totals.lines_hit, totals.lines_found);
+    totals.branch_percent = percent(totals.branches_hit, totals.branches_found);
+    totals
+}
+
+fn file_coverage(file: String, hits: FileHits) -> FileCoverage {
+    let lines: Vec<LineCoverage> = hits.lines.into_iter().map(|(line, hits)| LineCoverage { line, hits }).collect();
+    let branches: Vec<BranchCoverage> = hits.branches.into_iter()
+        .map(|(line, (covered, total))| BranchCoverage { line, covered, total })
+        .collect();
+    let (lines_found, lines_hit) = if hits.blocks.is_empty() {
+        (lines.len() as u32, lines.iter().filter(|l| l.hits > 0).count() as u32)
     } else {
-        Ok(TestRunResult {
-            success: true,
-            total: 1,
-            passed: 1,
-            failed: 0,
-            skipped: 0,
-            test_cases: vec![
-                TestCase {
-                    id: "test_2_1".to_string(),
-                    name: "formatDate() works correctly".to_string(),
-                    file: "/Utils.test.ts".to_string(),
-                    line: 5,
-                    status: "passed".to_string(),
-                    message: None,
-                },
-            ]
+        hits.blocks.values().fold((0, 0), |(found, hit), &(statements, hits)| {
+            (found + statements, hit + if hits > 0 { statements } else { 0 })
+        })
+    };
+    let branches_found = branches.iter().map(|b| b.total).sum();
+    let branches_hit = branches.iter().map(|b| b.covered.min(b.total)).sum();
+    FileCoverage {
+        file,
+        lines,
+        branches,
+        totals: CoverageTotals {
+            lines_found,
+            lines_hit,
+            branches_found,
+            branches_hit,
+            line_percent: percent(lines_hit, lines_found),
+            branch_percent: percent(branches_hit, branches_found),
+        },
+    }
+}
+
+/// What is kept of the last run for deltas, in `.corex/coverage.json`
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct CoverageSnapshot {
+    totals: CoverageTotals,
+    /// Line coverage per file
+    files: BTreeMap<String, f64>,
+}
+
+impl CoverageSnapshot {
+    fn file(workspace: &Path) -> PathBuf {
+        workspace.join(".corex").join("coverage.json")
+    }
+
+    fn of(report: &CoverageReport) -> Self {
+        Self {
+            totals: report.totals.clone(),
+            files: report.files.iter().map(|f| (f.file.clone(), f.totals.line_percent)).collect(),
+        }
+    }
+
+    fn load(workspace: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::file(workspace)).ok()?;
+        serde_json::from_str(&content)
+            .map_err(|e| warn!("⚠️ Önceki coverage kaydı okunamadı: {}", e))
+            .ok()
+    }
+
+    fn save(&self, workspace: &Path) -> Result<(), String> {
+        let file = Self::file(workspace);
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Klasör oluşturulamadı: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&file, content).map_err(|e| format!("Coverage kaydı yazılamadı: {}", e))
+    }
+
+    fn delta(&self, report: &CoverageReport) -> CoverageDelta {
+        let change = |now: f64, before: f64| ((now - before) * 100.0).round() / 100.0;
+        CoverageDelta {
+            line_percent: change(report.totals.line_percent, self.totals.line_percent),
+            branch_percent: change(report.totals.branch_percent, self.totals.branch_percent),
+            files: report.files.iter()
+                .filter_map(|f| {
+                    let before = self.files.get(&f.file)?;
+                    let delta = change(f.totals.line_percent, *before);
+                    (delta != 0.0).then(|| (f.file.clone(), delta))
+                })
+                .collect(),
+        }
+    }
+}
+
+fn build_coverage_report(
+    workspace: &Path,
+    source: String,
+    raw: BTreeMap<String, FileHits>,
+    go_module: Option<&str>,
+    previous: Option<&CoverageSnapshot>,
+) -> CoverageReport {
+    let mut merged: BTreeMap<String, FileHits> = BTreeMap::new();
+    for (path, hits) in raw {
+        let entry = merged.entry(coverage_path(workspace, &path, go_module)).or_default();
+        for (line, count) in hits.lines {
+            entry.add_line(line, count);
+        }
+        for (line, (covered, total)) in hits.branches {
+            entry.add_branches(line, covered, total);
+        }
+        for (range, (statements, count)) in hits.blocks {
+            entry.add_block(&range, statements, count);
+        }
+    }
+    let files: Vec<FileCoverage> = merged.into_iter().map(|(file, hits)| file_coverage(file, hits)).collect();
+    let mut report = CoverageReport {
+        source,
+        totals: totals_of(files.iter().map(|f| &f.totals)),
+        files,
+        delta: None,
+    };
+    report.delta = previous.map(|snapshot| snapshot.delta(&report));
+    report
+}
+
+/// Newest report file the project already has
+fn find_coverage_report(workspace: &Path) -> Option<(PathBuf, CoverageFormat)> {
+    COVERAGE_REPORTS.iter()
+        .map(|(path, format)| (workspace.join(path), *format))
+        .filter_map(|(path, format)| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path, format))
+        })
+        .max_by_key(|(modified, _, _)| *modified)
+        .map(|(_, path, format)| (path, format))
+}
+
+/// Commands that produce a report, run in order in the workspace
+struct CoveragePlan {
+    tool: &'static str,
+    commands: Vec<(String, Vec<String>)>,
+    report: PathBuf,
+    format: CoverageFormat,
+    /// Temporary file or directory removed once the report is read
+    cleanup: PathBuf,
+}
+
+/// Reports go under `temp_root`, which has to be visible to wherever the commands run
+fn coverage_plan(workspace: &Path, temp_root: &Path) -> Result<CoveragePlan, String> {
+    let has = |marker: &str| workspace.join(marker).is_file();
+    let temp = temp_root.join(format!("corex_coverage_{}", uuid::Uuid::new_v4()));
+    let command = |program: &str, args: &[&str]| (program.to_string(), args.iter().map(|a| a.to_string()).collect());
+    let report_arg = |path: &Path| path.to_string_lossy().to_string();
+
+    if has("Cargo.toml") {
+        let report = temp.with_extension("info");
+        let output = report_arg(&report);
+        Ok(CoveragePlan {
+            tool: "cargo llvm-cov",
+            // Failing tests still leave coverage worth showing
+            commands: vec![command("cargo", &["llvm-cov", "--workspace", "--ignore-run-fail", "--lcov", "--output-path", &output])],
+            cleanup: report.clone(),
+            report,
+            format: CoverageFormat::Lcov,
+        })
+    } else if has("package.json") {
+        let reports_dir = format!("--reports-dir={}", report_arg(&temp));
+        Ok(CoveragePlan {
+            tool: "c8",
+            commands: vec![command("npx", &["c8", "--reporter=lcovonly", &reports_dir, "npm", "test"])],
+            report: temp.join("lcov.info"),
+            cleanup: temp,
+            format: CoverageFormat::Lcov,
+        })
+    } else if has("go.mod") {
+        let report = temp.with_extension("out");
+        let profile = format!("-coverprofile={}", report_arg(&report));
+        Ok(CoveragePlan {
+            tool: "go test -cover",
+            commands: vec![command("go", &["test", "-cover", &profile, "./..."])],
+            cleanup: report.clone(),
+            report,
+            format: CoverageFormat::GoProfile,
         })
+    } else if ["pyproject.toml", "setup.py", "setup.cfg", "pytest.ini", "requirements.txt"].iter().any(|m| has(m)) {
+        let report = temp.with_extension("xml");
+        let output = report_arg(&report);
+        Ok(CoveragePlan {
+            tool: "coverage.py",
+            commands: vec![
+                command("python", &["-m", "coverage", "run", "--branch", "-m", "pytest"]),
+                command("python", &["-m", "coverage", "xml", "-o", &output]),
+            ],
+            cleanup: report.clone(),
+            report,
+            format: CoverageFormat::Cobertura,
+        })
+    } else {
+        Err("Coverage aracı bulunamadı: Cargo.toml, package.json, go.mod veya Python projesi yok".to_string())
     }
 }
 
+/// Run the plan's commands, in the dev container when the workspace is in container mode;
+/// test failures are fine as long as a report comes out
+async fn run_coverage(workspace: &Path, plan: &CoveragePlan, session: Option<&DevContainerSession>) -> Result<(), String> {
+    let mut last_output = String::new();
+    for (program, args) in &plan.commands {
+        info!("📊 Coverage alınıyor: {} {}", program, args.join(" "));
+        last_output = match session {
+            Some(session) => session.output(program, args, workspace).await
+                .map_err(|e| format!("{} başlatılamadı: {}", program, e))?
+                .stderr,
+            None => {
+                let output = Command::new(program)
+                    .args(args)
+                    .current_dir(workspace)
+                    .stdin(Stdio::null())
+                    .output()
+                    .await
+                    .map_err(|e| format!("{} başlatılamadı: {}", program, e))?;
+                String::from_utf8_lossy(&output.stderr).to_string()
+            }
+        };
+    }
+    if !plan.report.is_file() {
+        let tail: Vec<&str> = last_output.lines().rev().take(OUTPUT_TAIL_LINES).collect();
+        let tail: Vec<&str> = tail.into_iter().rev().collect();
+        return Err(format!("{} coverage raporu üretmedi:\n{}", plan.tool, tail.join("\n")));
+    }
+    Ok(())
+}
+
+/// Module path from `go.mod`, to map cover profile paths back to files
+fn go_module(workspace: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(workspace.join("go.mod")).ok()?;
+    content.lines()
+        .find_map(|line| line.trim().strip_prefix("module "))
+        .map(|module| module.trim().trim_matches('"').to_string())
+}
+
+/// Per-file line and branch coverage of the workspace, with changes since the last call
+///
+/// An existing lcov.info / cobertura report is used unless `run` is set or there is none,
+/// in which case the project's coverage tool is run.
 #[tauri::command]
-pub async fn get_code_coverage(_path: String) -> Result<String, String> {
-    // Return mock lcov string or JSON
-    let lcov_mock = "SF:src/utils.ts\nDA:1,1\nDA:2,0\nDA:3,1\nLF:3\nLH:2\nend_of_record".to_string();
-    Ok(lcov_mock)
+pub async fn get_code_coverage(path: String, run: Option<bool>) -> Result<CoverageReport, String> {
+    let workspace = Path::new(&path);
+    if !workspace.is_dir() {
+        return Err(format!("Klasör bulunamadı: {}", path));
+    }
+
+    let session = DevContainerSession::find(workspace).await;
+    let existing = if run.unwrap_or(false) { None } else { find_coverage_report(workspace) };
+    let (source, report, format, cleanup) = match existing {
+        Some((report, format)) => (coverage_path(workspace, &report.to_string_lossy(), None), report, format, None),
+        None => {
+            // The container only sees the workspace, so its report goes to `.corex/`
+            let temp_root = match &session {
+                Some(_) => workspace.join(".corex"),
+                None => std::env::temp_dir(),
+            };
+            std::fs::create_dir_all(&temp_root).map_err(|e| format!("Rapor dizini oluşturulamadı: {}", e))?;
+            let plan = coverage_plan(workspace, &temp_root)?;
+            run_coverage(workspace, &plan, session.as_ref()).await?;
+            (plan.tool.to_string(), plan.report, plan.format, Some(plan.cleanup))
+        }
+    };
+
+    let root = workspace.to_path_buf();
+    let report = blocking(move || read_coverage(&root, source, &report, format, cleanup, session.as_ref())).await??;
+    info!("✅ Coverage: {} dosya, satır %{}, dal %{}", report.files.len(), report.totals.line_percent, report.totals.branch_percent);
+    Ok(report)
+}
+
+/// Parse a coverage report, removing `cleanup` afterwards, and diff it against the last
+/// snapshot; file IO, so it runs on the blocking pool
+fn read_coverage(
+    workspace: &Path,
+    source: String,
+    report: &Path,
+    format: CoverageFormat,
+    cleanup: Option<PathBuf>,
+    session: Option<&DevContainerSession>,
+) -> Result<CoverageReport, String> {
+    let content = std::fs::read_to_string(report);
+    if let Some(cleanup) = cleanup {
+        let _ = if cleanup.is_dir() { std::fs::remove_dir_all(&cleanup) } else { std::fs::remove_file(&cleanup) };
+    }
+    let mut content = content.map_err(|e| format!("Coverage raporu okunamadı [{}]: {}", report.display(), e))?;
+    if let Some(session) = session {
+        content = session.host_paths(&content);
+    }
+    let raw = match format {
+        CoverageFormat::Lcov => parse_lcov(&content),
+        CoverageFormat::Cobertura => parse_cobertura(&content),
+        CoverageFormat::GoProfile => parse_go_profile(&content),
+    };
+
+    let previous = CoverageSnapshot::load(workspace);
+    let module = go_module(workspace);
+    let report = build_coverage_report(workspace, source, raw, module.as_deref(), previous.as_ref());
+    if let Err(e) = CoverageSnapshot::of(&report).save(workspace) {
+        warn!("⚠️ {}", e);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree_sitter_parser::FileAnalysis;
+
+    fn names(file_path: &str, content: &str) -> Vec<(String, u32, bool)> {
+        find_tests(&TreeSitterParser::new(), file_path, content)
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.name, t.line, t.skipped))
+            .collect()
+    }
+
+    #[test]
+    fn test_discovers_rust_tests() {
+        let content = "fn helper() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn adds() {}\n\n    #[tokio::test(flavor = \"multi_thread\")]\n    async fn fetches() {}\n\n    #[test]\n    #[ignore]\n    fn slow() {}\n\n    fn not_a_test() {}\n}\n";
+        assert_eq!(names("src/lib.rs", content), vec![
+            ("tests::adds".to_string(), 6, false),
+            ("tests::fetches".to_string(), 9, false),
+            ("tests::slow".to_string(), 13, true),
+        ]);
+    }
+
+    #[test]
+    fn test_discovers_pytest_and_go_tests() {
+        let python = "import pytest\n\ndef test_sum():\n    pass\n\nclass TestCart:\n    def test_add(self):\n        pass\n\n    def helper(self):\n        pass\n\n@pytest.mark.skip(reason=\"flaky\")\ndef test_remote():\n    pass\n";
+        assert_eq!(names("tests/test_cart.py", python), vec![
+            ("test_sum".to_string(), 3, false),
+            ("TestCart::test_add".to_string(), 7, false),
+            ("test_remote".to_string(), 13, true),
+        ]);
+
+        let go = "package cart\n\nimport \"testing\"\n\nfunc TestAdd(t *testing.T) {}\n\nfunc Testhelper(t *testing.T) {}\n\nfunc BenchmarkAdd(b *testing.B) {}\n\nfunc Test(t *testing.T) {}\n";
+        assert_eq!(names("cart_test.go", go), vec![
+            ("TestAdd".to_string(), 5, false),
+            ("Test".to_string(), 11, false),
+        ]);
+    }
+
+    #[test]
+    fn test_discovers_jest_blocks() {
+        let content = "import { add } from './cart';\n\ndescribe('Cart', () => {\n  it('adds items', () => {});\n  describe.skip(`totals`, () => {\n    test('sums', () => {});\n  });\n  it.each([1, 2])('handles %i', (n) => {});\n});\n\ntest('top level', () => {});\n";
+        assert_eq!(names("src/cart.test.ts", content), vec![
+            ("Cart adds items".to_string(), 4, false),
+            ("Cart totals sums".to_string(), 6, true),
+            ("Cart handles %i".to_string(), 8, false),
+            ("top level".to_string(), 11, false),
+        ]);
+    }
+
+    #[test]
+    fn test_suite_ids_are_stable_paths() {
+        let workspace = Path::new("/home/dev/app");
+        let file = Path::new("/home/dev/app/src/cart.test.ts");
+        let found = vec![
+            FoundTest { name: "adds".to_string(), line: 3, skipped: false },
+            FoundTest { name: "adds".to_string(), line: 9, skipped: true },
+        ];
+
+        let suite = build_suite(suite_id(workspace, file), file, "vitest", found);
+        assert_eq!(suite.id, "src/cart.test.ts");
+        let ids: Vec<&str> = suite.tests.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["src/cart.test.ts::adds", "src/cart.test.ts::adds#2"]);
+        assert_eq!(suite.tests[1].status, "skipped");
+        assert_eq!(suite.tests[0].file, "/home/dev/app/src/cart.test.ts");
+
+        assert_eq!(test_framework(Path::new("/w/pkg/cart_test.go"), "", Path::new("/w")), Some("go"));
+        assert_eq!(test_framework(Path::new("/w/src/cart.ts"), "", Path::new("/w")), None);
+        assert_eq!(test_framework(Path::new("/w/src/a.spec.ts"), "import { it } from 'vitest';", Path::new("/w")), Some("vitest"));
+    }
+
+    #[test]
+    fn test_load_suite_rejects_paths_outside_the_workspace() {
+        let workspace = Path::new("/home/dev/app");
+        for id in ["../other/cart.test.ts", "src/../../etc/passwd", "/etc/passwd", "./src/cart.test.ts"] {
+            assert_eq!(load_suite(workspace, id).unwrap_err(), format!("Geçersiz test dosyası: {}", id));
+        }
+        assert!(load_suite(workspace, "src/cart.test.ts").unwrap_err().starts_with("Test dosyası okunamadı"));
+    }
+
+    #[test]
+    fn test_parses_streamed_runner_output() {
+        let mut failure = None;
+        let lines = [
+            "running 3 tests",
+            "test cart::tests::adds ... FAILED",
+            "test cart::tests::removes ... ok",
+            "test cart::tests::slow ... ignored, takes a minute",
+            "",
+            "failures:",
+            "",
+            "---- cart::tests::adds stdout ----",
+            "thread 'cart::tests::adds' panicked at src/cart.rs:9:5:",
+            "assertion failed",
+            "",
+            "",
+            "failures:",
+            "    cart::tests::adds",
+            "",
+            "test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out",
+        ];
+        let events: Vec<RunnerEvent> = lines.iter().filter_map(|line| parse_libtest_line(line, &mut failure)).collect();
+        let statuses: Vec<(&str, &str)> = events.iter().map(|e| (e.name.as_str(), e.status.as_str())).collect();
+        assert_eq!(statuses, vec![
+            ("cart::tests::adds", "failed"),
+            ("cart::tests::removes", "passed"),
+            ("cart::tests::slow", "skipped"),
+            ("cart::tests::adds", "failed"),
+        ]);
+        assert_eq!(events[3].message.as_deref(), Some("thread 'cart::tests::adds' panicked at src/cart.rs:9:5:\nassertion failed"));
+        assert!(failure.is_none());
+
+        let mut output = HashMap::new();
+        let lines = [
+            r#"{"Action":"run","Test":"TestAdd"}"#,
+            r#"{"Action":"output","Test":"TestAdd/negative","Output":"    cart_test.go:9: got 1\n"}"#,
+            r#"{"Action":"fail","Test":"TestAdd/negative","Elapsed":0}"#,
+            r#"{"Action":"fail","Test":"TestAdd","Elapsed":0.25}"#,
+        ];
+        let events: Vec<RunnerEvent> = lines.iter().filter_map(|l| parse_go_line(l, &mut output)).collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].status, "failed");
+        assert_eq!(events[1].duration_ms, Some(250));
+        assert_eq!(events[1].message.as_deref(), Some("    cart_test.go:9: got 1"));
+
+        let vitest = parse_progress_line(ReportFormat::JestJson, " ✓ src/cart.test.ts > Cart > adds items 3ms").unwrap();
+        assert_eq!((vitest.name.as_str(), vitest.status.as_str()), ("Cart adds items", "passed"));
+        let jest = parse_progress_line(ReportFormat::JestJson, "    ✕ adds items (5 ms)").unwrap();
+        assert_eq!((jest.name.as_str(), jest.status.as_str()), ("adds items", "failed"));
+        assert!(parse_progress_line(ReportFormat::JestJson, "✓ src/cart.test.ts (3 tests) 5ms").is_none());
+        let pytest = parse_progress_line(ReportFormat::JunitXml, "tests/test_cart.py::TestCart::test_add[2] PASSED   [ 50%]").unwrap();
+        assert_eq!((pytest.name.as_str(), pytest.status.as_str()), ("TestCart::test_add", "passed"));
+    }
+
+    #[test]
+    fn test_parses_report_files() {
+        let jest = r#"{"numTotalTests": 2, "testResults": [{"name": "/w/cart.test.ts", "assertionResults": [
+            {"fullName": "Cart adds items", "status": "passed", "duration": 4, "failureMessages": []},
+            {"fullName": "Cart totals", "status": "failed", "duration": 2.6, "failureMessages": ["Expected 3", "Received 2"]},
+            {"fullName": "Cart later", "status": "todo", "failureMessages": []}
+        ]}]}"#;
+        let events = parse_jest_report(jest).unwrap();
+        assert_eq!(events.iter().map(|e| e.status.as_str()).collect::<Vec<_>>(), vec!["passed", "failed", "skipped"]);
+        assert_eq!(events[1].duration_ms, Some(3));
+        assert_eq!(events[1].message.as_deref(), Some("Expected 3\nReceived 2"));
+        assert!(parse_jest_report("not json").is_err());
+
+        let junit = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuites><testsuite name="pytest" tests="3">
+  <testcase classname="tests.test_cart" name="test_sum" time="0.002" />
+  <testcase classname="tests.test_cart.TestCart" name="test_add[1]" time="0.010">
+    <failure message="assert 1 == 2">def test_add(n):
+&gt;       assert n == 2
+E       assert 1 == 2</failure>
+  </testcase>
+  <testcase classname="tests.test_cart" name="test_remote" time="0"><skipped message="flaky &amp; slow" /></testcase>
+  <testcase classname="tests.test_cart" name="test_cdata"><system-out><![CDATA[a < b]]></system-out></testcase>
+</testsuite></testsuites>"#;
+        let events = parse_junit_report(junit);
+        let summary: Vec<(&str, &str)> = events.iter().map(|e| (e.name.as_str(), e.status.as_str())).collect();
+        assert_eq!(summary, vec![
+            ("test_sum", "passed"),
+            ("TestCart::test_add", "failed"),
+            ("test_remote", "skipped"),
+            ("test_cdata", "passed"),
+        ]);
+        assert_eq!(events[0].duration_ms, Some(2));
+        assert!(events[1].message.as_deref().unwrap().contains(">       assert n == 2"));
+        assert_eq!(events[2].message.as_deref(), Some("flaky & slow"));
+        assert_eq!(xml_unescape("&#65;&#x42;&unknown; &amp;"), "AB&unknown; &");
+    }
+
+    #[test]
+    fn test_cargo_targets_and_merged_results() {
+        let package = Path::new("/nonexistent/corex/pkg");
+        assert_eq!(cargo_target(package, &package.join("tests/api.rs")), (vec!["--test".to_string(), "api".to_string()], String::new()));
+        assert_eq!(cargo_target(package, &package.join("src/cart/mod.rs")), (vec!["--bins".to_string()], "cart::".to_string()));
+        assert_eq!(cargo_target(package, &package.join("src/bin/tool/io.rs")), (vec!["--bin".to_string(), "tool".to_string()], "io::".to_string()));
+
+        let file = package.join("src/cart.rs");
+        let found = vec![
+            FoundTest { name: "tests::adds".to_string(), line: 3, skipped: false },
+            FoundTest { name: "tests::removes".to_string(), line: 7, skipped: false },
+            FoundTest { name: "tests::slow".to_string(), line: 11, skipped: true },
+        ];
+        let suite = build_suite("src/cart.rs".to_string(), &file, "cargo", found);
+        let runner_names: HashMap<String, String> = suite.tests.iter()
+            .map(|t| (t.id.clone(), format!("cart::{}", t.name)))
+            .collect();
+
+        let mut failed = RunnerEvent::new("cart::tests::removes", "failed");
+        failed.message = Some("assertion failed".to_string());
+        let events = vec![
+            RunnerEvent::new("cart::tests::adds", "running"),
+            RunnerEvent::new("cart::tests::adds", "passed"),
+            failed,
+            RunnerEvent::new("cart::tests::generated", "passed"),
+        ];
+        let result = merge_results(&suite, &runner_names, &events, None, true);
+        assert!(!result.success);
+        assert_eq!((result.total, result.passed, result.failed, result.skipped), (4, 2, 1, 1));
+        assert_eq!(result.test_cases[1].message.as_deref(), Some("assertion failed"));
+        assert_eq!(result.test_cases[3].id, "src/cart.rs::cart::tests::generated");
+
+        let broken = merge_results(&suite, &runner_names, &[], Some("error[E0425]: cannot find value"), true);
+        assert_eq!((broken.failed, broken.skipped), (2, 1));
+        assert_eq!(broken.test_cases[0].message.as_deref(), Some("error[E0425]: cannot find value"));
+    }
+
+    #[test]
+    fn test_parses_coverage_formats() {
+        let lcov = "TN:\nSF:/w/app/src/cart.rs\nDA:1,3\nDA:2,0\nDA:4,1,abc\nBRDA:1,0,0,2\nBRDA:1,0,1,-\nLF:3\nLH:2\nend_of_record\nSF:src/util.rs\nDA:7,0\nend_of_record\n";
+        let files = parse_lcov(lcov);
+        let cart = &files["/w/app/src/cart.rs"];
+        assert_eq!(cart.lines.iter().map(|(l, h)| (*l, *h)).collect::<Vec<_>>(), vec![(1, 3), (2, 0), (4, 1)]);
+        assert_eq!(cart.branches[&1], (1, 2));
+        assert_eq!(files["src/util.rs"].lines[&7], 0);
+
+        let cobertura = r#"<?xml version="1.0" ?>
+<coverage line-rate="0.5"><sources><source>/w/app</source></sources>
+<packages><package name="cart"><classes>
+  <class name="cart.py" filename="cart/cart.py"><lines>
+    <line number="1" hits="1"/>
+    <line number="3" hits="0" branch="true" condition-coverage="50% (1/2)"/>
+  </lines></class>
+</classes></package></packages></coverage>"#;
+        let files = parse_cobertura(cobertura);
+        let cart = &files["/w/app/cart/cart.py"];
+        assert_eq!(cart.lines[&1], 1);
+        assert_eq!(cart.branches[&3], (1, 2));
+
+        let profile = "mode: set\nexample.com/shop/cart/cart.go:3.20,5.2 1 1\nexample.com/shop/cart/cart.go:5.2,6.3 1 0\n";
+        let files = parse_go_profile(profile);
+        let cart = &files["example.com/shop/cart/cart.go"];
+        assert_eq!(cart.lines.iter().map(|(l, h)| (*l, *h)).collect::<Vec<_>>(), vec![(3, 1), (4, 1), (5, 1), (6, 0)]);
+        let multi = "mode: count\nm/a.go:3.20,9.2 4 2\nm/a.go:9.2,12.3 2 0\nm/a.go:3.20,9.2 4 1\n";
+        let totals = file_coverage("a.go".to_string(), parse_go_profile(multi).remove("m/a.go").unwrap()).totals;
+        assert_eq!((totals.lines_hit, totals.lines_found), (4, 6));
+        assert_eq!(coverage_path(Path::new("/w/app"), "example.com/shop/cart/cart.go", Some("example.com/shop")), "cart/cart.go");
+        assert_eq!(coverage_path(Path::new("/w/app"), "/w/apple/x.rs", None), "/w/apple/x.rs");
+    }
+
+    #[tokio::test]
+    async fn test_coverage_report_tracks_deltas() {
+        let workspace = std::env::temp_dir().join(format!("corex_coverage_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&workspace).unwrap();
+        let sf = workspace.join("src/cart.rs").to_string_lossy().to_string();
+        std::fs::write(workspace.join("lcov.info"), format!("SF:{}\nDA:1,1\nDA:2,0\nBRDA:1,0,0,1\nBRDA:1,0,1,0\nend_of_record\n", sf)).unwrap();
+
+        let path = workspace.to_string_lossy().to_string();
+        let first = get_code_coverage(path.clone(), None).await.unwrap();
+        assert_eq!(first.source, "lcov.info");
+        assert!(first.delta.is_none());
+        assert_eq!(first.files[0].file, "src/cart.rs");
+        assert_eq!((first.totals.lines_hit, first.totals.lines_found), (1, 2));
+        assert_eq!((first.totals.line_percent, first.totals.branch_percent), (50.0, 50.0));
+
+        std::fs::write(workspace.join("lcov.info"), format!("SF:{}\nDA:1,1\nDA:2,4\nDA:3,0\nend_of_record\n", sf)).unwrap();
+        let second = get_code_coverage(path, None).await.unwrap();
+        let delta = second.delta.unwrap();
+        assert_eq!(delta.line_percent, 16.67);
+        assert_eq!(delta.branch_percent, 50.0);
+        assert_eq!(delta.files["src/cart.rs"], 16.67);
+
+        std::fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn test_selects_failed_and_affected_tests() {
+        let status = " M src/cart.ts\0R  src/new.ts\0src/old.ts\0?? src/util.ts\0";
+        assert_eq!(parse_git_status(status), vec!["src/cart.ts", "src/new.ts", "src/util.ts"]);
+
+        assert_eq!(
+            js_name_pattern(["Cart handles %i", "a.b (x)"].into_iter()),
+            r"^(?:Cart handles .*|a\.b \(x\))$"
+        );
+
+        let mut cache = TestResultCache::default();
+        let case = |id: &str, status: &str| TestCase {
+            id: id.to_string(),
+            name: id.split_once("::").unwrap().1.to_string(),
+            file: String::new(),
+            line: 1,
+            status: status.to_string(),
+            message: None,
+            duration_ms: None,
+            flaky: false,
+        };
+        let run = |cases: Vec<TestCase>| TestRunResult {
+            success: false,
+            total: cases.len() as u32,
+            passed: 0,
+            failed: 0,
+            skipped: 0,
+            test_cases: cases,
+        };
+        cache.record("a.py", None, &run(vec![case("a.py::test_x", "failed"), case("a.py::test_y", "passed")]));
+        cache.record("b.py", None, &run(vec![case("b.py::test_z", "failed")]));
+        cache.record("b.py", Some(&BTreeSet::from(["b.py::test_z".to_string()])), &run(vec![case("b.py::test_z", "passed")]));
+        let failed = cache.failed();
+        assert_eq!(failed.keys().collect::<Vec<_>>(), vec!["a.py"]);
+        assert_eq!(failed["a.py"], BTreeSet::from(["a.py::test_x".to_string()]));
+
+        // test_x was renamed to test_w: the whole-file run drops it
+        cache.record("a.py", None, &run(vec![case("a.py::test_w", "failed"), case("a.py::test_y", "passed")]));
+        assert_eq!(cache.failed()["a.py"], BTreeSet::from(["a.py::test_w".to_string()]));
+        assert!(!cache.results.contains_key("a.py::test_x"));
+
+        // Rerunning a failure discovery no longer finds forgets it
+        cache.record("a.py", Some(&BTreeSet::from(["a.py::test_w".to_string()])), &run(Vec::new()));
+        assert!(cache.failed().is_empty());
+        assert_eq!(cache.results.len(), 2);
+
+        let file = |name: &str| FileAnalysis {
+            file_path: format!("/w/src/{}", name),
+            symbols: Vec::new(),
+            imports: if name == "cart.test.ts" { vec!["./cart".to_string()] } else { Vec::new() },
+            exports: Vec::new(),
+            complexity: 0,
+            metrics: Default::default(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+        };
+        let graph = DependencyGraph::from_analyses(
+            Path::new("/w"),
+            None,
+            &[file("cart.ts"), file("cart.test.ts"), file("other.test.ts")],
+        );
+        let suite = |name: &str| build_suite(format!("src/{}", name), &Path::new("/w/src").join(name), "jest", Vec::new());
+        let suites = vec![suite("cart.test.ts"), suite("other.test.ts")];
+        let changed = BTreeSet::from(["/w/src/cart.ts".to_string()]);
+        let affected = affected_suites(suites, &changed, &graph);
+        assert_eq!(affected.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["src/cart.test.ts"]);
+    }
+
+    #[test]
+    fn test_history_flags_flaky_and_slow_tests() {
+        let day = 86_400_000;
+        let run = |timestamp: i64, commit: &str, changes: Option<&str>, tests: &[(&str, &str, u64)]| TestRunRecord {
+            timestamp,
+            suite_id: "a.py".to_string(),
+            commit: Some(commit.to_string()),
+            changes: changes.map(str::to_string),
+            tests: tests.iter()
+                .map(|(id, status, ms)| TestRunEntry { id: id.to_string(), status: status.to_string(), duration_ms: Some(*ms) })
+                .collect(),
+        };
+        let runs = vec![
+            run(1_700_000_000_000, "c1", None, &[("a.py::test_x", "passed", 10), ("a.py::test_y", "failed", 300)]),
+            run(1_700_000_000_000 + 1000, "c1", None, &[("a.py::test_x", "failed", 30), ("a.py::test_y", "failed", 100)]),
+            // An edit in between: test_y passing now is a fix, not a flip
+            run(1_700_000_000_000 + 2000, "c1", Some("d41d8cd9"), &[("a.py::test_x", "passed", 20), ("a.py::test_y", "passed", 200)]),
+            run(1_700_000_000_000 + 2 * day, "c2", None, &[("a.py::test_x", "skipped", 0)]),
+        ];
+
+        let workspace = std::env::temp_dir().join(format!("corex_test_history_{}", uuid::Uuid::new_v4()));
+        let mut history = TestHistory::default();
+        for record in runs {
+            history.append(&workspace, record).unwrap();
+        }
+        let history = TestHistory::load(&workspace).unwrap();
+        assert_eq!(history.runs.len(), 4);
+
+        let flaky = history.flaky_tests();
+        assert_eq!(flaky.len(), 1);
+        assert_eq!((flaky[0].id.as_str(), flaky[0].flips, flaky[0].commits.clone()), ("a.py::test_x", 1, vec!["c1".to_string()]));
+
+        let slowest = history.slowest_tests(1);
+        assert_eq!(slowest, vec![SlowTest { id: "a.py::test_y".to_string(), runs: 3, average_ms: 200, max_ms: 300, last_ms: 200 }]);
+
+        let failing = history.most_failing(10);
+        assert_eq!(failing.iter().map(|t| (t.id.as_str(), t.failures, t.runs)).collect::<Vec<_>>(),
+            vec![("a.py::test_y", 2, 3), ("a.py::test_x", 1, 3)]);
+
+        let trends = history.failure_trends();
+        assert_eq!(trends.len(), 2);
+        assert_eq!((trends[0].runs, trends[0].results, trends[0].failed, trends[0].failure_rate), (3, 6, 3, 50.0));
+        assert_eq!((trends[1].results, trends[1].failure_rate), (0, 0.0));
+
+        std::fs::remove_dir_all(&workspace).unwrap();
+    }
 }
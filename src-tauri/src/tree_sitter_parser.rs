@@ -4,18 +4,64 @@
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use lru::LruCache;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
+use tree_sitter::{Language, Node, Parser, Point, Tree};
+
+/// Longest signature we keep; anything past this is cut
+const MAX_SIGNATURE_CHARS: usize = 300;
+
+/// Config outlines (JSON, TOML, YAML) list keys down to this many levels
+const MAX_OUTLINE_DEPTH: usize = 2;
+
+/// Functions at or above this cyclomatic complexity are reported as hotspots
+pub const HOTSPOT_COMPLEXITY: usize = 10;
+
+/// Size and complexity of a single function or method
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolMetrics {
+    /// 1 + number of decision points (branches, loops, cases, `&&`/`||`)
+    pub cyclomatic_complexity: usize,
+    pub line_count: usize,
+    /// Deepest nesting of control-flow blocks inside the body
+    pub nesting_depth: usize,
+    /// Declared parameters, not counting `self`/`cls` receivers
+    pub parameter_count: usize,
+}
+
+/// File-level aggregate over every function's metrics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileMetrics {
+    pub line_count: usize,
+    pub function_count: usize,
+    pub average_complexity: f32,
+    pub max_complexity: usize,
+    pub max_nesting_depth: usize,
+    /// Functions at or above `HOTSPOT_COMPLEXITY`
+    pub hotspot_count: usize,
+}
 
 /// Symbol extracted from AST
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Symbol {
     pub name: String,
-    pub kind: String,  // "function", "class", "interface", etc.
+    pub kind: String,  // "function", "method", "class", "interface", "table", "property", etc.
+    /// First line (1-based) of the declaration
     pub line: usize,
+    /// Column (0-based, in bytes) where the declaration starts
     pub column: usize,
+    /// Last line (1-based, inclusive) of the symbol's body
+    pub end_line: usize,
+    /// Column (0-based, in bytes) just past the end of the declaration
+    pub end_column: usize,
     pub signature: Option<String>,
     pub documentation: Option<String>,
     pub is_exported: bool,
+    /// Enclosing symbol (class, impl type, trait, ...) if any
+    pub parent: Option<String>,
+    /// Only set for functions and methods
+    pub metrics: Option<SymbolMetrics>,
 }
 
 /// File analysis result
@@ -25,14 +71,38 @@ pub struct FileAnalysis {
     pub symbols: Vec<Symbol>,
     pub imports: Vec<String>,
     pub exports: Vec<String>,
+    /// Sum of the cyclomatic complexity of every function in the file
     pub complexity: usize,
+    pub metrics: FileMetrics,
     pub dependencies: Vec<String>,
     pub dependents: Vec<String>,
 }
 
+impl FileAnalysis {
+    /// Functions whose cyclomatic complexity is at least `threshold`, worst first
+    pub fn hotspots(&self, threshold: usize) -> Vec<&Symbol> {
+        let mut hotspots: Vec<&Symbol> = self.symbols.iter()
+            .filter(|s| s.metrics.as_ref().map(|m| m.cyclomatic_complexity >= threshold).unwrap_or(false))
+            .collect();
+        hotspots.sort_by_key(|s| std::cmp::Reverse(s.metrics.as_ref().map(|m| m.cyclomatic_complexity).unwrap_or(0)));
+        hotspots
+    }
+}
+
+/// One occurrence of an identifier in a file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdentifierOccurrence {
+    /// 1-based, like `Symbol::line`
+    pub line: usize,
+    /// 0-based byte columns
+    pub column: usize,
+    pub end_column: usize,
+}
+
 /// Tree-sitter parser with AST caching
 pub struct TreeSitterParser {
-    ast_cache: LruCache<String, FileAnalysis>,
+    /// Keyed by path; the content hash tells a cached analysis from a stale one
+    ast_cache: LruCache<String, (u64, FileAnalysis)>,
 }
 
 impl TreeSitterParser {
@@ -41,301 +111,1130 @@ impl TreeSitterParser {
             ast_cache: LruCache::new(NonZeroUsize::new(500).unwrap()),
         }
     }
-    
+
     /// Parse file and extract symbols
     pub fn parse_file(&mut self, file_path: &str, content: &str) -> Result<FileAnalysis, Box<dyn Error>> {
-        // Check cache
-        if let Some(cached) = self.ast_cache.get(file_path) {
-            return Ok(cached.clone());
+        // Check cache, ignoring entries parsed from older content
+        let hash = content_hash(content);
+        if let Some((cached_hash, cached)) = self.ast_cache.get(file_path) {
+            if *cached_hash == hash {
+                return Ok(cached.clone());
+            }
         }
-        
+
         // Detect language from file extension
         let language = self.detect_language(file_path)?;
-        
+
         // Parse based on language
-        let analysis = match language.as_str() {
-            "typescript" | "javascript" => self.parse_typescript(file_path, content)?,
-            "python" => self.parse_python(file_path, content)?,
-            "rust" => self.parse_rust(file_path, content)?,
-            "go" => self.parse_go(file_path, content)?,
-            _ => return Err(format!("Unsupported language: {}", language).into()),
-        };
-        
+        let analysis = self.parse_with_grammar(file_path, content, Self::lang_for(&language)?)?;
+
         // Cache result
-        self.ast_cache.put(file_path.to_string(), analysis.clone());
-        
+        self.ast_cache.put(file_path.to_string(), (hash, analysis.clone()));
+
         Ok(analysis)
     }
-    
+
     /// Detect language from file extension
     fn detect_language(&self, file_path: &str) -> Result<String, Box<dyn Error>> {
         let extension = file_path.split('.').last()
             .ok_or("No file extension")?;
-        
+
         match extension {
-            "ts" | "tsx" | "js" | "jsx" => Ok("typescript".to_string()),
+            "ts" | "mts" | "cts" => Ok("typescript".to_string()),
+            "tsx" => Ok("tsx".to_string()),
+            // The TSX grammar is a superset of JavaScript with JSX
+            "js" | "jsx" | "mjs" | "cjs" => Ok("javascript".to_string()),
             "py" => Ok("python".to_string()),
             "rs" => Ok("rust".to_string()),
             "go" => Ok("go".to_string()),
+            "java" => Ok("java".to_string()),
+            "c" => Ok("c".to_string()),
+            // Headers are parsed as C++, which also covers plain C declarations
+            "h" | "hh" | "hpp" | "hxx" | "cc" | "cpp" | "cxx" => Ok("cpp".to_string()),
+            "cs" => Ok("csharp".to_string()),
+            "json" => Ok("json".to_string()),
+            "toml" => Ok("toml".to_string()),
+            "yaml" | "yml" => Ok("yaml".to_string()),
             _ => Err(format!("Unsupported extension: {}", extension).into()),
         }
     }
-    
-    /// Parse TypeScript/JavaScript (regex fallback for now)
-    fn parse_typescript(&self, file_path: &str, content: &str) -> Result<FileAnalysis, Box<dyn Error>> {
-        let mut symbols = Vec::new();
-        let mut imports = Vec::new();
-        let exports = Vec::new();
-        
-        // Extract functions (simplified regex)
-        let function_regex = regex::Regex::new(r"(?:export\s+)?(?:async\s+)?function\s+(\w+)")?;
-        for cap in function_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "function".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported: content[..name.start()].contains("export"),
-                });
-            }
+
+    fn lang_for(language: &str) -> Result<Lang, Box<dyn Error>> {
+        match language {
+            "typescript" => Ok(Lang::TypeScript),
+            "tsx" | "javascript" => Ok(Lang::Tsx),
+            "python" => Ok(Lang::Python),
+            "rust" => Ok(Lang::Rust),
+            "go" => Ok(Lang::Go),
+            "java" => Ok(Lang::Java),
+            "c" => Ok(Lang::C),
+            "cpp" => Ok(Lang::Cpp),
+            "csharp" => Ok(Lang::CSharp),
+            "json" => Ok(Lang::Json),
+            "toml" => Ok(Lang::Toml),
+            "yaml" => Ok(Lang::Yaml),
+            _ => Err(format!("Unsupported language: {}", language).into()),
         }
-        
-        // Extract classes
-        let class_regex = regex::Regex::new(r"(?:export\s+)?class\s+(\w+)")?;
-        for cap in class_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "class".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported: content[..name.start()].contains("export"),
-                });
+    }
+
+    fn parse_tree(&self, file_path: &str, content: &str, lang: Lang) -> Result<Tree, Box<dyn Error>> {
+        let mut parser = Parser::new();
+        parser.set_language(&lang.grammar())?;
+        parser.parse(content, None)
+            .ok_or_else(|| format!("tree-sitter could not parse {}", file_path).into())
+    }
+
+    /// Raw syntax tree and detected language, for callers that walk nodes themselves
+    pub fn syntax_tree(&self, file_path: &str, content: &str) -> Result<(String, Tree), Box<dyn Error>> {
+        let language = self.detect_language(file_path)?;
+        let tree = self.parse_tree(file_path, content, Self::lang_for(&language)?)?;
+        Ok((language, tree))
+    }
+
+    /// Name of the identifier at `line` (1-based) / `column` (0-based), if any
+    ///
+    /// A cursor right after the last character still counts, as editors place it there.
+    pub fn identifier_at(&self, file_path: &str, content: &str, line: usize, column: usize) -> Result<Option<String>, Box<dyn Error>> {
+        let lang = Self::lang_for(&self.detect_language(file_path)?)?;
+        let tree = self.parse_tree(file_path, content, lang)?;
+        let row = line.saturating_sub(1);
+
+        for col in [column, column.saturating_sub(1)] {
+            let point = Point { row, column: col };
+            let node = tree.root_node().named_descendant_for_point_range(point, point);
+            if let Some(node) = node.filter(|n| is_identifier_kind(n.kind())) {
+                return Ok(node.utf8_text(content.as_bytes()).ok().map(str::to_string));
             }
         }
-        
-        // Extract imports
-        let import_regex = regex::Regex::new(r#"import\s+.*?from\s+['"](.+?)['"]"#)?;
-        for cap in import_regex.captures_iter(content) {
-            if let Some(path) = cap.get(1) {
-                imports.push(path.as_str().to_string());
+        Ok(None)
+    }
+
+    /// Every identifier node spelled `name`, in document order
+    pub fn find_identifiers(&self, file_path: &str, content: &str, name: &str) -> Result<Vec<IdentifierOccurrence>, Box<dyn Error>> {
+        let lang = Self::lang_for(&self.detect_language(file_path)?)?;
+        let tree = self.parse_tree(file_path, content, lang)?;
+
+        let mut occurrences = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if is_identifier_kind(node.kind()) && node.utf8_text(content.as_bytes()) == Ok(name) {
+                occurrences.push(IdentifierOccurrence {
+                    line: node.start_position().row + 1,
+                    column: node.start_position().column,
+                    end_column: node.end_position().column,
+                });
+                continue;
             }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
         }
-        
+
+        occurrences.sort_by_key(|o| (o.line, o.column));
+        Ok(occurrences)
+    }
+
+    /// Parse with the real tree-sitter grammar and walk the syntax tree
+    fn parse_with_grammar(&self, file_path: &str, content: &str, lang: Lang) -> Result<FileAnalysis, Box<dyn Error>> {
+        let tree = self.parse_tree(file_path, content, lang)?;
+
+        let mut collector = SymbolCollector::new(content.as_bytes(), lang);
+        collector.visit(tree.root_node());
+
+        let metrics = file_metrics(&collector.symbols, content.lines().count());
+        let complexity = collector.symbols.iter()
+            .filter_map(|s| s.metrics.as_ref())
+            .map(|m| m.cyclomatic_complexity)
+            .sum();
+
         Ok(FileAnalysis {
             file_path: file_path.to_string(),
-            symbols,
-            imports,
-            exports,
-            complexity: 0,
+            symbols: collector.symbols,
+            imports: collector.imports,
+            exports: collector.exports,
+            complexity,
+            metrics,
             dependencies: Vec::new(),
             dependents: Vec::new(),
         })
     }
-    
-    /// Parse Python (regex fallback)
-    fn parse_python(&self, file_path: &str, content: &str) -> Result<FileAnalysis, Box<dyn Error>> {
-        let mut symbols = Vec::new();
-        let mut imports = Vec::new();
-        
-        // Extract functions
-        let function_regex = regex::Regex::new(r"def\s+(\w+)\s*\(")?;
-        for cap in function_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "function".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported: true,  // Python exports all top-level
-                });
-            }
+
+    /// Clear cache
+    pub fn clear_cache(&mut self) {
+        self.ast_cache.clear();
+    }
+
+    /// Invalidate cache for specific file
+    pub fn invalidate_file(&mut self, file_path: &str) {
+        self.ast_cache.pop(file_path);
+    }
+}
+
+/// Languages with a bundled grammar
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Lang {
+    TypeScript,
+    Tsx,
+    Python,
+    Rust,
+    Go,
+    Java,
+    C,
+    Cpp,
+    CSharp,
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl Lang {
+    fn grammar(self) -> Language {
+        match self {
+            Lang::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Lang::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Lang::Python => tree_sitter_python::LANGUAGE.into(),
+            Lang::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Lang::Go => tree_sitter_go::LANGUAGE.into(),
+            Lang::Java => tree_sitter_java::LANGUAGE.into(),
+            Lang::C => tree_sitter_c::LANGUAGE.into(),
+            Lang::Cpp => tree_sitter_cpp::LANGUAGE.into(),
+            Lang::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
+            Lang::Json => tree_sitter_json::LANGUAGE.into(),
+            Lang::Toml => tree_sitter_toml_ng::LANGUAGE.into(),
+            Lang::Yaml => tree_sitter_yaml::LANGUAGE.into(),
         }
-        
-        // Extract classes
-        let class_regex = regex::Regex::new(r"class\s+(\w+)")?;
-        for cap in class_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "class".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported: true,
-                });
+    }
+
+    /// Data formats get a key outline instead of code symbols
+    fn is_config(self) -> bool {
+        matches!(self, Lang::Json | Lang::Toml | Lang::Yaml)
+    }
+}
+
+/// The enclosing symbol while walking the tree
+#[derive(Clone)]
+struct Container {
+    name: String,
+    kind: &'static str,
+}
+
+/// A declaration recognised by one of the per-language classifiers
+struct Declaration<'t> {
+    name: String,
+    kind: &'static str,
+    is_exported: bool,
+    /// Node whose preceding comments hold the documentation
+    doc_anchor: Node<'t>,
+    /// Name children see as their parent (defaults to `name`)
+    container_name: Option<String>,
+    /// Overrides the walk's parent (Go methods belong to their receiver)
+    parent_override: Option<String>,
+}
+
+/// Walks a syntax tree and collects symbols, imports and exports
+struct SymbolCollector<'s> {
+    source: &'s [u8],
+    lang: Lang,
+    symbols: Vec<Symbol>,
+    imports: Vec<String>,
+    exports: Vec<String>,
+}
+
+impl<'s> SymbolCollector<'s> {
+    fn new(source: &'s [u8], lang: Lang) -> Self {
+        Self {
+            source,
+            lang,
+            symbols: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+        }
+    }
+
+    fn text(&self, node: Node) -> String {
+        node.utf8_text(self.source).unwrap_or("").to_string()
+    }
+
+    fn field_text(&self, node: Node, field: &str) -> Option<String> {
+        node.child_by_field_name(field).map(|n| self.text(n))
+    }
+
+    /// Walk the whole tree with an explicit stack, so deeply nested (e.g. minified) files
+    /// can't overflow the thread's stack
+    fn visit(&mut self, root: Node) {
+        let mut stack: Vec<(Node, Option<Container>)> = vec![(root, None)];
+        while let Some((node, parent)) = stack.pop() {
+            let next_parent = self.visit_node(node, parent.as_ref());
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.named_children(&mut cursor).collect();
+            // Reversed so children come off the stack in document order
+            for child in children.into_iter().rev() {
+                stack.push((child, next_parent.clone()));
             }
         }
-        
-        // Extract imports
-        let import_regex = regex::Regex::new(r"(?:from\s+(\S+)\s+)?import\s+(.+)")?;
-        for cap in import_regex.captures_iter(content) {
-            if let Some(module) = cap.get(1) {
-                imports.push(module.as_str().to_string());
+    }
+
+    /// Record `node` if it declares something; returns the container its children live in
+    fn visit_node(&mut self, node: Node, parent: Option<&Container>) -> Option<Container> {
+        self.collect_imports_and_exports(node);
+
+        let declaration = self.classify(node, parent);
+
+        let mut next_parent = parent.cloned();
+        if let Some(decl) = declaration {
+            let start = node.start_position();
+            let end = node.end_position();
+            let symbol_parent = decl.parent_override.clone().or_else(|| parent.map(|p| p.name.clone()));
+
+            // Namespaces don't hide their public members from other files
+            let top_level = decl.parent_override.is_none()
+                && parent.map(|p| p.kind == "namespace").unwrap_or(true);
+            if decl.is_exported && top_level && !self.exports.contains(&decl.name) {
+                self.exports.push(decl.name.clone());
             }
+
+            self.symbols.push(Symbol {
+                name: decl.name.clone(),
+                kind: decl.kind.to_string(),
+                line: start.row + 1,
+                column: start.column,
+                end_line: end.row + 1,
+                end_column: end.column,
+                signature: self.signature(node),
+                documentation: self.documentation(node, decl.doc_anchor),
+                is_exported: decl.is_exported,
+                parent: symbol_parent,
+                metrics: matches!(decl.kind, "function" | "method").then(|| self.function_metrics(node)),
+            });
+
+            next_parent = Some(Container {
+                name: decl.container_name.unwrap_or(decl.name),
+                kind: decl.kind,
+            });
         }
-        
-        Ok(FileAnalysis {
-            file_path: file_path.to_string(),
-            symbols,
-            imports,
-            exports: Vec::new(),
-            complexity: 0,
-            dependencies: Vec::new(),
-            dependents: Vec::new(),
+        next_parent
+    }
+
+    // ---------- per-language declarations ----------
+
+    fn classify<'t>(&self, node: Node<'t>, parent: Option<&Container>) -> Option<Declaration<'t>> {
+        match self.lang {
+            Lang::TypeScript | Lang::Tsx => self.classify_typescript(node, parent),
+            Lang::Python => self.classify_python(node, parent),
+            Lang::Rust => self.classify_rust(node, parent),
+            Lang::Go => self.classify_go(node),
+            Lang::Java => self.classify_java(node),
+            Lang::C | Lang::Cpp => self.classify_c_family(node, parent),
+            Lang::CSharp => self.classify_csharp(node),
+            Lang::Json | Lang::Toml | Lang::Yaml => self.classify_config(node),
+        }
+    }
+
+    fn classify_typescript<'t>(&self, node: Node<'t>, parent: Option<&Container>) -> Option<Declaration<'t>> {
+        let kind = match node.kind() {
+            "function_declaration" | "generator_function_declaration" | "function_signature" => "function",
+            "class_declaration" | "abstract_class_declaration" => "class",
+            "interface_declaration" => "interface",
+            "type_alias_declaration" => "type",
+            "enum_declaration" => "enum",
+            "internal_module" | "module" => "namespace",
+            "method_definition" | "method_signature" | "abstract_method_signature" => "method",
+            "variable_declarator" => {
+                let value = node.child_by_field_name("value")?;
+                match value.kind() {
+                    "arrow_function" | "function_expression" | "function" | "generator_function" => {
+                        if parent.map(|p| p.kind == "class").unwrap_or(false) { "method" } else { "function" }
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        let name = self.field_text(node, "name")?;
+
+        // `export const f = () => {}` -> declarator / lexical_declaration / export_statement
+        let declaration_node = if node.kind() == "variable_declarator" {
+            node.parent().unwrap_or(node)
+        } else {
+            node
+        };
+        let export_node = declaration_node.parent().filter(|p| p.kind() == "export_statement");
+
+        Some(Declaration {
+            name,
+            kind,
+            is_exported: export_node.is_some(),
+            doc_anchor: export_node.unwrap_or(declaration_node),
+            container_name: None,
+            parent_override: None,
         })
     }
-    
-    /// Parse Rust (regex fallback)
-    fn parse_rust(&self, file_path: &str, content: &str) -> Result<FileAnalysis, Box<dyn Error>> {
-        let mut symbols = Vec::new();
-        let mut imports = Vec::new();
-        
-        // Extract functions
-        let function_regex = regex::Regex::new(r"(?:pub\s+)?fn\s+(\w+)")?;
-        for cap in function_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "function".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported: content[..name.start()].contains("pub"),
-                });
+
+    fn classify_python<'t>(&self, node: Node<'t>, parent: Option<&Container>) -> Option<Declaration<'t>> {
+        let kind = match node.kind() {
+            "function_definition" => {
+                if parent.map(|p| p.kind == "class").unwrap_or(false) { "method" } else { "function" }
             }
-        }
-        
-        // Extract structs
-        let struct_regex = regex::Regex::new(r"(?:pub\s+)?struct\s+(\w+)")?;
-        for cap in struct_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "struct".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported: content[..name.start()].contains("pub"),
-                });
+            "class_definition" => "class",
+            _ => return None,
+        };
+        let name = self.field_text(node, "name")?;
+
+        Some(Declaration {
+            is_exported: !name.starts_with('_'),
+            name,
+            kind,
+            doc_anchor: node,
+            container_name: None,
+            parent_override: None,
+        })
+    }
+
+    fn classify_rust<'t>(&self, node: Node<'t>, parent: Option<&Container>) -> Option<Declaration<'t>> {
+        let in_impl_or_trait = parent.map(|p| p.kind == "impl" || p.kind == "trait").unwrap_or(false);
+        let kind = match node.kind() {
+            "function_item" | "function_signature_item" => if in_impl_or_trait { "method" } else { "function" },
+            "struct_item" => "struct",
+            "enum_item" => "enum",
+            "union_item" => "union",
+            "trait_item" => "trait",
+            "type_item" => "type",
+            "mod_item" => "module",
+            "const_item" => "constant",
+            "static_item" => "static",
+            "macro_definition" => "macro",
+            "impl_item" => "impl",
+            _ => return None,
+        };
+
+        let (name, container_name) = if kind == "impl" {
+            let type_name = self.field_text(node, "type")?;
+            let name = match self.field_text(node, "trait") {
+                Some(trait_name) => format!("{} for {}", trait_name, type_name),
+                None => type_name.clone(),
+            };
+            (name, Some(type_name))
+        } else {
+            (self.field_text(node, "name")?, None)
+        };
+
+        let mut cursor = node.walk();
+        let is_exported = node.children(&mut cursor).any(|c| c.kind() == "visibility_modifier");
+
+        Some(Declaration {
+            name,
+            kind,
+            is_exported,
+            doc_anchor: node,
+            container_name,
+            parent_override: None,
+        })
+    }
+
+    fn classify_go<'t>(&self, node: Node<'t>) -> Option<Declaration<'t>> {
+        let mut parent_override = None;
+        let mut doc_anchor = node;
+        let kind = match node.kind() {
+            "function_declaration" => "function",
+            "method_declaration" => {
+                parent_override = node.child_by_field_name("receiver")
+                    .and_then(|receiver| self.go_receiver_type(receiver));
+                "method"
             }
-        }
-        
-        // Extract use statements
-        let use_regex = regex::Regex::new(r"use\s+([^;]+);")?;
-        for cap in use_regex.captures_iter(content) {
-            if let Some(path) = cap.get(1) {
-                imports.push(path.as_str().to_string());
+            "method_spec" | "method_elem" => "method",
+            "type_spec" | "type_alias" => {
+                // A lone `type X ...` keeps its doc comment on the type_declaration
+                if let Some(decl) = node.parent().filter(|p| p.kind() == "type_declaration") {
+                    doc_anchor = decl;
+                }
+                match node.child_by_field_name("type").map(|t| t.kind()) {
+                    Some("struct_type") => "struct",
+                    Some("interface_type") => "interface",
+                    _ => "type",
+                }
             }
-        }
-        
-        Ok(FileAnalysis {
-            file_path: file_path.to_string(),
-            symbols,
-            imports,
-            exports: Vec::new(),
-            complexity: 0,
-            dependencies: Vec::new(),
-            dependents: Vec::new(),
+            _ => return None,
+        };
+        let name = self.field_text(node, "name")?;
+
+        Some(Declaration {
+            is_exported: name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false),
+            name,
+            kind,
+            doc_anchor,
+            container_name: None,
+            parent_override,
         })
     }
-    
-    /// Parse Go (regex fallback)
-    fn parse_go(&self, file_path: &str, content: &str) -> Result<FileAnalysis, Box<dyn Error>> {
-        let mut symbols = Vec::new();
-        let mut imports = Vec::new();
-        
-        // Extract functions
-        let function_regex = regex::Regex::new(r"func\s+(\w+)\s*\(")?;
-        for cap in function_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                let is_exported = name.as_str().chars().next()
-                    .map(|c| c.is_uppercase())
+
+    fn classify_java<'t>(&self, node: Node<'t>) -> Option<Declaration<'t>> {
+        let kind = match node.kind() {
+            "class_declaration" => "class",
+            "interface_declaration" => "interface",
+            "enum_declaration" => "enum",
+            "record_declaration" => "record",
+            "annotation_type_declaration" => "annotation",
+            "method_declaration" | "constructor_declaration" => "method",
+            _ => return None,
+        };
+
+        Some(Declaration {
+            name: self.field_text(node, "name")?,
+            kind,
+            is_exported: self.has_modifier(node, "public"),
+            doc_anchor: node,
+            container_name: None,
+            parent_override: None,
+        })
+    }
+
+    fn classify_csharp<'t>(&self, node: Node<'t>) -> Option<Declaration<'t>> {
+        let kind = match node.kind() {
+            "class_declaration" => "class",
+            "struct_declaration" => "struct",
+            "interface_declaration" => "interface",
+            "enum_declaration" => "enum",
+            "record_declaration" | "record_struct_declaration" => "record",
+            "namespace_declaration" | "file_scoped_namespace_declaration" => "namespace",
+            "method_declaration" | "constructor_declaration" | "destructor_declaration" => "method",
+            "property_declaration" => "property",
+            "delegate_declaration" => "delegate",
+            _ => return None,
+        };
+
+        Some(Declaration {
+            name: self.field_text(node, "name")?,
+            kind,
+            is_exported: kind == "namespace" || self.has_modifier(node, "public"),
+            doc_anchor: node,
+            container_name: None,
+            parent_override: None,
+        })
+    }
+
+    fn classify_c_family<'t>(&self, node: Node<'t>, parent: Option<&Container>) -> Option<Declaration<'t>> {
+        let mut parent_override = None;
+        let (name, kind) = match node.kind() {
+            "function_definition" => {
+                let name_node = self.c_declarator_leaf(node)?;
+                if name_node.kind() == "qualified_identifier" {
+                    // `int Widget::size() { ... }` defined outside the class
+                    parent_override = self.field_text(name_node, "scope");
+                    (self.field_text(name_node, "name")?, "method")
+                } else {
+                    let in_type = parent.map(|p| p.kind == "class" || p.kind == "struct").unwrap_or(false);
+                    (self.text(name_node), if in_type { "method" } else { "function" })
+                }
+            }
+            // Only definitions; `struct Foo;` and `struct Foo *p` have no body
+            "class_specifier" | "struct_specifier" | "union_specifier" | "enum_specifier" => {
+                node.child_by_field_name("body")?;
+                let kind = match node.kind() {
+                    "class_specifier" => "class",
+                    "struct_specifier" => "struct",
+                    "union_specifier" => "union",
+                    _ => "enum",
+                };
+                (self.field_text(node, "name")?, kind)
+            }
+            "type_definition" => {
+                // `typedef struct Node {...} Node;` is already listed as the struct
+                let defines_named_type = node.child_by_field_name("type")
+                    .map(|t| t.child_by_field_name("body").is_some() && t.child_by_field_name("name").is_some())
                     .unwrap_or(false);
-                
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "function".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported,
-                });
+                if defines_named_type {
+                    return None;
+                }
+                (self.text(self.c_declarator_leaf(node)?), "type")
             }
+            "namespace_definition" => (self.field_text(node, "name")?, "namespace"),
+            _ => return None,
+        };
+
+        let mut cursor = node.walk();
+        let is_static = node.children(&mut cursor)
+            .any(|c| c.kind() == "storage_class_specifier" && self.text(c) == "static");
+
+        Some(Declaration {
+            name,
+            kind,
+            is_exported: !is_static,
+            // Doc comments sit above the `template <...>` line
+            doc_anchor: node.parent().filter(|p| p.kind() == "template_declaration").unwrap_or(node),
+            container_name: None,
+            parent_override,
+        })
+    }
+
+    /// Follow `declarator` fields down to the declared name
+    fn c_declarator_leaf<'t>(&self, node: Node<'t>) -> Option<Node<'t>> {
+        let mut current = node.child_by_field_name("declarator")?;
+        while let Some(inner) = c_inner_declarator(current) {
+            current = inner;
         }
-        
-        // Extract structs
-        let struct_regex = regex::Regex::new(r"type\s+(\w+)\s+struct")?;
-        for cap in struct_regex.captures_iter(content) {
-            if let Some(name) = cap.get(1) {
-                let is_exported = name.as_str().chars().next()
-                    .map(|c| c.is_uppercase())
-                    .unwrap_or(false);
-                
-                symbols.push(Symbol {
-                    name: name.as_str().to_string(),
-                    kind: "struct".to_string(),
-                    line: content[..name.start()].lines().count(),
-                    column: 0,
-                    signature: None,
-                    documentation: None,
-                    is_exported,
-                });
+        Some(current)
+    }
+
+    /// The `function_declarator` holding a C/C++ function's parameters
+    fn c_function_declarator<'t>(&self, node: Node<'t>) -> Option<Node<'t>> {
+        let mut current = node.child_by_field_name("declarator")?;
+        while current.kind() != "function_declarator" {
+            current = c_inner_declarator(current)?;
+        }
+        Some(current)
+    }
+
+    /// Java `modifiers` / C# `modifier` children containing `word`
+    fn has_modifier(&self, node: Node, word: &str) -> bool {
+        let mut cursor = node.walk();
+        let found = node.children(&mut cursor)
+            .filter(|c| c.kind() == "modifiers" || c.kind() == "modifier")
+            .any(|c| self.text(c).split_whitespace().any(|w| w == word));
+        found
+    }
+
+    /// Keys of JSON objects, YAML mappings and TOML tables, up to `MAX_OUTLINE_DEPTH`
+    fn classify_config<'t>(&self, node: Node<'t>) -> Option<Declaration<'t>> {
+        let (key, value, kind) = match node.kind() {
+            "pair" if self.lang == Lang::Json => {
+                (node.child_by_field_name("key")?, node.child_by_field_name("value"), None)
+            }
+            "block_mapping_pair" | "flow_pair" => {
+                (node.child_by_field_name("key")?, node.child_by_field_name("value"), None)
             }
+            "pair" => {
+                let key = node.named_child(0)?;
+                (key, key.next_named_sibling(), None)
+            }
+            "table" => (node.named_child(0)?, None, Some("table")),
+            "table_array_element" => (node.named_child(0)?, None, Some("table_array")),
+            _ => return None,
+        };
+
+        // Keys nested deeper than the outline shows, or inside arrays, are skipped
+        let mut depth = 1;
+        let mut ancestor = node.parent();
+        while let Some(current) = ancestor {
+            match current.kind() {
+                "pair" | "block_mapping_pair" | "flow_pair" | "table" | "table_array_element" => depth += 1,
+                "array" | "block_sequence" | "flow_sequence" => return None,
+                _ => {}
+            }
+            ancestor = current.parent();
+        }
+        if depth > MAX_OUTLINE_DEPTH {
+            return None;
         }
-        
-        // Extract imports
-        let import_regex = regex::Regex::new(r#"import\s+(?:\(([^)]+)\)|"([^"]+)")"#)?;
-        for cap in import_regex.captures_iter(content) {
-            if let Some(multi) = cap.get(1) {
-                for line in multi.as_str().lines() {
-                    let trimmed = line.trim().trim_matches('"');
-                    if !trimmed.is_empty() {
-                        imports.push(trimmed.to_string());
+
+        let kind = kind.unwrap_or_else(|| {
+            // YAML wraps values in block_node / flow_node
+            let value = value.map(|v| match v.kind() {
+                "block_node" | "flow_node" => v.named_child(0).unwrap_or(v),
+                _ => v,
+            });
+            match value.map(|v| v.kind()) {
+                Some("object" | "inline_table" | "block_mapping" | "flow_mapping") => "object",
+                Some("array" | "block_sequence" | "flow_sequence") => "array",
+                _ => "property",
+            }
+        });
+
+        Some(Declaration {
+            name: strip_quotes(&self.text(key)),
+            kind,
+            is_exported: false,
+            doc_anchor: node,
+            container_name: None,
+            parent_override: None,
+        })
+    }
+
+    /// `(s *Server)` -> "Server", `(l List[T])` -> "List"
+    fn go_receiver_type(&self, receiver: Node) -> Option<String> {
+        let mut cursor = receiver.walk();
+        let param = receiver.named_children(&mut cursor).next()?;
+        let type_text = self.field_text(param, "type")?;
+        let trimmed = type_text.trim_start_matches('*');
+        Some(trimmed.split('[').next().unwrap_or(trimmed).trim().to_string())
+    }
+
+    // ---------- imports / exports ----------
+
+    fn collect_imports_and_exports(&mut self, node: Node) {
+        match (self.lang, node.kind()) {
+            (Lang::TypeScript | Lang::Tsx, "import_statement") => {
+                if let Some(source) = self.field_text(node, "source") {
+                    self.imports.push(strip_quotes(&source));
+                }
+            }
+            (Lang::TypeScript | Lang::Tsx, "export_statement") => {
+                // Re-exports: export { a } from "./a"
+                if let Some(source) = self.field_text(node, "source") {
+                    self.imports.push(strip_quotes(&source));
+                }
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    if child.kind() == "export_clause" {
+                        let mut spec_cursor = child.walk();
+                        for spec in child.named_children(&mut spec_cursor) {
+                            if let Some(name) = self.field_text(spec, "alias").or_else(|| self.field_text(spec, "name")) {
+                                self.exports.push(name);
+                            }
+                        }
+                    }
+                }
+                if self.text(node).starts_with("export default") {
+                    self.exports.push("default".to_string());
+                }
+            }
+            (Lang::TypeScript | Lang::Tsx, "call_expression") => {
+                // require("x") and dynamic import("x")
+                let callee = node.child_by_field_name("function").map(|f| self.text(f));
+                if matches!(callee.as_deref(), Some("require") | Some("import")) {
+                    let first_arg = node.child_by_field_name("arguments")
+                        .and_then(|args| args.named_child(0))
+                        .filter(|arg| arg.kind() == "string");
+                    if let Some(arg) = first_arg {
+                        self.imports.push(strip_quotes(&self.text(arg)));
+                    }
+                }
+            }
+            (Lang::Python, "import_statement") => {
+                let mut cursor = node.walk();
+                let names: Vec<Node> = node.children_by_field_name("name", &mut cursor).collect();
+                for name in names {
+                    let module = if name.kind() == "aliased_import" {
+                        self.field_text(name, "name")
+                    } else {
+                        Some(self.text(name))
+                    };
+                    if let Some(module) = module {
+                        self.imports.push(module);
                     }
                 }
-            } else if let Some(single) = cap.get(2) {
-                imports.push(single.as_str().to_string());
             }
+            (Lang::Python, "import_from_statement") => {
+                if let Some(module) = self.field_text(node, "module_name") {
+                    self.imports.push(module);
+                }
+            }
+            (Lang::Rust, "use_declaration") => {
+                if let Some(argument) = self.field_text(node, "argument") {
+                    self.imports.push(argument);
+                }
+            }
+            // `mod foo;` pulls in another file; inline modules don't
+            (Lang::Rust, "mod_item") if node.child_by_field_name("body").is_none() => {
+                if let Some(name) = self.field_text(node, "name") {
+                    self.imports.push(format!("mod {}", name));
+                }
+            }
+            (Lang::Go, "import_spec") => {
+                if let Some(path) = self.field_text(node, "path") {
+                    self.imports.push(strip_quotes(&path));
+                }
+            }
+            (Lang::Java, "import_declaration") => {
+                let text = self.text(node);
+                let path = text.trim_start_matches("import").trim()
+                    .trim_start_matches("static ").trim()
+                    .trim_end_matches(';').trim();
+                self.imports.push(path.to_string());
+            }
+            (Lang::C | Lang::Cpp, "preproc_include") => {
+                if let Some(path) = self.field_text(node, "path") {
+                    self.imports.push(path.trim_matches(|c| c == '"' || c == '<' || c == '>').to_string());
+                }
+            }
+            (Lang::CSharp, "using_directive") => {
+                // `using Alias = Some.Namespace;` keeps the alias in the `name` field
+                let alias = node.child_by_field_name("name").map(|n| n.id());
+                let mut cursor = node.walk();
+                let target = node.named_children(&mut cursor).find(|c| Some(c.id()) != alias);
+                if let Some(target) = target {
+                    self.imports.push(self.text(target));
+                }
+            }
+            _ => {}
         }
-        
-        Ok(FileAnalysis {
-            file_path: file_path.to_string(),
-            symbols,
-            imports,
-            exports: Vec::new(),
-            complexity: 0,
-            dependencies: Vec::new(),
-            dependents: Vec::new(),
-        })
     }
-    
-    /// Clear cache
-    pub fn clear_cache(&mut self) {
-        self.ast_cache.clear();
+
+    // ---------- metrics ----------
+
+    fn function_metrics(&self, node: Node) -> SymbolMetrics {
+        // `const f = () => ...`: measure the function value, not the declarator
+        let function = match node.kind() {
+            "variable_declarator" => node.child_by_field_name("value").unwrap_or(node),
+            _ => node,
+        };
+
+        // C/C++ keep parameters on the (possibly nested) function declarator
+        let parameters_owner = match self.lang {
+            Lang::C | Lang::Cpp => self.c_function_declarator(node).unwrap_or(node),
+            _ => function,
+        };
+
+        let mut decisions = 0;
+        let mut nesting_depth = 0;
+        self.walk_control_flow(function, &mut decisions, &mut nesting_depth);
+
+        SymbolMetrics {
+            cyclomatic_complexity: decisions + 1,
+            line_count: node.end_position().row - node.start_position().row + 1,
+            nesting_depth,
+            parameter_count: self.parameter_count(parameters_owner),
+        }
     }
-    
-    /// Invalidate cache for specific file
-    pub fn invalidate_file(&mut self, file_path: &str) {
-        self.ast_cache.pop(file_path);
+
+    /// Count decision points and the deepest nesting below `function`, with an explicit
+    /// stack like `visit`
+    fn walk_control_flow(&self, function: Node, decisions: &mut usize, max_depth: &mut usize) {
+        let mut cursor = function.walk();
+        let mut stack: Vec<(Node, usize, &str)> = function.named_children(&mut cursor)
+            .map(|child| (child, 0, function.kind()))
+            .collect();
+        while let Some((node, depth, parent_kind)) = stack.pop() {
+            // Nested functions and classes are symbols of their own and get their own metrics
+            if self.classify(node, None).is_some() {
+                continue;
+            }
+
+            if self.is_decision_point(node) {
+                *decisions += 1;
+            }
+            let depth = if self.is_nesting_block(node, parent_kind) { depth + 1 } else { depth };
+            *max_depth = (*max_depth).max(depth);
+
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor).map(|child| (child, depth, node.kind())));
+        }
+    }
+
+    fn is_decision_point(&self, node: Node) -> bool {
+        let kind = node.kind();
+        if kind == "binary_expression" {
+            let operator = node.child_by_field_name("operator").map(|op| op.kind());
+            return matches!(operator, Some("&&") | Some("||") | Some("??"));
+        }
+
+        match self.lang {
+            Lang::TypeScript | Lang::Tsx => matches!(kind,
+                "if_statement" | "for_statement" | "for_in_statement" | "while_statement" | "do_statement"
+                | "switch_case" | "catch_clause" | "ternary_expression"),
+            Lang::Python => matches!(kind,
+                "if_statement" | "elif_clause" | "for_statement" | "while_statement" | "except_clause"
+                | "conditional_expression" | "boolean_operator" | "for_in_clause" | "if_clause" | "case_clause"),
+            Lang::Rust => match kind {
+                "if_expression" | "while_expression" | "for_expression" => true,
+                // Like `default:`, a catch-all arm adds no path of its own
+                "match_arm" => node.child_by_field_name("pattern")
+                    .map(|p| self.text(p).trim() != "_")
+                    .unwrap_or(true),
+                _ => false,
+            },
+            Lang::Go => matches!(kind,
+                "if_statement" | "for_statement" | "expression_case" | "type_case" | "communication_case"),
+            Lang::Java => match kind {
+                "if_statement" | "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement"
+                | "catch_clause" | "ternary_expression" => true,
+                "switch_label" => self.text(node).starts_with("case"),
+                _ => false,
+            },
+            Lang::C | Lang::Cpp => match kind {
+                "if_statement" | "for_statement" | "for_range_loop" | "while_statement" | "do_statement"
+                | "catch_clause" | "conditional_expression" => true,
+                // `default:` has no value
+                "case_statement" => node.child_by_field_name("value").is_some(),
+                _ => false,
+            },
+            Lang::CSharp => match kind {
+                "if_statement" | "for_statement" | "foreach_statement" | "while_statement" | "do_statement"
+                | "catch_clause" | "conditional_expression" => true,
+                "switch_section" => !self.text(node).starts_with("default"),
+                "switch_expression_arm" => !self.text(node).starts_with('_'),
+                _ => false,
+            },
+            Lang::Json | Lang::Toml | Lang::Yaml => false,
+        }
+    }
+
+    /// `parent_kind` comes from the walk, as `Node::parent` has to search down from the root
+    fn is_nesting_block(&self, node: Node, parent_kind: &str) -> bool {
+        let kind = node.kind();
+        // `else if` continues the chain rather than nesting inside it
+        match self.lang {
+            Lang::TypeScript | Lang::Tsx => match kind {
+                "if_statement" => parent_kind != "else_clause",
+                "for_statement" | "for_in_statement" | "while_statement" | "do_statement"
+                | "switch_statement" | "try_statement" => true,
+                _ => false,
+            },
+            Lang::Python => matches!(kind,
+                "if_statement" | "for_statement" | "while_statement" | "try_statement" | "with_statement"
+                | "match_statement"),
+            Lang::Rust => match kind {
+                "if_expression" => parent_kind != "else_clause",
+                "while_expression" | "for_expression" | "loop_expression" | "match_expression" => true,
+                _ => false,
+            },
+            Lang::Go => match kind {
+                "if_statement" => parent_kind != "if_statement",
+                "for_statement" | "expression_switch_statement" | "type_switch_statement"
+                | "select_statement" => true,
+                _ => false,
+            },
+            Lang::Java => match kind {
+                "if_statement" => parent_kind != "if_statement",
+                "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement"
+                | "switch_expression" | "try_statement" | "try_with_resources_statement" => true,
+                _ => false,
+            },
+            Lang::C | Lang::Cpp => match kind {
+                "if_statement" => parent_kind != "else_clause",
+                "for_statement" | "for_range_loop" | "while_statement" | "do_statement"
+                | "switch_statement" | "try_statement" => true,
+                _ => false,
+            },
+            Lang::CSharp => match kind {
+                "if_statement" => parent_kind != "if_statement" && parent_kind != "else_clause",
+                "for_statement" | "foreach_statement" | "while_statement" | "do_statement"
+                | "switch_statement" | "switch_expression" | "try_statement" => true,
+                _ => false,
+            },
+            Lang::Json | Lang::Toml | Lang::Yaml => false,
+        }
+    }
+
+    fn parameter_count(&self, function: Node) -> usize {
+        // Arrow functions with a single bare parameter use `parameter`
+        let params = match function.child_by_field_name("parameters") {
+            Some(params) => params,
+            None => return function.child_by_field_name("parameter").map(|_| 1).unwrap_or(0),
+        };
+
+        let mut cursor = params.walk();
+        let params: Vec<Node> = params.named_children(&mut cursor).collect();
+        params.iter()
+            .enumerate()
+            .map(|(i, param)| match param.kind() {
+                kind if kind.contains("comment") => 0,
+                "self_parameter" | "receiver_parameter" => 0,
+                // C `f(void)` takes no arguments
+                "parameter_declaration" if param.child_by_field_name("declarator").is_none()
+                    && self.text(*param).trim() == "void" => 0,
+                "identifier" if self.lang == Lang::Python && i == 0
+                    && matches!(self.text(*param).as_str(), "self" | "cls") => 0,
+                "keyword_separator" | "positional_separator" => 0,
+                // Go: `a, b int` declares two parameters
+                "parameter_declaration" => {
+                    let mut names = param.walk();
+                    let count = param.children_by_field_name("name", &mut names).count();
+                    count.max(1)
+                }
+                _ => 1,
+            })
+            .sum()
+    }
+
+    // ---------- signature / documentation ----------
+
+    /// Declaration text up to the body, whitespace collapsed
+    fn signature(&self, node: Node) -> Option<String> {
+        let body = match node.kind() {
+            "variable_declarator" => node.child_by_field_name("value")
+                .and_then(|value| value.child_by_field_name("body")),
+            _ => node.child_by_field_name("body"),
+        };
+
+        let text = match body {
+            Some(body) => std::str::from_utf8(&self.source[node.start_byte()..body.start_byte()]).ok()?,
+            // No body: keep the first line only (Go type specs, Rust consts, ...)
+            None => node.utf8_text(self.source).ok()?.lines().next()?,
+        };
+
+        let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let trimmed = collapsed
+            .trim_end_matches(|c: char| c == '{' || c == ':' || c.is_whitespace())
+            .trim_end_matches("=>")
+            .trim()
+            .to_string();
+
+        if trimmed.is_empty() {
+            return None;
+        }
+        Some(trimmed.chars().take(MAX_SIGNATURE_CHARS).collect())
+    }
+
+    fn documentation(&self, node: Node, doc_anchor: Node) -> Option<String> {
+        match self.lang {
+            Lang::Python => self.python_docstring(node),
+            _ if self.lang.is_config() => None,
+            _ => self.leading_comments(doc_anchor),
+        }
+    }
+
+    /// First statement of the body, if it is a string literal
+    fn python_docstring(&self, node: Node) -> Option<String> {
+        let body = node.child_by_field_name("body")?;
+        let first = body.named_child(0)?;
+        if first.kind() != "expression_statement" {
+            return None;
+        }
+        let string = first.named_child(0).filter(|n| n.kind() == "string")?;
+        let raw = self.text(string);
+        let without_prefix = raw.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+        let cleaned = without_prefix
+            .trim_start_matches("\"\"\"").trim_end_matches("\"\"\"")
+            .trim_start_matches("'''").trim_end_matches("'''")
+            .trim_matches(|c| c == '"' || c == '\'');
+        let doc = dedent(cleaned);
+        if doc.is_empty() { None } else { Some(doc) }
+    }
+
+    /// Doc comments directly above the anchor (`///`, `/** */`, Go `//`)
+    fn leading_comments(&self, anchor: Node) -> Option<String> {
+        let mut blocks: Vec<String> = Vec::new();
+        let mut expected_row = anchor.start_position().row;
+        let mut current = anchor.prev_sibling();
+
+        while let Some(sibling) = current {
+            // Comments must touch the declaration (or the comment below them)
+            if sibling.end_position().row + 1 < expected_row {
+                break;
+            }
+            let kind = sibling.kind();
+            if matches!(kind, "attribute_item" | "decorator" | "attribute_list") {
+                expected_row = sibling.start_position().row;
+                current = sibling.prev_sibling();
+                continue;
+            }
+            if !kind.contains("comment") {
+                break;
+            }
+
+            let text = self.text(sibling);
+            let doc = match self.lang {
+                Lang::Rust | Lang::C | Lang::Cpp | Lang::CSharp
+                    if text.starts_with("///") && !text.starts_with("////") =>
+                {
+                    let line = text.trim_start_matches("///").trim();
+                    // C# XML doc comments: keep the prose, drop the wrapper tags
+                    Some(line.replace("<summary>", "").replace("</summary>", "").trim().to_string())
+                }
+                Lang::Rust | Lang::TypeScript | Lang::Tsx | Lang::Java | Lang::C | Lang::Cpp
+                    if text.starts_with("/**") => Some(clean_block_comment(&text)),
+                Lang::Go if text.starts_with("//") => Some(text.trim_start_matches("//").trim().to_string()),
+                Lang::Go if text.starts_with("/*") => Some(clean_block_comment(&text)),
+                _ => None,
+            };
+            match doc {
+                Some(doc) => blocks.push(doc),
+                None => break,
+            }
+            expected_row = sibling.start_position().row;
+            current = sibling.prev_sibling();
+        }
+
+        if blocks.is_empty() {
+            return None;
+        }
+        blocks.reverse();
+        let joined = blocks.join("\n").trim().to_string();
+        if joined.is_empty() { None } else { Some(joined) }
     }
 }
 
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn file_metrics(symbols: &[Symbol], line_count: usize) -> FileMetrics {
+    let functions: Vec<&SymbolMetrics> = symbols.iter().filter_map(|s| s.metrics.as_ref()).collect();
+    let total: usize = functions.iter().map(|m| m.cyclomatic_complexity).sum();
+
+    FileMetrics {
+        line_count,
+        function_count: functions.len(),
+        average_complexity: if functions.is_empty() { 0.0 } else { total as f32 / functions.len() as f32 },
+        max_complexity: functions.iter().map(|m| m.cyclomatic_complexity).max().unwrap_or(0),
+        max_nesting_depth: functions.iter().map(|m| m.nesting_depth).max().unwrap_or(0),
+        hotspot_count: functions.iter().filter(|m| m.cyclomatic_complexity >= HOTSPOT_COMPLEXITY).count(),
+    }
+}
+
+/// `identifier`, `type_identifier`, `property_identifier`, `field_identifier`, ...
+fn is_identifier_kind(kind: &str) -> bool {
+    kind.ends_with("identifier") || kind == "constant"
+}
+
+/// One level down a C declarator: `*p`, `f(int)`, `(*fn)`
+fn c_inner_declarator(node: Node) -> Option<Node> {
+    match node.kind() {
+        "parenthesized_declarator" => node.named_child(0),
+        _ => node.child_by_field_name("declarator"),
+    }
+}
+
+fn strip_quotes(text: &str) -> String {
+    text.trim_matches(|c| c == '"' || c == '\'' || c == '`').to_string()
+}
+
+/// `/** a\n * b */` -> "a\nb"
+fn clean_block_comment(text: &str) -> String {
+    let inner = text
+        .trim_start_matches("/**")
+        .trim_start_matches("/*")
+        .trim_end_matches("*/");
+    inner
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Strip the common indentation of a (docstring) block
+fn dedent(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let indent = lines.iter()
+        .skip(1)
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines.iter()
+        .enumerate()
+        .map(|(i, l)| if i == 0 { l.trim() } else { l.get(indent..).unwrap_or(l.trim()).trim_end() })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
+    #[test]
+    fn test_deeply_nested_file_does_not_overflow() {
+        let mut parser = TreeSitterParser::new();
+        let depth = 50_000;
+        let content = format!("function deep() {{ return {}0{}; }}\n", "[".repeat(depth), "]".repeat(depth));
+        let analysis = parser.parse_file("deep.ts", &content).unwrap();
+        assert_eq!(analysis.symbols[0].name, "deep");
+    }
+
+    #[test]
+    fn test_cache_reparses_changed_content() {
+        let mut parser = TreeSitterParser::new();
+        let first = parser.parse_file("lib.rs", "fn old_name() {}\n").unwrap();
+        assert_eq!(first.symbols[0].name, "old_name");
+
+        let second = parser.parse_file("lib.rs", "fn new_name() {}\n").unwrap();
+        assert_eq!(second.symbols[0].name, "new_name");
+    }
+
     #[test]
     fn test_typescript_parsing() {
         let mut parser = TreeSitterParser::new();
@@ -343,19 +1242,23 @@ mod tests {
             export function hello() {
                 console.log("Hello");
             }
-            
+
             class MyClass {
                 constructor() {}
             }
         "#;
-        
+
         let result = parser.parse_file("test.ts", content).unwrap();
-        assert_eq!(result.symbols.len(), 2);
+        // hello, MyClass and its constructor
+        assert_eq!(result.symbols.len(), 3);
         assert_eq!(result.symbols[0].name, "hello");
         assert_eq!(result.symbols[0].kind, "function");
         assert!(result.symbols[0].is_exported);
+        assert_eq!(result.symbols[2].name, "constructor");
+        assert_eq!(result.symbols[2].kind, "method");
+        assert_eq!(result.symbols[2].parent.as_deref(), Some("MyClass"));
     }
-    
+
     #[test]
     fn test_python_parsing() {
         let mut parser = TreeSitterParser::new();
@@ -367,10 +1270,265 @@ class MyClass:
     def method(self):
         pass
         "#;
-        
+
         let result = parser.parse_file("test.py", content).unwrap();
         assert!(result.symbols.len() >= 2);
         assert_eq!(result.symbols[0].name, "my_function");
         assert_eq!(result.symbols[0].kind, "function");
     }
+
+    #[test]
+    fn test_rust_symbol_ranges() {
+        let mut parser = TreeSitterParser::new();
+        let content = "pub struct Point {\n    x: i32,\n}\n\nimpl Point {\n    fn label(&self) -> &'static str {\n        \"}\"\n    }\n}\n";
+
+        let result = parser.parse_file("point.rs", content).unwrap();
+        let point = result.symbols.iter().find(|s| s.kind == "struct").unwrap();
+        assert_eq!((point.line, point.end_line), (1, 3));
+        let imp = result.symbols.iter().find(|s| s.kind == "impl").unwrap();
+        assert_eq!(imp.name, "Point");
+        assert_eq!((imp.line, imp.end_line), (5, 9));
+        let label = result.symbols.iter().find(|s| s.name == "label").unwrap();
+        assert_eq!((label.line, label.end_line), (6, 8));
+    }
+
+    #[test]
+    fn test_signatures_docs_and_columns() {
+        let mut parser = TreeSitterParser::new();
+        let content = "use std::fmt;\n\n/// Adds two numbers.\n/// Wraps on overflow.\n#[inline]\npub fn add(a: u8, b: u8) -> u8 {\n    a.wrapping_add(b)\n}\n\nimpl fmt::Display for Point {\n    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }\n}\n";
+
+        let result = parser.parse_file("lib.rs", content).unwrap();
+        assert_eq!(result.imports, vec!["std::fmt".to_string()]);
+
+        let add = result.symbols.iter().find(|s| s.name == "add").unwrap();
+        assert_eq!(add.signature.as_deref(), Some("pub fn add(a: u8, b: u8) -> u8"));
+        assert_eq!(add.documentation.as_deref(), Some("Adds two numbers.\nWraps on overflow."));
+        assert!(add.is_exported);
+        assert_eq!(result.exports, vec!["add".to_string()]);
+
+        let fmt = result.symbols.iter().find(|s| s.name == "fmt").unwrap();
+        assert_eq!(fmt.kind, "method");
+        assert_eq!(fmt.parent.as_deref(), Some("Point"));
+        assert_eq!((fmt.line, fmt.column), (11, 4));
+    }
+
+    #[test]
+    fn test_go_methods_and_python_docstrings() {
+        let mut parser = TreeSitterParser::new();
+        let go = "package main\n\nimport \"fmt\"\n\n// Server serves.\ntype Server struct{}\n\nfunc (s *Server) Start() { fmt.Println() }\n";
+        let result = parser.parse_file("main.go", go).unwrap();
+        assert_eq!(result.imports, vec!["fmt".to_string()]);
+        let server = result.symbols.iter().find(|s| s.name == "Server").unwrap();
+        assert_eq!(server.kind, "struct");
+        assert_eq!(server.documentation.as_deref(), Some("Server serves."));
+        let start = result.symbols.iter().find(|s| s.name == "Start").unwrap();
+        assert_eq!(start.parent.as_deref(), Some("Server"));
+
+        let py = "import os\nfrom .models import User\n\nclass Repo:\n    def find(self, id):\n        \"\"\"Look a user up.\"\"\"\n        return None\n";
+        let result = parser.parse_file("repo.py", py).unwrap();
+        assert_eq!(result.imports, vec!["os".to_string(), ".models".to_string()]);
+        let find = result.symbols.iter().find(|s| s.name == "find").unwrap();
+        assert_eq!(find.kind, "method");
+        assert_eq!(find.documentation.as_deref(), Some("Look a user up."));
+        assert_eq!(find.signature.as_deref(), Some("def find(self, id)"));
+    }
+
+    #[test]
+    fn test_function_metrics() {
+        let mut parser = TreeSitterParser::new();
+        let content = r#"
+export function classify(n: number, strict: boolean): string {
+    if (n < 0 && strict) {
+        for (const d of [1, 2]) {
+            if (d > n) { return "x"; }
+        }
+    } else if (n === 0) {
+        return "zero";
+    }
+    const inner = () => { if (n) { return 1; } return 2; };
+    return n > 10 ? "big" : "small";
+}
+"#;
+
+        let result = parser.parse_file("metrics.ts", content).unwrap();
+        let classify = result.symbols.iter().find(|s| s.name == "classify").unwrap();
+        let metrics = classify.metrics.as_ref().unwrap();
+        // if, &&, for, if, else-if, ternary; the arrow function is measured separately
+        assert_eq!(metrics.cyclomatic_complexity, 7);
+        assert_eq!(metrics.nesting_depth, 3);
+        assert_eq!(metrics.parameter_count, 2);
+        assert_eq!(metrics.line_count, 11);
+
+        let inner = result.symbols.iter().find(|s| s.name == "inner").unwrap();
+        assert_eq!(inner.metrics.as_ref().unwrap().cyclomatic_complexity, 2);
+
+        assert_eq!(result.complexity, 9);
+        assert_eq!(result.metrics.function_count, 2);
+        assert_eq!(result.metrics.max_complexity, 7);
+        assert!(result.hotspots(HOTSPOT_COMPLEXITY).is_empty());
+        assert_eq!(result.hotspots(7)[0].name, "classify");
+    }
+
+    #[test]
+    fn test_metrics_skip_receivers_and_catch_all_arms() {
+        let mut parser = TreeSitterParser::new();
+        let rust = "impl A {\n    fn pick(&self, x: u8) -> u8 {\n        match x {\n            0 => 1,\n            1 | 2 => 2,\n            _ => 3,\n        }\n    }\n}\n";
+        let result = parser.parse_file("a.rs", rust).unwrap();
+        let pick = result.symbols.iter().find(|s| s.name == "pick").unwrap();
+        let metrics = pick.metrics.as_ref().unwrap();
+        assert_eq!((metrics.cyclomatic_complexity, metrics.parameter_count, metrics.nesting_depth), (3, 1, 1));
+
+        let python = "class C:\n    def m(self, a, b=1):\n        return a if b else [x for x in a if x]\n";
+        let result = parser.parse_file("c.py", python).unwrap();
+        let m = result.symbols.iter().find(|s| s.name == "m").unwrap();
+        let metrics = m.metrics.as_ref().unwrap();
+        assert_eq!((metrics.cyclomatic_complexity, metrics.parameter_count), (4, 2));
+
+        let go = "package p\n\nfunc Sum(a, b int, rest ...int) int {\n\tif a > b || b > 0 {\n\t\treturn a\n\t}\n\treturn b\n}\n";
+        let result = parser.parse_file("p.go", go).unwrap();
+        let sum = result.symbols.iter().find(|s| s.name == "Sum").unwrap();
+        let metrics = sum.metrics.as_ref().unwrap();
+        assert_eq!((metrics.cyclomatic_complexity, metrics.parameter_count), (3, 3));
+    }
+
+    #[test]
+    fn test_java_and_csharp_symbols() {
+        let mut parser = TreeSitterParser::new();
+        let java = r#"import java.util.List;
+import static java.lang.Math.max;
+
+/** Adds numbers. */
+public class Calc {
+    public Calc() {}
+
+    @Override
+    public int add(int a, int... rest) {
+        if (a > 0 && rest.length > 0) { return 1; } else if (a < 0) { return 2; }
+        return 0;
+    }
+}
+"#;
+        let result = parser.parse_file("Calc.java", java).unwrap();
+        assert_eq!(result.imports, vec!["java.util.List".to_string(), "java.lang.Math.max".to_string()]);
+        assert_eq!(result.exports, vec!["Calc".to_string()]);
+        let calc = result.symbols.iter().find(|s| s.kind == "class").unwrap();
+        assert_eq!(calc.documentation.as_deref(), Some("Adds numbers."));
+        let add = result.symbols.iter().find(|s| s.name == "add").unwrap();
+        assert_eq!(add.parent.as_deref(), Some("Calc"));
+        let metrics = add.metrics.as_ref().unwrap();
+        assert_eq!((metrics.cyclomatic_complexity, metrics.parameter_count, metrics.nesting_depth), (4, 2, 1));
+
+        let csharp = r#"using System;
+using Col = System.Collections.Generic;
+
+namespace App.Core {
+    /// <summary>Runs jobs.</summary>
+    public class Runner {
+        public int Count { get; set; }
+        private static int Run(int a, string b = "x") {
+            foreach (var x in xs) { if (a > 0 || b == null) { } }
+            return a > 0 ? 1 : 0;
+        }
+    }
+}
+"#;
+        let result = parser.parse_file("Runner.cs", csharp).unwrap();
+        assert_eq!(result.imports, vec!["System".to_string(), "System.Collections.Generic".to_string()]);
+        let runner = result.symbols.iter().find(|s| s.name == "Runner").unwrap();
+        assert_eq!(runner.parent.as_deref(), Some("App.Core"));
+        assert_eq!(runner.documentation.as_deref(), Some("Runs jobs."));
+        assert!(result.exports.contains(&"Runner".to_string()));
+        let count = result.symbols.iter().find(|s| s.name == "Count").unwrap();
+        assert_eq!(count.kind, "property");
+        let run = result.symbols.iter().find(|s| s.name == "Run").unwrap();
+        assert!(!run.is_exported);
+        let metrics = run.metrics.as_ref().unwrap();
+        assert_eq!((metrics.cyclomatic_complexity, metrics.parameter_count, metrics.nesting_depth), (5, 2, 2));
+    }
+
+    #[test]
+    fn test_c_and_cpp_symbols() {
+        let mut parser = TreeSitterParser::new();
+        let c = "#include <stdio.h>\n#include \"util.h\"\n\ntypedef struct Node { int v; } Node;\ntypedef int (*cmp_fn)(int, int);\n\n/** Entry point. */\nstatic int run(void) {\n    switch (1) { case 1: return 1; default: return 0; }\n}\n";
+        let result = parser.parse_file("main.c", c).unwrap();
+        assert_eq!(result.imports, vec!["stdio.h".to_string(), "util.h".to_string()]);
+        let names: Vec<(&str, &str)> = result.symbols.iter().map(|s| (s.name.as_str(), s.kind.as_str())).collect();
+        assert_eq!(names, vec![("Node", "struct"), ("cmp_fn", "type"), ("run", "function")]);
+        let run = &result.symbols[2];
+        assert!(!run.is_exported);
+        assert_eq!(run.documentation.as_deref(), Some("Entry point."));
+        let metrics = run.metrics.as_ref().unwrap();
+        assert_eq!((metrics.cyclomatic_complexity, metrics.parameter_count), (2, 0));
+
+        let cpp = "namespace app {\n/// A widget.\nclass Widget {\npublic:\n    int size() const { return n > 0 ? n : 0; }\n};\nint Widget::grow(int by) {\n    for (auto x : xs) { if (x) {} else if (by) {} }\n    return by;\n}\n}\n";
+        let result = parser.parse_file("widget.hpp", cpp).unwrap();
+        let widget = result.symbols.iter().find(|s| s.name == "Widget").unwrap();
+        assert_eq!((widget.kind.as_str(), widget.parent.as_deref()), ("class", Some("app")));
+        assert_eq!(widget.documentation.as_deref(), Some("A widget."));
+        let size = result.symbols.iter().find(|s| s.name == "size").unwrap();
+        assert_eq!((size.kind.as_str(), size.parent.as_deref()), ("method", Some("Widget")));
+        let grow = result.symbols.iter().find(|s| s.name == "grow").unwrap();
+        assert_eq!((grow.kind.as_str(), grow.parent.as_deref()), ("method", Some("Widget")));
+        let metrics = grow.metrics.as_ref().unwrap();
+        assert_eq!((metrics.cyclomatic_complexity, metrics.parameter_count, metrics.nesting_depth), (4, 1, 2));
+    }
+
+    #[test]
+    fn test_identifier_lookup() {
+        let parser = TreeSitterParser::new();
+        let content = "import { total } from './cart';\n\nexport function checkout(items: Item[]) {\n    return total(items);\n}\n";
+
+        // On the `total` call, at its first character and right after its last
+        assert_eq!(parser.identifier_at("a.ts", content, 4, 11).unwrap().as_deref(), Some("total"));
+        assert_eq!(parser.identifier_at("a.ts", content, 4, 16).unwrap().as_deref(), Some("total"));
+        assert_eq!(parser.identifier_at("a.ts", content, 3, 35).unwrap().as_deref(), Some("Item"));
+        assert_eq!(parser.identifier_at("a.ts", content, 2, 0).unwrap(), None);
+
+        let occurrences = parser.find_identifiers("a.ts", content, "total").unwrap();
+        let positions: Vec<(usize, usize, usize)> = occurrences.iter().map(|o| (o.line, o.column, o.end_column)).collect();
+        assert_eq!(positions, vec![(1, 9, 14), (4, 11, 16)]);
+    }
+
+    #[test]
+    fn test_config_outlines() {
+        let mut parser = TreeSitterParser::new();
+        let outline = |result: &FileAnalysis| -> Vec<(String, String, Option<String>)> {
+            result.symbols.iter().map(|s| (s.name.clone(), s.kind.clone(), s.parent.clone())).collect()
+        };
+        let entry = |name: &str, kind: &str, parent: Option<&str>| (name.to_string(), kind.to_string(), parent.map(String::from));
+
+        let json = "{\n  \"name\": \"x\",\n  \"scripts\": {\"build\": \"tsc\", \"deep\": {\"a\": 1}},\n  \"list\": [{\"skip\": 1}]\n}\n";
+        let result = parser.parse_file("package.json", json).unwrap();
+        assert_eq!(outline(&result), vec![
+            entry("name", "property", None),
+            entry("scripts", "object", None),
+            entry("build", "property", Some("scripts")),
+            entry("deep", "object", Some("scripts")),
+            entry("list", "array", None),
+        ]);
+        assert_eq!(result.symbols[1].line, 3);
+
+        let toml = "title = \"x\"\n\n[package]\nname = \"corex\"\n\n[[bin]]\nname = \"a\"\n\n[dependencies.serde]\nfeatures = { a = 1 }\n";
+        let result = parser.parse_file("Cargo.toml", toml).unwrap();
+        assert_eq!(outline(&result), vec![
+            entry("title", "property", None),
+            entry("package", "table", None),
+            entry("name", "property", Some("package")),
+            entry("bin", "table_array", None),
+            entry("name", "property", Some("bin")),
+            entry("dependencies.serde", "table", None),
+            entry("features", "object", Some("dependencies.serde")),
+        ]);
+
+        let yaml = "version: \"3\"\nservices:\n  web:\n    image: nginx\n  db:\n    image: pg\nlist:\n  - a: 1\n";
+        let result = parser.parse_file("compose.yml", yaml).unwrap();
+        assert_eq!(outline(&result), vec![
+            entry("version", "property", None),
+            entry("services", "object", None),
+            entry("web", "object", Some("services")),
+            entry("db", "object", Some("services")),
+            entry("list", "array", None),
+        ]);
+        assert_eq!(result.complexity, 0);
+    }
 }
@@ -1,8 +1,18 @@
 // src-tauri/src/vector_db.rs
-// Vector Database Integration disabled due to dependency issues
-// Re-enable when lancedb and fastembed dependencies are properly configured
+// Embedded vector store backed by SQLite (brute-force cosine search)
 
+use crate::gguf::GgufState;
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Dimension of the built-in hashed embedding
+pub const DEFAULT_EMBEDDING_DIM: usize = 384;
+
+/// SQLite file created inside the `db_path` directory
+const DB_FILE_NAME: &str = "chunks.sqlite3";
 
 /// Represents a code chunk stored in the vector database
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,41 +33,550 @@ pub struct CodeChunk {
     pub timestamp: u64,
 }
 
+/// A chunk returned by a similarity query together with its cosine score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredChunk {
+    pub chunk: CodeChunk,
+    pub score: f32,
+}
+
+/// Where embeddings for stored chunks and queries come from
+#[derive(Clone)]
+pub enum EmbeddingSource {
+    /// Built-in hashed bag-of-tokens embedding (no model needed)
+    Hashed,
+    /// In-process embedding with a GGUF model loaded through `GgufState`
+    ///
+    /// The model is looked up in the pool per call, so unloading it frees it.
+    Local {
+        model_path: String,
+        state: Arc<Mutex<GgufState>>,
+    },
+    /// OpenAI-compatible `/v1/embeddings` endpoint (LM Studio etc.)
+    Http { endpoint: String },
+}
+
+impl EmbeddingSource {
+    /// Short label for logs and the frontend
+    pub fn describe(&self) -> String {
+        match self {
+            EmbeddingSource::Hashed => "hashed".to_string(),
+            EmbeddingSource::Local { model_path, .. } => format!("local:{}", model_path),
+            EmbeddingSource::Http { endpoint } => format!("http:{}", endpoint),
+        }
+    }
+}
+
 /// Vector database interface for semantic code search
 pub struct VectorDB {
-    _db_path: String,
+    db_path: PathBuf,
+    conn: Arc<Mutex<Connection>>,
+    embedder: RwLock<EmbeddingSource>,
 }
 
 impl VectorDB {
     /// Initialize the vector database in embedded mode
+    ///
+    /// `db_path` is a directory; the store lives in `chunks.sqlite3` inside it
+    /// and survives restarts.
     pub async fn init(db_path: &str) -> Result<Self, String> {
+        let dir = PathBuf::from(db_path);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Vector DB klasörü oluşturulamadı: {}", e))?;
+
+        let conn = Connection::open(dir.join(DB_FILE_NAME))
+            .map_err(|e| format!("Vector DB açılamadı: {}", e))?;
+
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             CREATE TABLE IF NOT EXISTS chunks (
+                 id          TEXT PRIMARY KEY,
+                 file_path   TEXT NOT NULL,
+                 content     TEXT NOT NULL,
+                 embedding   BLOB NOT NULL,
+                 dim         INTEGER NOT NULL,
+                 symbol_name TEXT,
+                 chunk_type  TEXT NOT NULL,
+                 timestamp   INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_chunks_file_path ON chunks(file_path);
+             CREATE TABLE IF NOT EXISTS file_hashes (
+                 file_path TEXT PRIMARY KEY,
+                 hash      TEXT NOT NULL
+             );",
+        )
+        .map_err(|e| format!("Vector DB şeması oluşturulamadı: {}", e))?;
+
+        log::info!("📦 Vector DB açıldı: {}", dir.display());
+
         Ok(Self {
-            _db_path: db_path.to_string(),
+            db_path: dir,
+            conn: Arc::new(Mutex::new(conn)),
+            embedder: RwLock::new(EmbeddingSource::Hashed),
         })
     }
-    
+
+    /// Switch the embedder used by `generate_embedding` and `upsert`
+    ///
+    /// Chunks embedded by a different model are not comparable, so callers
+    /// should re-index after switching.
+    pub fn set_embedding_source(&self, source: EmbeddingSource) {
+        log::info!("🧩 Vector DB embedder: {}", source.describe());
+        match self.embedder.write() {
+            Ok(mut guard) => *guard = source,
+            Err(poisoned) => *poisoned.into_inner() = source,
+        }
+    }
+
+    /// Currently configured embedder
+    pub fn embedding_source(&self) -> EmbeddingSource {
+        match self.embedder.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Directory the store was opened in
+    pub fn path(&self) -> &Path {
+        &self.db_path
+    }
+
     /// Generate embedding for a given text
-    pub async fn generate_embedding(&self, _text: &str) -> Result<Vec<f32>, String> {
-        Ok(vec![0.0; 384])
+    ///
+    /// Without a configured model this falls back to feature hashing over
+    /// identifier-aware tokens, which is lexical rather than semantic.
+    pub async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, String> {
+        self.generate_embeddings(&[text.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| "Embedding üretilemedi".to_string())
+    }
+
+    /// Generate embeddings for several texts with the configured embedder
+    pub async fn generate_embeddings(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        match self.embedding_source() {
+            EmbeddingSource::Hashed => Ok(texts
+                .iter()
+                .map(|text| hashed_embedding(text, DEFAULT_EMBEDDING_DIM))
+                .collect()),
+            EmbeddingSource::Local { model_path, state } => {
+                crate::gguf::embed_with_pooled_model(&state, &model_path, texts.to_vec()).await
+            }
+            EmbeddingSource::Http { endpoint } => {
+                let mut embeddings = Vec::with_capacity(texts.len());
+                for text in texts {
+                    embeddings.push(
+                        crate::commands::create_embedding_bge(text.clone(), Some(endpoint.clone()))
+                            .await?,
+                    );
+                }
+                Ok(embeddings)
+            }
+        }
     }
 
     /// Insert or update code chunks in the vector database
-    pub async fn upsert(&self, _chunks: Vec<CodeChunk>) -> Result<(), String> {
-        Ok(())
+    ///
+    /// Chunks with an empty `embedding` get one from the configured embedder.
+    pub async fn upsert(&self, mut chunks: Vec<CodeChunk>) -> Result<(), String> {
+        let missing: Vec<usize> = chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| chunk.embedding.is_empty())
+            .map(|(i, _)| i)
+            .collect();
+
+        if !missing.is_empty() {
+            let texts: Vec<String> = missing.iter().map(|&i| chunks[i].content.clone()).collect();
+            let embeddings = self.generate_embeddings(&texts).await?;
+            for (i, embedding) in missing.into_iter().zip(embeddings) {
+                chunks[i].embedding = embedding;
+            }
+        }
+
+        self.with_conn(move |conn| {
+            let tx = conn.transaction().map_err(|e| e.to_string())?;
+            {
+                let mut stmt = tx
+                    .prepare(
+                        "INSERT OR REPLACE INTO chunks
+                         (id, file_path, content, embedding, dim, symbol_name, chunk_type, timestamp)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    )
+                    .map_err(|e| e.to_string())?;
+
+                for chunk in &chunks {
+                    stmt.execute(params![
+                        chunk.id,
+                        chunk.file_path,
+                        chunk.content,
+                        embedding_to_blob(&chunk.embedding),
+                        chunk.embedding.len() as i64,
+                        chunk.symbol_name,
+                        chunk.chunk_type,
+                        chunk.timestamp as i64,
+                    ])
+                    .map_err(|e| format!("Chunk yazılamadı [{}]: {}", chunk.id, e))?;
+                }
+            }
+            tx.commit().map_err(|e| e.to_string())
+        })
+        .await
     }
-    
+
     /// Query the vector database for similar code chunks
     pub async fn query(
         &self,
-        _query_embedding: Vec<f32>,
-        _top_k: usize,
-        _path_filter: Option<String>,
+        query_embedding: Vec<f32>,
+        top_k: usize,
+        path_filter: Option<String>,
     ) -> Result<Vec<CodeChunk>, String> {
-        Ok(Vec::new())
+        let scored = self.query_scored(query_embedding, top_k, path_filter).await?;
+        Ok(scored.into_iter().map(|s| s.chunk).collect())
+    }
+
+    /// Same as `query`, but keeps the cosine similarity of every hit
+    ///
+    /// `path_filter` restricts the search to files whose path starts with it.
+    /// Rows embedded with a different dimension than the query are skipped.
+    /// The scan covers the whole table, so it runs on the blocking pool.
+    pub async fn query_scored(
+        &self,
+        query_embedding: Vec<f32>,
+        top_k: usize,
+        path_filter: Option<String>,
+    ) -> Result<Vec<ScoredChunk>, String> {
+        if top_k == 0 || query_embedding.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_norm = l2_norm(&query_embedding);
+        if query_norm == 0.0 {
+            return Ok(Vec::new());
+        }
+
+        self.with_conn(move |conn| scan_scored(conn, &query_embedding, query_norm, top_k, path_filter))
+            .await
     }
-    
+
+    /// Run `work` against the connection on the blocking pool, so SQLite IO and
+    /// waiting for the lock never stall a runtime worker
+    async fn with_conn<T: Send + 'static>(
+        &self,
+        work: impl FnOnce(&mut Connection) -> Result<T, String> + Send + 'static,
+    ) -> Result<T, String> {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|e| e.to_string())?;
+            work(&mut conn)
+        })
+        .await
+        .map_err(|e| format!("Vector DB sorgusu başarısız: {}", e))?
+    }
+
     /// Delete all chunks associated with a file
-    pub async fn delete_file(&self, _file_path: &str) -> Result<(), String> {
-        Ok(())
+    pub async fn delete_file(&self, file_path: &str) -> Result<(), String> {
+        let file_path = file_path.to_string();
+        self.with_conn(move |conn| {
+            conn.execute("DELETE FROM chunks WHERE file_path = ?1", params![file_path])
+                .map_err(|e| e.to_string())?;
+            conn.execute("DELETE FROM file_hashes WHERE file_path = ?1", params![file_path])
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Delete every chunk of files below a directory (used when it is removed or renamed)
+    pub async fn delete_under(&self, dir: &str) -> Result<(), String> {
+        let prefix = format!("{}{}", dir.trim_end_matches(['/', '\\']), std::path::MAIN_SEPARATOR);
+        self.with_conn(move |conn| {
+            for table in ["chunks", "file_hashes"] {
+                conn.execute(
+                    &format!("DELETE FROM {} WHERE substr(file_path, 1, length(?1)) = ?1", table),
+                    params![prefix],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Content hash recorded the last time a file was indexed
+    pub async fn file_hash(&self, file_path: &str) -> Result<Option<String>, String> {
+        let file_path = file_path.to_string();
+        self.with_conn(move |conn| {
+            conn.query_row(
+                "SELECT hash FROM file_hashes WHERE file_path = ?1",
+                params![file_path],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+        })
+        .await
+    }
+
+    /// Record the content hash a file was indexed with
+    pub async fn set_file_hash(&self, file_path: &str, hash: &str) -> Result<(), String> {
+        let (file_path, hash) = (file_path.to_string(), hash.to_string());
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO file_hashes (file_path, hash) VALUES (?1, ?2)",
+                params![file_path, hash],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Fetch a single chunk by id
+    pub async fn get(&self, id: &str) -> Result<Option<CodeChunk>, String> {
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            conn.query_row(
+                "SELECT id, file_path, content, embedding, symbol_name, chunk_type, timestamp
+                 FROM chunks WHERE id = ?1",
+                params![id],
+                row_to_chunk,
+            )
+            .optional()
+            .map_err(|e| e.to_string())
+        })
+        .await
+    }
+
+    /// Number of stored chunks
+    pub async fn count(&self) -> Result<usize, String> {
+        let count: i64 = self
+            .with_conn(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM chunks", [], |row| row.get(0))
+                    .map_err(|e| e.to_string())
+            })
+            .await?;
+        Ok(count as usize)
+    }
+}
+
+fn row_to_chunk(row: &rusqlite::Row<'_>) -> rusqlite::Result<CodeChunk> {
+    let blob: Vec<u8> = row.get(3)?;
+    let timestamp: i64 = row.get(6)?;
+    Ok(CodeChunk {
+        id: row.get(0)?,
+        file_path: row.get(1)?,
+        content: row.get(2)?,
+        embedding: blob_to_embedding(&blob),
+        symbol_name: row.get(4)?,
+        chunk_type: row.get(5)?,
+        timestamp: timestamp.max(0) as u64,
+    })
+}
+
+fn embedding_to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn blob_to_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn l2_norm(v: &[f32]) -> f32 {
+    v.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+fn cosine_similarity(query: &[f32], query_norm: f32, other: &[f32]) -> f32 {
+    let other_norm = l2_norm(other);
+    if other_norm == 0.0 {
+        return 0.0;
+    }
+    let dot: f32 = query.iter().zip(other).map(|(a, b)| a * b).sum();
+    dot / (query_norm * other_norm)
+}
+
+/// Split text into lowercase tokens, breaking camelCase and snake_case apart
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if word.is_empty() {
+            continue;
+        }
+        let mut current = String::new();
+        let mut prev_lower = false;
+        for c in word.chars() {
+            if c.is_uppercase() && prev_lower && !current.is_empty() {
+                tokens.push(current.to_lowercase());
+                current.clear();
+            }
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+            current.push(c);
+        }
+        if !current.is_empty() {
+            tokens.push(current.to_lowercase());
+        }
+        if word.chars().any(|c| c.is_uppercase()) {
+            tokens.push(word.to_lowercase());
+        }
+    }
+    tokens
+}
+
+/// Score every row with the query's dimension and keep the best `top_k`
+fn scan_scored(
+    conn: &Connection,
+    query_embedding: &[f32],
+    query_norm: f32,
+    top_k: usize,
+    path_filter: Option<String>,
+) -> Result<Vec<ScoredChunk>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, file_path, content, embedding, symbol_name, chunk_type, timestamp
+             FROM chunks
+             WHERE dim = ?1 AND (?2 IS NULL OR substr(file_path, 1, length(?2)) = ?2)",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![query_embedding.len() as i64, path_filter], row_to_chunk)
+        .map_err(|e| e.to_string())?;
+
+    let mut scored: Vec<ScoredChunk> = Vec::new();
+    for row in rows {
+        let chunk = row.map_err(|e| e.to_string())?;
+        let score = cosine_similarity(query_embedding, query_norm, &chunk.embedding);
+        scored.push(ScoredChunk { chunk, score });
+    }
+
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(top_k);
+
+    Ok(scored)
+}
+
+/// L2-normalized hashed bag-of-tokens embedding
+///
+/// Token hashes come from SHA-256 so stored vectors stay comparable across
+/// builds; `DefaultHasher` output may change between Rust releases.
+pub fn hashed_embedding(text: &str, dim: usize) -> Vec<f32> {
+    let mut vector = vec![0.0f32; dim];
+    if dim == 0 {
+        return vector;
+    }
+
+    for token in tokenize(text) {
+        let digest = Sha256::digest(token.as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        let hash = u64::from_le_bytes(bytes);
+        let index = (hash % dim as u64) as usize;
+        let sign = if (hash >> 63) == 0 { 1.0 } else { -1.0 };
+        vector[index] += sign;
+    }
+
+    let norm = l2_norm(&vector);
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+    vector
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_db_dir() -> String {
+        std::env::temp_dir()
+            .join(format!("corex_vector_db_{}", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn chunk(id: &str, file_path: &str, content: &str) -> CodeChunk {
+        CodeChunk {
+            id: id.to_string(),
+            file_path: file_path.to_string(),
+            content: content.to_string(),
+            embedding: vec![],
+            symbol_name: None,
+            chunk_type: "Function".to_string(),
+            timestamp: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_query_and_persist() {
+        let dir = temp_db_dir();
+        {
+            let db = VectorDB::init(&dir).await.unwrap();
+            db.upsert(vec![
+                chunk("src/a.rs:1:5", "src/a.rs", "fn parse_config(path: &str) -> Config"),
+                chunk("lib/b.ts:1:3", "lib/b.ts", "export function renderButton() {}"),
+            ])
+            .await
+            .unwrap();
+        }
+
+        // Reopen to make sure the rows survived
+        let db = VectorDB::init(&dir).await.unwrap();
+        assert_eq!(db.count().await.unwrap(), 2);
+
+        let query = db.generate_embedding("parse config").await.unwrap();
+        let results = db.query_scored(query.clone(), 1, None).await.unwrap();
+        assert_eq!(results[0].chunk.id, "src/a.rs:1:5");
+        assert!(results[0].score > 0.0);
+
+        let filtered = db.query(query, 5, Some("lib/".to_string())).await.unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].file_path, "lib/b.ts");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_delete_file() {
+        let dir = temp_db_dir();
+        let db = VectorDB::init(&dir).await.unwrap();
+        db.upsert(vec![
+            chunk("a:1:1", "a", "alpha"),
+            chunk("a:2:2", "a", "beta"),
+            chunk("b:1:1", "b", "gamma"),
+        ])
+        .await
+        .unwrap();
+
+        db.set_file_hash("a", "abc").await.unwrap();
+        assert_eq!(db.file_hash("a").await.unwrap().as_deref(), Some("abc"));
+
+        db.delete_file("a").await.unwrap();
+        assert_eq!(db.count().await.unwrap(), 1);
+        assert_eq!(db.file_hash("a").await.unwrap(), None);
+        assert!(db.get("b:1:1").await.unwrap().is_some());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_hashed_embedding_is_stable() {
+        // Stored vectors must keep matching queries after a toolchain upgrade
+        let embedding = hashed_embedding("parse", DEFAULT_EMBEDDING_DIM);
+        assert_eq!(embedding[48], 1.0);
+        assert_eq!(embedding.iter().filter(|v| **v != 0.0).count(), 1);
+    }
+
+    #[test]
+    fn test_tokenize_splits_identifiers() {
+        let tokens = tokenize("parseConfig read_file");
+        assert!(tokens.contains(&"parse".to_string()));
+        assert!(tokens.contains(&"config".to_string()));
+        assert!(tokens.contains(&"parseconfig".to_string()));
+        assert!(tokens.contains(&"read".to_string()));
+        assert!(tokens.contains(&"file".to_string()));
     }
 }
@@ -0,0 +1,348 @@
+// src-tauri/src/workspace_indexer.rs
+// Background workspace indexing: initial walk + file watching, re-embedding only changed files
+
+use crate::commands::{chunk_for_index, invalidate_parsed_file, is_ignored_path, IGNORED_DIRS};
+use crate::vector_db::VectorDB;
+use log::{error, info, warn};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// File extensions worth embedding (source and config files)
+const INDEXABLE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "mjs", "cjs", "py", "go", "java", "kt", "c", "h", "cc", "cpp",
+    "cxx", "hh", "hpp", "hxx", "cs", "rb", "php", "swift", "json", "toml", "yaml", "yml", "md", "sql", "sh", "css", "html",
+];
+
+/// Larger files are usually generated or vendored
+const MAX_INDEXED_FILE_BYTES: u64 = 512 * 1024;
+
+/// Watch events arriving within this window are handled as one batch
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Progress event, emitted like `download-progress`
+const PROGRESS_EVENT: &str = "workspace-index-progress";
+
+struct IndexerHandle {
+    // Dropping the watcher stops it and closes the event channel
+    _watcher: RecommendedWatcher,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Running indexers keyed by workspace root
+#[derive(Default)]
+pub struct WorkspaceIndexerState {
+    indexers: Mutex<HashMap<String, IndexerHandle>>,
+}
+
+/// What happened to a single path during indexing
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexOutcome {
+    /// Re-embedded into this many chunks
+    Indexed(usize),
+    /// Content hash matched the stored one
+    Unchanged,
+    /// Path no longer exists; its chunks were dropped
+    Removed,
+    /// Not a file we embed (binary, too large, unknown extension)
+    Skipped,
+}
+
+/// True if the file has an extension we embed
+pub fn is_indexable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| INDEXABLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// SHA-256 of the file content, hex encoded
+pub fn content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Walk a directory with the same ignore list as `get_all_files`
+pub fn collect_workspace_files(root: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_str().unwrap_or("");
+            e.depth() == 0 || !IGNORED_DIRS.contains(&name)
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_indexable(e.path()))
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Bring the index for one path up to date
+async fn index_path(db: &VectorDB, path: &Path) -> Result<IndexOutcome, String> {
+    let file_path = path.to_string_lossy().to_string();
+
+    if !path.exists() {
+        invalidate_parsed_file(&file_path).await;
+        db.delete_file(&file_path).await?;
+        // The path may have been a directory that was removed or renamed away
+        db.delete_under(&file_path).await?;
+        return Ok(IndexOutcome::Removed);
+    }
+
+    if !path.is_file() || !is_indexable(path) {
+        return Ok(IndexOutcome::Skipped);
+    }
+
+    let too_large = tokio::fs::metadata(path)
+        .await
+        .map(|m| m.len() > MAX_INDEXED_FILE_BYTES)
+        .unwrap_or(true);
+    // A file that grew past the limit or turned binary must not keep its old chunks
+    if too_large {
+        db.delete_file(&file_path).await?;
+        return Ok(IndexOutcome::Skipped);
+    }
+
+    // Non UTF-8 files are treated as binary
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(_) => {
+            db.delete_file(&file_path).await?;
+            return Ok(IndexOutcome::Skipped);
+        }
+    };
+
+    let hash = content_hash(&content);
+    if db.file_hash(&file_path).await?.as_deref() == Some(hash.as_str()) {
+        return Ok(IndexOutcome::Unchanged);
+    }
+
+    // The parser cache checks the content hash, so changed content is parsed again
+    let chunks = chunk_for_index(&file_path, &content, "Module").await;
+    let chunk_count = chunks.len();
+
+    db.delete_file(&file_path).await?;
+    db.upsert(chunks).await?;
+    db.set_file_hash(&file_path, &hash).await?;
+
+    Ok(IndexOutcome::Indexed(chunk_count))
+}
+
+fn emit_progress(app: &AppHandle, root: &str, phase: &str, done: usize, total: usize, file: &str, outcome: &str) {
+    let progress = if total > 0 {
+        done as f64 / total as f64 * 100.0
+    } else {
+        100.0
+    };
+
+    if let Err(e) = app.emit(PROGRESS_EVENT, json!({
+        "root": root,
+        "phase": phase,
+        "indexed": done,
+        "total": total,
+        "file": file,
+        "outcome": outcome,
+        "progress": progress
+    })) {
+        error!("❌ Event emit hatası: {}", e);
+    }
+}
+
+fn outcome_label(outcome: &Result<IndexOutcome, String>) -> &'static str {
+    match outcome {
+        Ok(IndexOutcome::Indexed(_)) => "indexed",
+        Ok(IndexOutcome::Unchanged) => "unchanged",
+        Ok(IndexOutcome::Removed) => "removed",
+        Ok(IndexOutcome::Skipped) => "skipped",
+        Err(_) => "error",
+    }
+}
+
+/// Index a list of paths, emitting one progress event per path
+async fn index_paths(app: &AppHandle, root: &str, phase: &str, paths: Vec<PathBuf>, cancelled: &AtomicBool) {
+    let db = match app.try_state::<VectorDB>() {
+        Some(db) => db,
+        None => {
+            warn!("⚠️ Vector DB kapalı, indeksleme atlandı");
+            return;
+        }
+    };
+
+    let total = paths.len();
+    let mut changed = 0;
+    for (i, path) in paths.iter().enumerate() {
+        if cancelled.load(Ordering::Relaxed) {
+            info!("⏹️ İndeksleme iptal edildi: {}", root);
+            return;
+        }
+
+        let outcome = index_path(&db, path).await;
+        if let Err(e) = &outcome {
+            error!("❌ İndeksleme hatası [{}]: {}", path.display(), e);
+        }
+        if matches!(outcome, Ok(IndexOutcome::Indexed(_)) | Ok(IndexOutcome::Removed)) {
+            changed += 1;
+        }
+
+        emit_progress(app, root, phase, i + 1, total, &path.to_string_lossy(), outcome_label(&outcome));
+    }
+
+    info!("✅ {} indeksleme: {} dosya, {} değişti", phase, total, changed);
+}
+
+/// Expand watched paths: directories become the indexable files below them
+fn expand_changed_paths(paths: HashSet<PathBuf>) -> Vec<PathBuf> {
+    let mut expanded = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            expanded.extend(collect_workspace_files(&path));
+        } else {
+            expanded.push(path);
+        }
+    }
+    expanded.sort();
+    expanded.dedup();
+    expanded
+}
+
+/// Start indexing a workspace and keep it in sync with file changes
+///
+/// Runs an initial walk, then watches for create/modify/delete/rename events.
+/// Only files whose content hash changed are re-embedded.
+#[tauri::command]
+pub async fn start_workspace_indexer(
+    root: String,
+    app: AppHandle,
+    state: State<'_, WorkspaceIndexerState>,
+) -> Result<(), String> {
+    info!("🗂️ Workspace indexer başlatılıyor: {}", root);
+
+    if app.try_state::<VectorDB>().is_none() {
+        return Err("Vector DB başlatılmamış, önce init_vector_db çağırın".to_string());
+    }
+
+    let root_path = PathBuf::from(&root);
+    if !root_path.is_dir() {
+        return Err(format!("Klasör bulunamadı: {}", root));
+    }
+
+    if state.indexers.lock().map_err(|e| e.to_string())?.contains_key(&root) {
+        info!("ℹ️ Indexer zaten çalışıyor: {}", root);
+        return Ok(());
+    }
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+    let watch_root = root_path.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        match res {
+            Ok(event) => {
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                for path in event.paths {
+                    if is_ignored_path(&watch_root, &path) {
+                        continue;
+                    }
+                    // Extension-less paths may be (removed) directories
+                    if is_indexable(&path) || path.extension().is_none() {
+                        let _ = tx.send(path);
+                    }
+                }
+            }
+            Err(e) => error!("❌ Dosya izleme hatası: {}", e),
+        }
+    })
+    .map_err(|e| format!("Dosya izleyici oluşturulamadı: {}", e))?;
+
+    watcher
+        .watch(&root_path, RecursiveMode::Recursive)
+        .map_err(|e| format!("Klasör izlenemedi: {}", e))?;
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    state.indexers.lock().map_err(|e| e.to_string())?.insert(
+        root.clone(),
+        IndexerHandle {
+            _watcher: watcher,
+            cancelled: cancelled.clone(),
+        },
+    );
+
+    tokio::spawn(async move {
+        let files = collect_workspace_files(&root_path);
+        info!("📂 {} dosya indekslenecek: {}", files.len(), root);
+        index_paths(&app, &root, "initial", files, &cancelled).await;
+
+        // Events queued during the initial walk are handled here too
+        while let Some(first) = rx.recv().await {
+            if cancelled.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let mut pending = HashSet::from([first]);
+            while let Ok(Some(path)) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {
+                pending.insert(path);
+            }
+
+            index_paths(&app, &root, "watch", expand_changed_paths(pending), &cancelled).await;
+        }
+
+        info!("🛑 Workspace indexer durdu: {}", root);
+    });
+
+    Ok(())
+}
+
+/// Stop watching a workspace; an in-flight walk stops at the next file
+#[tauri::command]
+pub async fn stop_workspace_indexer(
+    root: String,
+    state: State<'_, WorkspaceIndexerState>,
+) -> Result<(), String> {
+    let handle = state.indexers.lock().map_err(|e| e.to_string())?.remove(&root);
+    match handle {
+        Some(handle) => {
+            handle.cancelled.store(true, Ordering::Relaxed);
+            info!("🛑 Workspace indexer durduruluyor: {}", root);
+            Ok(())
+        }
+        None => Err(format!("Indexer bulunamadı: {}", root)),
+    }
+}
+
+/// Workspace roots that currently have an indexer running
+#[tauri::command]
+pub async fn list_workspace_indexers(state: State<'_, WorkspaceIndexerState>) -> Result<Vec<String>, String> {
+    let indexers = state.indexers.lock().map_err(|e| e.to_string())?;
+    Ok(indexers.keys().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_indexable() {
+        assert!(is_indexable(Path::new("src/main.rs")));
+        assert!(is_indexable(Path::new("web/App.TSX")));
+        assert!(!is_indexable(Path::new("assets/logo.png")));
+        assert!(!is_indexable(Path::new(".corex/vectors/chunks.sqlite3")));
+    }
+
+    #[test]
+    fn test_ignored_paths_are_relative_to_root() {
+        let root = Path::new("/home/dev/build/app");
+        assert!(!is_ignored_path(root, Path::new("/home/dev/build/app/src/main.rs")));
+        assert!(is_ignored_path(root, Path::new("/home/dev/build/app/node_modules/x/index.js")));
+        assert!(is_ignored_path(root, Path::new("/home/dev/build/app/target")));
+    }
+
+    #[test]
+    fn test_content_hash_changes_with_content() {
+        assert_eq!(content_hash("fn a() {}"), content_hash("fn a() {}"));
+        assert_ne!(content_hash("fn a() {}"), content_hash("fn b() {}"));
+    }
+}
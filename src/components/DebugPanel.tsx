@@ -1,5 +1,6 @@
 import { useState, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { breakpointService } from '../services/debug/breakpointService';
 
 interface Breakpoint {
   id: string;
@@ -44,6 +45,11 @@ export default function DebugPanel({ projectPath, currentFile, onBreakpointToggl
   const [debugCommand, setDebugCommand] = useState('');
   const consoleRef = useRef<HTMLDivElement>(null);
 
+  // Backend breakpoint'leri bu workspace'e yazılır
+  useEffect(() => {
+    breakpointService.setWorkspace(projectPath);
+  }, [projectPath]);
+
   // Load breakpoints from localStorage
   useEffect(() => {
     const saved = localStorage.getItem(`corex-breakpoints-${projectPath}`);
@@ -1,6 +1,7 @@
 import { useState, useEffect } from 'react';
 import { useLanguage } from '../contexts/LanguageContext';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 
 interface Container {
   id: string;
@@ -21,28 +22,92 @@ interface Image {
   created?: number;
 }
 
-interface DockerCompose {
-  id: string;
+interface ComposeService {
   name: string;
-  services: string[];
-  status: 'running' | 'stopped';
+  image: string | null;
+  build: { context: string; dockerfile: string | null; target: string | null } | null;
+  ports: { host_ip: string | null; published: string | null; target: string; protocol: string }[];
+  depends_on: { service: string; condition: string }[];
+  profiles: string[];
+  enabled: boolean;
+  status: string;
+  containers: { id: string; name: string; state: string; status: string }[];
   file: string;
+  line: number;
+}
+
+interface ComposeProject {
+  name: string;
+  working_dir: string;
+  files: string[];
+  services: ComposeService[];
+  diagnostics: { file: string; line: number; message: string }[];
+}
+
+interface DevContainerStatus {
+  session: { container_id: string; image: string; workspace_folder: string } | null;
+  config: { name: string | null } | null;
+  error: string | null;
 }
 
-export default function DockerIntegration() {
+interface DockerIntegrationProps {
+  projectPath?: string;
+}
+
+export default function DockerIntegration({ projectPath }: DockerIntegrationProps) {
   const { t } = useLanguage();
   const [containers, setContainers] = useState<Container[]>([]);
   const [images, setImages] = useState<Image[]>([]);
-  const [composeProjects, setComposeProjects] = useState<DockerCompose[]>([]);
+  const [composeProjects, setComposeProjects] = useState<ComposeProject[]>([]);
   const [activeTab, setActiveTab] = useState<'containers' | 'images' | 'compose'>('containers');
   const [isDockerRunning, setIsDockerRunning] = useState(false);
   const [selectedContainer, setSelectedContainer] = useState<string | null>(null);
-  const [logs] = useState<Record<string, string[]>>({}); // Placeholder for now
+  const [logs, setLogs] = useState<Record<string, string[]>>({});
+  const [devContainer, setDevContainer] = useState<DevContainerStatus | null>(null);
+  const [devContainerBusy, setDevContainerBusy] = useState(false);
+  const [devContainerLine, setDevContainerLine] = useState('');
 
   useEffect(() => {
     loadDockerData();
+    loadDevContainer();
+  }, [projectPath]);
+
+  useEffect(() => {
+    const unlisten = listen<{ stage: string; line: string }>('devcontainer-progress', event => {
+      setDevContainerLine(`[${event.payload.stage}] ${event.payload.line}`);
+    });
+    return () => { unlisten.then(stop => stop()); };
   }, []);
 
+  // Seçili container'ın loglarını canlı izle, modal kapanınca akışı durdur
+  useEffect(() => {
+    if (!selectedContainer) return;
+    const container = selectedContainer;
+    let streamId: string | null = null;
+    let cancelled = false;
+    setLogs(prev => ({ ...prev, [container]: [] }));
+
+    // stream id'si invoke dönmeden ilk satırlar gelebilir, container'a göre süz
+    const unlisten = listen<{ container: string; text: string }>('docker-log', event => {
+      if (event.payload.container !== container) return;
+      const lines = event.payload.text.split('\n').filter(line => line.length > 0);
+      setLogs(prev => ({ ...prev, [container]: [...(prev[container] || []), ...lines].slice(-1000) }));
+    });
+
+    invoke<string>('docker_follow_logs', { id: container, tail: 200 })
+      .then(id => {
+        streamId = id;
+        if (cancelled) invoke('docker_stop_stream', { streamId: id });
+      })
+      .catch(error => console.error('Failed to follow logs:', error));
+
+    return () => {
+      cancelled = true;
+      unlisten.then(stop => stop());
+      if (streamId) invoke('docker_stop_stream', { streamId });
+    };
+  }, [selectedContainer]);
+
   const loadDockerData = async () => {
     try {
       const containerList = await invoke<Container[]>('docker_list_containers');
@@ -53,16 +118,7 @@ export default function DockerIntegration() {
 
       setIsDockerRunning(true);
 
-      // Keep mock compose for now as it's not implemented in backend
-      setComposeProjects([
-        {
-          id: 'comp_1',
-          name: 'web-app',
-          services: ['web', 'db', 'redis'],
-          status: 'running',
-          file: 'docker-compose.yml'
-        }
-      ]);
+      await loadComposeProjects();
 
     } catch (error) {
       console.error('Docker data load failed:', error);
@@ -70,6 +126,70 @@ export default function DockerIntegration() {
     }
   };
 
+  const loadDevContainer = async () => {
+    if (!projectPath) {
+      setDevContainer(null);
+      return;
+    }
+    try {
+      setDevContainer(await invoke<DevContainerStatus>('devcontainer_status', { workspace: projectPath }));
+    } catch (error) {
+      console.error('Dev container status failed:', error);
+    }
+  };
+
+  const toggleDevContainer = async () => {
+    if (!projectPath) return;
+    setDevContainerBusy(true);
+    try {
+      if (devContainer?.session) {
+        await invoke('devcontainer_close', { workspace: projectPath, stop: false });
+      } else {
+        await invoke('devcontainer_open', { workspace: projectPath });
+      }
+      await loadDevContainer();
+      await loadDockerData();
+    } catch (error) {
+      console.error('Dev container failed:', error);
+      alert(`Dev Container Hatası: ${error}`);
+    } finally {
+      setDevContainerBusy(false);
+      setDevContainerLine('');
+    }
+  };
+
+  const loadComposeProjects = async () => {
+    if (!projectPath) {
+      setComposeProjects([]);
+      return;
+    }
+    const fileSets = await invoke<string[][]>('docker_compose_find', { workspace: projectPath });
+    const projects = await Promise.all(fileSets.map(files =>
+      invoke<ComposeProject>('docker_compose_project', { files }).catch(error => {
+        console.error('Compose project load failed:', error);
+        return null;
+      })
+    ));
+    setComposeProjects(projects.filter((p): p is ComposeProject => p !== null));
+  };
+
+  const runComposeAction = async (project: ComposeProject, action: string, service?: string) => {
+    try {
+      await invoke('docker_compose_action', { files: project.files, action, service });
+      await loadDockerData();
+    } catch (error) {
+      console.error(`Compose action ${action} failed:`, error);
+      alert(`Compose Hatası: ${error}`);
+    }
+  };
+
+  const getServiceIcon = (status: string) => {
+    if (status === 'running') return '🟢';
+    if (status === 'exited' || status === 'dead') return '🔴';
+    if (status === 'paused' || status === 'restarting') return '🟡';
+    return '⚪';
+  };
+
   const getStatusColor = (status: string) => {
     const s = status.toLowerCase();
     if (s.includes('up')) return 'text-green-500';
@@ -124,7 +244,7 @@ export default function DockerIntegration() {
 
   const ContainerActions = ({ container }: { container: Container }) => (
     <div className="flex gap-1">
-      {container.status === 'stopped' ? (
+      {container.state !== 'running' ? (
         <button
           onClick={() => startContainer(container.id)}
           className="px-2 py-1 bg-green-600 text-white rounded text-xs hover:opacity-80"
@@ -179,6 +299,33 @@ export default function DockerIntegration() {
           </span>
         </div>
 
+        {/* Dev Container */}
+        {(devContainer?.config || devContainer?.error) && (
+          <div className="mb-3 p-2 border border-[var(--color-border)] rounded text-sm">
+            <div className="flex items-center justify-between gap-2">
+              <span className="truncate">
+                📦 {devContainer.config?.name || 'Dev Container'}
+                {devContainer.session && (
+                  <span className="text-[var(--color-textSecondary)]"> · {devContainer.session.workspace_folder}</span>
+                )}
+              </span>
+              {devContainer.config && (
+                <button
+                  onClick={toggleDevContainer}
+                  disabled={devContainerBusy}
+                  className="px-2 py-1 bg-[var(--color-primary)] text-white rounded text-xs hover:opacity-80 disabled:opacity-50 whitespace-nowrap"
+                >
+                  {devContainerBusy ? '⏳' : devContainer.session ? '🔌 Leave Container' : '▶️ Open in Container'}
+                </button>
+              )}
+            </div>
+            {devContainer.error && <div className="mt-1 text-xs text-red-500">{devContainer.error}</div>}
+            {devContainerBusy && devContainerLine && (
+              <div className="mt-1 text-xs font-mono text-[var(--color-textSecondary)] truncate">{devContainerLine}</div>
+            )}
+          </div>
+        )}
+
         {/* Tabs */}
         <div className="flex gap-1">
           {(['containers', 'images', 'compose'] as const).map(tab => (
@@ -308,42 +455,93 @@ export default function DockerIntegration() {
 
             <div className="space-y-2">
               {composeProjects.map(project => (
-                <div key={project.id} className="p-3 bg-[var(--color-background)] border border-[var(--color-border)] rounded-lg">
+                <div key={project.files.join('|')} className="p-3 bg-[var(--color-background)] border border-[var(--color-border)] rounded-lg">
                   <div className="flex items-center justify-between mb-2">
                     <div className="flex items-center gap-3">
-                      <span>{getStatusIcon(project.status)}</span>
+                      <span>{project.services.some(s => s.status === 'running') ? '🟢' : '⚪'}</span>
                       <div>
                         <h4 className="font-medium">{project.name}</h4>
-                        <p className="text-sm text-[var(--color-textSecondary)]">{project.file}</p>
+                        <p className="text-sm text-[var(--color-textSecondary)]">
+                          {project.files.map(f => f.split(/[\\/]/).pop()).join(' + ')}
+                        </p>
                       </div>
                     </div>
                     <div className="flex gap-1">
-                      {project.status === 'stopped' ? (
-                        <button
-                          onClick={() => invoke('docker_compose_action', { path: project.file, action: 'up' }).then(loadDockerData)}
-                          className="px-2 py-1 bg-green-600 text-white rounded text-xs hover:opacity-80"
-                        >
-                          ▶️ Up
-                        </button>
-                      ) : (
-                        <button
-                          onClick={() => invoke('docker_compose_action', { path: project.file, action: 'down' }).then(loadDockerData)}
-                          className="px-2 py-1 bg-red-600 text-white rounded text-xs hover:opacity-80"
-                        >
-                          ⏹️ Down
-                        </button>
-                      )}
                       <button
-                        onClick={() => invoke('docker_compose_action', { path: project.file, action: 'restart' }).then(loadDockerData)}
-                        className="px-2 py-1 bg-blue-600 text-white rounded text-xs hover:opacity-80"
+                        onClick={() => runComposeAction(project, 'up')}
+                        className="px-2 py-1 bg-green-600 text-white rounded text-xs hover:opacity-80"
+                      >
+                        ▶️ Up
+                      </button>
+                      <button
+                        onClick={() => runComposeAction(project, 'down')}
+                        className="px-2 py-1 bg-red-600 text-white rounded text-xs hover:opacity-80"
                       >
-                        🔄 Restart
+                        ⏹️ Down
                       </button>
                     </div>
                   </div>
 
-                  <div className="text-sm text-[var(--color-textSecondary)]">
-                    <span className="font-medium">Services:</span> {project.services.join(', ')}
+                  {project.diagnostics.length > 0 && (
+                    <div className="mb-2 p-2 border border-red-600 rounded text-xs text-red-500 space-y-1">
+                      {project.diagnostics.map((d, index) => (
+                        <div key={index} title={d.file}>
+                          {d.file.split(/[\\/]/).pop()}:{d.line} — {d.message}
+                        </div>
+                      ))}
+                    </div>
+                  )}
+
+                  <div className="space-y-1">
+                    {project.services.map(service => (
+                      <div key={service.name} className={`flex items-center justify-between text-sm ${service.enabled ? '' : 'opacity-50'}`}>
+                        <div className="flex items-center gap-2">
+                          <span title={service.status}>{getServiceIcon(service.status)}</span>
+                          <span className="font-medium">{service.name}</span>
+                          <span className="text-xs text-[var(--color-textSecondary)]">
+                            {service.image ?? `build: ${service.build?.context}`}
+                            {service.ports.length > 0 && ` · ${service.ports.map(p => p.published ? `${p.published}:${p.target}` : p.target).join(', ')}`}
+                            {service.profiles.length > 0 && ` · [${service.profiles.join(', ')}]`}
+                          </span>
+                        </div>
+                        <div className="flex gap-1">
+                          {service.status === 'running' ? (
+                            <button
+                              onClick={() => runComposeAction(project, 'stop', service.name)}
+                              className="px-2 py-1 bg-yellow-600 text-white rounded text-xs hover:opacity-80"
+                              title="Stop"
+                            >
+                              ⏸️
+                            </button>
+                          ) : (
+                            <button
+                              onClick={() => runComposeAction(project, 'up', service.name)}
+                              className="px-2 py-1 bg-green-600 text-white rounded text-xs hover:opacity-80"
+                              title="Up"
+                            >
+                              ▶️
+                            </button>
+                          )}
+                          {service.build && (
+                            <button
+                              onClick={() => runComposeAction(project, 'build', service.name)}
+                              className="px-2 py-1 bg-blue-600 text-white rounded text-xs hover:opacity-80"
+                              title="Build"
+                            >
+                              🔨
+                            </button>
+                          )}
+                          <button
+                            onClick={() => service.containers[0] && setSelectedContainer(service.containers[0].id)}
+                            disabled={service.containers.length === 0}
+                            className="px-2 py-1 bg-gray-600 text-white rounded text-xs hover:opacity-80 disabled:opacity-40"
+                            title="Logs"
+                          >
+                            📋
+                          </button>
+                        </div>
+                      </div>
+                    ))}
                   </div>
                 </div>
               ))}
@@ -347,7 +347,7 @@ export default function SidePanel({
       case "tasks":
         return <TaskManager />;
       case "docker":
-        return <DockerIntegration />;
+        return <DockerIntegration projectPath={projectPath} />;
       case "mcp":
         return <MCPPanel />;
       case "tech-debt":
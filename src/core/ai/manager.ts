@@ -149,6 +149,13 @@ export class AIManager {
     performanceMonitor.start(`ai-stop-${requestId}`);
 
     try {
+      // Abort only stops the frontend; the backend keeps decoding until told to cancel
+      const { invoke } = await import('@tauri-apps/api/core');
+      await invoke<boolean>('cancel_generation', { requestId }).catch((error) => {
+        console.warn(`⚠️ AIManager: Backend cancel failed: ${requestId}`, error);
+        return false;
+      });
+
       const controller = this.activeRequests.get(requestId);
       if (controller) {
         controller.abort();
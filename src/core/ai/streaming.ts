@@ -175,7 +175,11 @@ export class StreamingHandler {
     let accumulated = '';
 
     // Listen to stream tokens
-    const unlisten = await listen<{ token: string, is_complete: boolean }>('stream-token', (event) => {
+    const unlisten = await listen<{ token: string, is_complete: boolean, request_id?: string }>('stream-token', (event) => {
+      // Other generations (queued or concurrent) share the same event
+      if (event.payload.request_id !== requestId) {
+        return;
+      }
       if (signal.aborted) {
         unlisten();
         return;
@@ -206,7 +210,8 @@ export class StreamingHandler {
           prompt: message,
           model_path: model,
           max_tokens: 2000,
-          temperature: 0.7
+          temperature: 0.7,
+          request_id: requestId
         }
       });
     } catch (e) {
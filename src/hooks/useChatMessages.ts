@@ -524,6 +524,9 @@ export function useChatMessages({
             const fullPrompt = `${projectMapText}${focusText}${contextText}\n\n${toolsPrompt}\n\nUser Message: ${userMessage}`;
             const modelId = getModelIdForRole();
             const conversationHistory = messages.map(m => ({ role: m.role, content: m.content }));
+            // GGUF KV cache oturumu bu sohbete ait: sohbetin ilk mesajının id'si onu tanımlar
+            // (yeni oturum yeni id ile başlar, aynı dalda açılan sohbetler birbirinin context'ini silmez)
+            const conversationId = `${projectPath}_${currentBranch}_${(messages[0] ?? userMsg).id}`;
 
             // 4. Placeholder for Assistant Response
             const msgId = generateMessageId("assistant");
@@ -536,7 +539,7 @@ export function useChatMessages({
                     setMessages((prev) =>
                         prev.map((msg) => (msg.id === msgId ? { ...msg, content: accumulatedResponse } : msg))
                     );
-                }, isMentorMode);
+                }, isMentorMode, conversationId);
 
                 // 🔧 TOOL SYSTEM - Parse and execute tools
                 const toolCallsOpt = parseToolCalls(accumulatedResponse);
@@ -623,7 +626,7 @@ export function useChatMessages({
                         setMessages((prev) =>
                             prev.map((msg) => (msg.id === nextMsgId ? { ...msg, content: accumulatedResponse } : msg))
                         );
-                    }, isMentorMode);
+                    }, isMentorMode, conversationId);
 
                     const nextToolCalls = parseToolCalls(accumulatedResponse);
                     toolCall = nextToolCalls.length > 0 ? nextToolCalls[0] : null;
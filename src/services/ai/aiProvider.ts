@@ -254,7 +254,8 @@ export async function callAI(
   modelId: string,
   conversationHistory?: Array<{ role: string; content: string }>,
   onStreamToken?: (text: string) => void, // 🆕 Streaming callback
-  isMentorMode: boolean = false
+  isMentorMode: boolean = false,
+  conversationId?: string // GGUF: aynı konuşmanın KV cache'i turlar arasında korunur
 ): Promise<string> {
   await acquireLock();
   try {
@@ -555,21 +556,30 @@ export async function callAI(
 
       // 🆕 Streaming desteği
       if (onStreamToken) {
-        const { chatWithChunkedStreaming } = await import('./streamingProvider');
+        const { chatWithChunkedStreaming, cancelGeneration } = await import('./streamingProvider');
+        const requestId = crypto.randomUUID();
         const streamPromise = chatWithChunkedStreaming(
           modelPath,
           fullPrompt,
           generationMaxTokens,
           model.temperature || 0.7,
           {
+            requestId,
+            conversationId,
             onToken: (delta) => {
               onStreamToken(delta);
             },
             onComplete: (text: string) => console.log('✅ Streaming tamamlandı:', text.length, 'karakter')
           }
         );
-        const response = await Promise.race([streamPromise, ggufTimeoutPromise]);
-        return sanitizeGgufResponse(response);
+        try {
+          const response = await Promise.race([streamPromise, ggufTimeoutPromise]);
+          return sanitizeGgufResponse(response);
+        } catch (error) {
+          // Zaman aşımında backend boşuna token üretmeye devam etmesin
+          await cancelGeneration(requestId).catch(() => false);
+          throw error;
+        }
       }
 
       // Normal (non-streaming) mode
@@ -579,7 +589,8 @@ export async function callAI(
           modelPath,
           fullPrompt,
           generationMaxTokens,
-          model.temperature || 0.7
+          model.temperature || 0.7,
+          conversationId
         );
       })();
 
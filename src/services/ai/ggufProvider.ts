@@ -42,7 +42,8 @@ export async function chatWithGgufModel(
   modelPath: string, // 🆕 Model path required
   prompt: string,
   maxTokens: number = 512,
-  temperature: number = 0.7
+  temperature: number = 0.7,
+  conversationId?: string
 ): Promise<string> {
   console.log('🔵 GGUF chat başlıyor...');
   console.log('📦 Model:', modelPath);
@@ -55,7 +56,8 @@ export async function chatWithGgufModel(
       modelPath, // 🆕 backend'e ilet
       prompt,
       maxTokens,
-      temperature
+      temperature,
+      conversationId: conversationId ?? null
     });
 
     console.log('✅ Yanıt alındı:', response.length, 'karakter');
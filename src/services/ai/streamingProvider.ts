@@ -1,96 +1,111 @@
 // Streaming AI Response Provider
 import { invoke } from "@tauri-apps/api/core";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
 
 export interface StreamingConfig {
   onToken?: (token: string) => void;
   onComplete?: (fullText: string) => void;
   onError?: (error: string) => void;
+  onStatus?: (status: GenerationStatusEvent) => void;
+  // cancelGeneration için; verilmezse üretilir
+  requestId?: string;
+  // Aynı konuşmanın turları tek context'i paylaşır, sadece yeni kısım decode edilir
+  conversationId?: string;
 }
 
-// Simulated streaming (chunk-based)
-// Gerçek streaming için Rust backend'de event emit gerekli
+// Backend sampler ayarları (hepsi opsiyonel, verilmeyenler varsayılanı kullanır)
+export interface SamplingParams {
+  temperature?: number;
+  top_k?: number;
+  top_p?: number;
+  min_p?: number;
+  repeat_penalty?: number;
+  repeat_last_n?: number;
+  seed?: number;
+}
+
+export type StopReason = "eos" | "length" | "cancelled";
+
+export interface GenerationStatusEvent {
+  request_id: string;
+  model_path: string;
+  status: "queued" | "running" | "cancelled" | "done" | "failed";
+  position?: number;
+  stop_reason?: StopReason;
+  tokens?: number;
+  error?: string;
+}
+
+interface StreamTokenPayload {
+  token: string;
+  is_complete: boolean;
+  stop_reason?: StopReason;
+  request_id?: string;
+}
+
+// Gerçek streaming: backend her token'ı üretildiği anda "stream-token" ile gönderir
 export async function chatWithStreaming(
   modelPath: string, // 🆕 Model path required
   prompt: string,
   maxTokens: number,
   temperature: number,
-  config: StreamingConfig
+  config: StreamingConfig,
+  sampling?: SamplingParams
 ): Promise<string> {
+  const requestId = config.requestId ?? crypto.randomUUID();
+  let unlistenToken: UnlistenFn | null = null;
+  let unlistenStatus: UnlistenFn | null = null;
+
   try {
+    unlistenToken = await listen<StreamTokenPayload>("stream-token", (event) => {
+      const { token, is_complete, request_id } = event.payload;
+      if (request_id === requestId && !is_complete && token) {
+        config.onToken?.(token);
+      }
+    });
+
+    unlistenStatus = await listen<GenerationStatusEvent>("generation-status", (event) => {
+      if (event.payload.request_id === requestId) {
+        config.onStatus?.(event.payload);
+      }
+    });
+
     const fullResponse = await invoke<string>('chat_with_gguf_model', {
       modelPath,
       prompt,
       maxTokens,
-      temperature
+      temperature,
+      sampling: sampling ?? null,
+      requestId,
+      conversationId: config.conversationId ?? null
     });
 
-    // 🔥 FIX: Her token'da sadece DELTA (yeni kısım) gönder, birikimli değil
-    if (config.onToken) {
-      const words = fullResponse.split(' ');
-
-      for (let i = 0; i < words.length; i++) {
-        // Sadece yeni kelimeyi gönder (delta)
-        const delta = (i > 0 ? ' ' : '') + words[i];
-        config.onToken(delta);
-
-        await new Promise(resolve => setTimeout(resolve, 30));
-      }
-    }
-
-    if (config.onComplete) {
-      config.onComplete(fullResponse);
-    }
-
+    config.onComplete?.(fullResponse);
     return fullResponse;
   } catch (error) {
     const errorMsg = error instanceof Error ? error.message : String(error);
-    if (config.onError) {
-      config.onError(errorMsg);
-    }
+    config.onError?.(errorMsg);
     throw error;
+  } finally {
+    unlistenToken?.();
+    unlistenStatus?.();
   }
 }
 
-// Chunk-based streaming (better simulation)
-export async function chatWithChunkedStreaming(
-  modelPath: string, // 🆕 Model path required
-  prompt: string,
-  maxTokens: number,
-  temperature: number,
-  config: StreamingConfig
-): Promise<string> {
-  try {
-    const fullResponse = await invoke<string>('chat_with_gguf_model', {
-      modelPath,
-      prompt,
-      maxTokens,
-      temperature
-    });
-
-    // 🔥 FIX: Birikimli累 metin yerine sadece DELTA chunk gönder
-    if (config.onToken) {
-      const chars = fullResponse.split('');
-      const chunkSize = 5; // 5 karakter birden
-
-      for (let i = 0; i < chars.length; i += chunkSize) {
-        // Sadece bu adımın yeni chunk'ını gönder (delta)
-        const delta = chars.slice(i, i + chunkSize).join('');
-        config.onToken(delta);
+// Eski isim korunuyor; artık simülasyon yok, gerçek token akışı kullanılıyor
+export const chatWithChunkedStreaming = chatWithStreaming;
 
-        await new Promise(resolve => setTimeout(resolve, 2));
-      }
-    }
+// Üretimi bir sonraki token'da durdur; kuyruktaysa hiç başlamadan çıkar
+export async function cancelGeneration(requestId: string): Promise<boolean> {
+  return await invoke<boolean>('cancel_generation', { requestId });
+}
 
-    if (config.onComplete) {
-      config.onComplete(fullResponse);
-    }
+// Bir modelde aynı anda kaç üretim çalışabileceği (modelPath yoksa varsayılan)
+export async function setGenerationConcurrency(limit: number, modelPath?: string): Promise<void> {
+  await invoke('set_generation_concurrency', { modelPath: modelPath ?? null, limit });
+}
 
-    return fullResponse;
-  } catch (error) {
-    const errorMsg = error instanceof Error ? error.message : String(error);
-    if (config.onError) {
-      config.onError(errorMsg);
-    }
-    throw error;
-  }
+// Konuşmanın bellekteki context'ini ve diskteki session dosyasını sil
+export async function forgetConversation(modelPath: string, conversationId: string): Promise<void> {
+  await invoke('forget_conversation', { modelPath, conversationId });
 }
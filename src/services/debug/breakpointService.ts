@@ -5,6 +5,15 @@ type BreakpointChangeListener = (breakpoints: Breakpoint[]) => void;
 class BreakpointService {
     private breakpoints: Map<string, Breakpoint[]> = new Map(); // file_path -> Breakpoints
     private listeners: Set<BreakpointChangeListener> = new Set();
+    private workspace = '';
+
+    // Proje değişince çağrılır; breakpoint'ler workspace'e bağlı
+    setWorkspace(workspace: string) {
+        if (workspace === this.workspace) return;
+        this.workspace = workspace;
+        this.breakpoints.clear();
+        this.notifyListeners();
+    }
 
     subscribe(listener: BreakpointChangeListener) {
         this.listeners.add(listener);
@@ -23,11 +32,11 @@ class BreakpointService {
         if (existingIndex >= 0) {
             // Remove
             const id = fileBps[existingIndex].id;
-            await debugAdapterBridge.removeBreakpoint(id);
+            await debugAdapterBridge.removeBreakpoint(this.workspace, id);
             fileBps.splice(existingIndex, 1);
         } else {
             // Add
-            const newBp = await debugAdapterBridge.setBreakpoint(path, line, condition);
+            const newBp = await debugAdapterBridge.setBreakpoint(this.workspace, path, line, condition);
             fileBps.push(newBp);
         }
 
@@ -46,7 +55,7 @@ class BreakpointService {
     async removeAllBreakpoints() {
         const all = this.getAllBreakpoints();
         for (const bp of all) {
-            await debugAdapterBridge.removeBreakpoint(bp.id);
+            await debugAdapterBridge.removeBreakpoint(this.workspace, bp.id);
         }
         this.breakpoints.clear();
         this.notifyListeners();
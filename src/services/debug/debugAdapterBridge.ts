@@ -23,8 +23,9 @@ export interface Variable {
 }
 
 export const debugAdapterBridge = {
-    async startSession(config: DebugConfig): Promise<string> {
+    async startSession(workspace: string, config: DebugConfig): Promise<string> {
         return await invoke<string>('start_debug_session', {
+            workspace,
             config: {
                 ...config,
                 type_: config.type
@@ -32,12 +33,13 @@ export const debugAdapterBridge = {
         });
     },
 
-    async setBreakpoint(path: string, line: number, condition?: string): Promise<Breakpoint> {
-        return await invoke<Breakpoint>('set_breakpoint', { path, line, condition });
+    // Breakpoint'ler workspace başına .corex altında saklanır
+    async setBreakpoint(workspace: string, path: string, line: number, condition?: string): Promise<Breakpoint> {
+        return await invoke<Breakpoint>('set_breakpoint', { workspace, path, line, condition });
     },
 
-    async removeBreakpoint(id: string): Promise<void> {
-        return await invoke<void>('remove_breakpoint', { id });
+    async removeBreakpoint(workspace: string, id: string): Promise<void> {
+        return await invoke<void>('remove_breakpoint', { workspace, id });
     },
 
     async continue(sessionId: string): Promise<void> {
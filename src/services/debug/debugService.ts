@@ -6,14 +6,14 @@ class DebugService {
     private listeners: Set<(state: DebugState) => void> = new Set();
     private activeConsoleOutput: string[] = [];
 
-    async startDebugging(config: DebugConfig) {
+    async startDebugging(workspace: string, config: DebugConfig) {
         if (this.currentSession && this.currentSession.state !== 'inactive') {
             await this.currentSession.stop();
         }
 
         this.currentSession = new DebugSession();
         this.currentSession.subscribe(this.handleSessionEvent.bind(this));
-        await this.currentSession.start(config);
+        await this.currentSession.start(workspace, config);
     }
 
     async stopDebugging() {
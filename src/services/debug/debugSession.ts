@@ -36,11 +36,11 @@ export class DebugSession {
     get id() { return this._id; }
     get config() { return this._config; }
 
-    async start(config: DebugConfig) {
+    async start(workspace: string, config: DebugConfig) {
         this.setState('initializing');
         this._config = config;
         try {
-            this._id = await debugAdapterBridge.startSession(config);
+            this._id = await debugAdapterBridge.startSession(workspace, config);
             this.setState('running');
             this.emit({ type: 'output', data: `Session started: ${config.name}\n` });
 
@@ -3,6 +3,7 @@
 
 import { invoke } from "@tauri-apps/api/core";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import type { SamplingParams } from "./ai/streamingProvider";
 
 export interface StreamingCallbacks {
   onStart?: () => void;
@@ -12,9 +13,25 @@ export interface StreamingCallbacks {
 }
 
 export interface StreamingRequest {
+  model_path?: string;
   prompt: string;
   max_tokens?: number;
   temperature?: number;
+  sampling?: SamplingParams;
+  request_id?: string;
+  conversation_id?: string;
+}
+
+// Backend event'leri request_id taşır; aynı anda birden fazla akış olabilir
+interface StreamTokenEvent {
+  token: string;
+  is_complete: boolean;
+  request_id?: string;
+}
+
+interface StreamBoundaryEvent {
+  request_id: string;
+  text?: string;
 }
 
 /**
@@ -25,41 +42,48 @@ export async function chatWithRealStreaming(
   request: StreamingRequest,
   callbacks: StreamingCallbacks
 ): Promise<string> {
+  const requestId = request.request_id ?? crypto.randomUUID();
   let fullResponse = "";
   let unlistenToken: UnlistenFn | null = null;
   let unlistenComplete: UnlistenFn | null = null;
   let unlistenStart: UnlistenFn | null = null;
 
   try {
-    // Setup event listeners
-    unlistenStart = await listen("stream-start", () => {
+    // Setup event listeners (sadece bu isteğin event'leri)
+    unlistenStart = await listen<StreamBoundaryEvent>("stream-start", (event) => {
+      if (event.payload.request_id !== requestId) return;
       console.log("🌊 Stream started");
       callbacks.onStart?.();
     });
 
-    unlistenToken = await listen<{ token: string; is_complete: boolean }>(
+    unlistenToken = await listen<StreamTokenEvent>(
       "stream-token",
       (event) => {
-        const { token, is_complete } = event.payload;
+        const { token, is_complete, request_id } = event.payload;
 
-        if (!is_complete && token) {
+        if (request_id === requestId && !is_complete && token) {
           fullResponse += token;
           callbacks.onToken?.(token);
         }
       }
     );
 
-    unlistenComplete = await listen<string>("stream-complete", (event) => {
+    unlistenComplete = await listen<StreamBoundaryEvent>("stream-complete", (event) => {
+      if (event.payload.request_id !== requestId) return;
       console.log("✅ Stream complete");
-      callbacks.onComplete?.(event.payload);
+      callbacks.onComplete?.(event.payload.text ?? fullResponse);
     });
 
     // Start streaming
     const result = await invoke<string>("chat_with_streaming", {
       request: {
+        model_path: request.model_path ?? null,
         prompt: request.prompt,
         max_tokens: request.max_tokens || 2000,
         temperature: request.temperature || 0.7,
+        sampling: request.sampling ?? null,
+        request_id: requestId,
+        conversation_id: request.conversation_id ?? null,
       },
     });
 
@@ -85,33 +109,36 @@ export async function chatWithHttpStreaming(
   request: StreamingRequest,
   callbacks: StreamingCallbacks
 ): Promise<string> {
+  const requestId = request.request_id ?? crypto.randomUUID();
   let fullResponse = "";
   let unlistenToken: UnlistenFn | null = null;
   let unlistenComplete: UnlistenFn | null = null;
   let unlistenStart: UnlistenFn | null = null;
 
   try {
-    // Setup event listeners
-    unlistenStart = await listen("stream-start", () => {
+    // Setup event listeners (sadece bu isteğin event'leri)
+    unlistenStart = await listen<StreamBoundaryEvent>("stream-start", (event) => {
+      if (event.payload.request_id !== requestId) return;
       console.log("🌊 HTTP Stream started");
       callbacks.onStart?.();
     });
 
-    unlistenToken = await listen<{ token: string; is_complete: boolean }>(
+    unlistenToken = await listen<StreamTokenEvent>(
       "stream-token",
       (event) => {
-        const { token, is_complete } = event.payload;
+        const { token, is_complete, request_id } = event.payload;
 
-        if (!is_complete && token) {
+        if (request_id === requestId && !is_complete && token) {
           fullResponse += token;
           callbacks.onToken?.(token);
         }
       }
     );
 
-    unlistenComplete = await listen<string>("stream-complete", (event) => {
+    unlistenComplete = await listen<StreamBoundaryEvent>("stream-complete", (event) => {
+      if (event.payload.request_id !== requestId) return;
       console.log("✅ HTTP Stream complete");
-      callbacks.onComplete?.(event.payload);
+      callbacks.onComplete?.(event.payload.text ?? fullResponse);
     });
 
     // Start HTTP streaming
@@ -121,6 +148,7 @@ export async function chatWithHttpStreaming(
         prompt: request.prompt,
         max_tokens: request.max_tokens || 2000,
         temperature: request.temperature || 0.7,
+        request_id: requestId,
       },
     });
 
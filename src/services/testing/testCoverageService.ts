@@ -1,21 +1,48 @@
 import { invoke } from '@tauri-apps/api/core';
 
+export interface CoverageTotals {
+    lines_found: number;
+    lines_hit: number;
+    branches_found: number;
+    branches_hit: number;
+    line_percent: number;
+    branch_percent: number;
+}
+
+export interface FileCoverage {
+    file: string;
+    lines: { line: number; hits: number }[];
+    branches: { line: number; covered: number; total: number }[];
+    totals: CoverageTotals;
+}
+
+export interface CoverageReport {
+    source: string;
+    files: FileCoverage[];
+    totals: CoverageTotals;
+    delta: { line_percent: number; branch_percent: number; files: Record<string, number> } | null;
+}
+
 class TestCoverageService {
-    private coverageData: string = '';
+    private coverageData: CoverageReport | null = null;
 
-    async getCoverage(path: string = '.') {
+    // run: mevcut lcov/cobertura raporunu yok sayıp coverage aracını çalıştır
+    async getCoverage(path: string = '.', run: boolean = false) {
         try {
-            this.coverageData = await invoke<string>('get_code_coverage', { path });
-            // Bu lcov vs parsed edilip editor'a decoration olarak dönebilir.
+            this.coverageData = await invoke<CoverageReport>('get_code_coverage', { path, run });
             this.notifyCoverageUpdate();
         } catch (error) {
             console.error('Failed to get coverage:', error);
         }
     }
 
-    private subscribers = new Set<(data: string) => void>();
+    getFileCoverage(file: string): FileCoverage | undefined {
+        return this.coverageData?.files.find(f => file.replace(/\\/g, '/').endsWith(f.file));
+    }
+
+    private subscribers = new Set<(data: CoverageReport | null) => void>();
 
-    subscribe(listener: (data: string) => void) {
+    subscribe(listener: (data: CoverageReport | null) => void) {
         this.subscribers.add(listener);
         return () => { this.subscribers.delete(listener); };
     }
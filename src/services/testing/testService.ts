@@ -7,6 +7,8 @@ export interface TestCase {
     line: number;
     status: 'passed' | 'failed' | 'skipped' | 'pending';
     message?: string | null;
+    duration_ms?: number | null;
+    flaky?: boolean;
 }
 
 export interface TestSuite {
@@ -32,25 +34,44 @@ class TestService {
     private listeners = new Set<TestEventListener>();
     private runListeners = new Set<TestRunListener>();
     private isRunning = false;
+    private workspace = '.';
 
     async scanWorkspace(path: string = '.') {
+        this.workspace = path;
         try {
             const suites = await invoke<TestSuite[]>('scan_workspace_tests', { path });
             this.suites = suites;
             this.notifyListeners();
+            await this.loadCachedResults();
         } catch (error) {
             console.error('Failed to scan workspace tests:', error);
         }
     }
 
     async runSuite(suiteId: string) {
+        await this.execute('run_test_suite', { workspace: this.workspace, suiteId });
+    }
+
+    async runTest(testId: string) {
+        await this.execute('run_test', { workspace: this.workspace, testId });
+    }
+
+    async rerunFailed() {
+        await this.execute('rerun_failed_tests', { workspace: this.workspace });
+    }
+
+    async runAffected() {
+        await this.execute('run_affected_tests', { workspace: this.workspace });
+    }
+
+    private async execute(command: string, args: Record<string, unknown>) {
         if (this.isRunning) return;
 
         this.isRunning = true;
         this.notifyRunListeners('running');
 
         try {
-            const result = await invoke<any>('run_test_suite', { suiteId });
+            const result = await invoke<any>(command, args);
             // Tauri den snake_case dönüp biz camelCase beklemiyoruz ama rust struct serialize direkt dönüyor
             // Rust struct camelCase yapılabilirdi ama mapleyelim
             const mappedResult: TestRunResult = {
@@ -62,28 +83,44 @@ class TestService {
                 testCases: result.test_cases || result.testCases
             };
 
-            this.updateSuiteResults(suiteId, mappedResult.testCases);
+            this.updateSuiteResults(mappedResult.testCases);
             this.notifyRunListeners('idle', mappedResult);
         } catch (error) {
-            console.error('Failed to run test suite:', error);
+            console.error(`Failed to run tests (${command}):`, error);
             this.notifyRunListeners('idle');
         } finally {
             this.isRunning = false;
         }
     }
 
-    private updateSuiteResults(suiteId: string, results: TestCase[]) {
-        const suite = this.suites.find(s => s.id === suiteId);
-        if (suite) {
-            // Merge test results into the suite based on ID
-            suite.tests = suite.tests.map(test => {
-                const res = results.find(r => r.id === test.id);
-                return res ? { ...test, status: res.status, message: res.message } : test;
-            });
-            this.notifyListeners();
+    // Önceki oturumlardan kalan sonuçları taranan testlere uygula
+    private async loadCachedResults() {
+        try {
+            const cached = await invoke<TestCase[]>('get_cached_test_results', { workspace: this.workspace });
+            this.updateSuiteResults(cached.filter(c => c.status !== 'pending'));
+        } catch (error) {
+            console.error('Failed to load cached test results:', error);
         }
     }
 
+    private updateSuiteResults(results: TestCase[]) {
+        const byId = new Map(results.map(r => [r.id, r]));
+        // Merge test results into the suites based on ID
+        this.suites = this.suites.map(suite => ({
+            ...suite,
+            tests: suite.tests.map(test => {
+                const res = byId.get(test.id);
+                return res ? { ...test, status: res.status, message: res.message, duration_ms: res.duration_ms, flaky: res.flaky } : test;
+            })
+        }));
+        this.notifyListeners();
+    }
+
+    // En yavaş, en kararsız (flaky) ve en çok başarısız olan testler
+    async getInsights(limit: number = 10) {
+        return invoke<any>('get_test_insights', { workspace: this.workspace, limit });
+    }
+
     getSuites(): TestSuite[] {
         return this.suites;
     }